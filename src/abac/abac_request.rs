@@ -0,0 +1,133 @@
+use crate::database::instance_id::InstanceId;
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
+use serde_json::{json, Map, Value};
+
+/// Demande d'autorisation évaluée par les politiques ABAC.
+///
+/// Les attributs sont accessibles dans les conditions par leur chemin :
+/// `subject.id`, `resource.name`, `resource.id`, `action`, `context.hour`, `context.weekday`...
+#[derive(Clone, Debug, PartialEq)]
+pub struct AbacRequest {
+    subject: Map<String, Value>,
+    resource: Map<String, Value>,
+    action: String,
+    context: Map<String, Value>,
+}
+
+impl AbacRequest {
+    /// Crée la demande avec l'heure locale courante comme contexte temporel.
+    pub fn new(user_id: u64, resource_name: &str, action: &str) -> Self {
+        let mut subject = Map::new();
+        subject.insert("id".to_string(), json!(user_id));
+        let mut resource = Map::new();
+        resource.insert("name".to_string(), json!(resource_name));
+        Self {
+            subject,
+            resource,
+            action: action.to_string(),
+            context: Map::new(),
+        }
+        .with_time(Local::now())
+    }
+
+    pub fn with_instance_id(self, instance_id: &InstanceId) -> Self {
+        let value = match instance_id {
+            InstanceId::Int(id) => json!(id),
+            InstanceId::BigInt(id) => json!(id),
+            InstanceId::Uuid(id) => json!(id.to_string()),
+            InstanceId::Str(id) => json!(id),
+        };
+        self.with_resource_attribute("id", value)
+    }
+
+    /// Renseigne `context.hour`, `context.minute`, `context.time` (`"HH:MM"`)
+    /// et `context.weekday` (1 = lundi, 7 = dimanche).
+    pub fn with_time<Tz: TimeZone>(self, now: DateTime<Tz>) -> Self {
+        self.with_context_attribute("hour", json!(now.hour()))
+            .with_context_attribute("minute", json!(now.minute()))
+            .with_context_attribute(
+                "time",
+                json!(format!("{:02}:{:02}", now.hour(), now.minute())),
+            )
+            .with_context_attribute("weekday", json!(now.weekday().number_from_monday()))
+    }
+
+    pub fn with_subject_attribute(mut self, key: &str, value: Value) -> Self {
+        self.subject.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn with_resource_attribute(mut self, key: &str, value: Value) -> Self {
+        self.resource.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn with_context_attribute(mut self, key: &str, value: Value) -> Self {
+        self.context.insert(key.to_string(), value);
+        self
+    }
+
+    /// Ajoute des attributs sans écraser ceux déjà présents (ex: `subject.id`).
+    pub(crate) fn extend_subject(&mut self, attributes: Map<String, Value>) {
+        for (key, value) in attributes {
+            self.subject.entry(key).or_insert(value);
+        }
+    }
+
+    pub(crate) fn extend_resource(&mut self, attributes: Map<String, Value>) {
+        for (key, value) in attributes {
+            self.resource.entry(key).or_insert(value);
+        }
+    }
+
+    pub fn get_subject(&self) -> &Map<String, Value> {
+        &self.subject
+    }
+    pub fn get_resource(&self) -> &Map<String, Value> {
+        &self.resource
+    }
+    pub fn get_action(&self) -> &str {
+        &self.action
+    }
+    pub fn get_context(&self) -> &Map<String, Value> {
+        &self.context
+    }
+
+    pub fn get_resource_name(&self) -> &str {
+        self.resource
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    pub fn get_user_id(&self) -> u64 {
+        self.subject
+            .get("id")
+            .and_then(Value::as_u64)
+            .unwrap_or_default()
+    }
+
+    /// Valeur d'un attribut par son chemin pointé (`subject.commune`, `context.hour`).
+    pub fn get_attribute(&self, path: &str) -> Option<Value> {
+        let (root, rest) = match path.split_once('.') {
+            Some((root, rest)) => (root, Some(rest)),
+            None => (path, None),
+        };
+        let map = match root {
+            "action" => return rest.is_none().then(|| json!(self.action)),
+            "subject" => &self.subject,
+            "resource" => &self.resource,
+            "context" => &self.context,
+            _ => return None,
+        };
+        let Some(rest) = rest else {
+            return Some(Value::Object(map.clone()));
+        };
+        let mut segments = rest.split('.');
+        let mut value = map.get(segments.next()?)?;
+        for segment in segments {
+            value = value.get(segment)?;
+        }
+        Some(value.clone())
+    }
+}
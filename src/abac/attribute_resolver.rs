@@ -0,0 +1,21 @@
+use crate::database::errors::DatabaseError;
+use crate::database::instance_id::InstanceId;
+use async_trait::async_trait;
+use serde_json::{Map, Value};
+
+/// Fournit les attributs métier (commune de l'agent, commune de la demande...)
+/// que les politiques comparent. Les deux méthodes renvoient par défaut aucun attribut.
+#[async_trait]
+pub trait AttributeResolver: Send + Sync {
+    async fn subject_attributes(&self, _user_id: u64) -> Result<Map<String, Value>, DatabaseError> {
+        Ok(Map::new())
+    }
+
+    async fn resource_attributes(
+        &self,
+        _resource_name: &str,
+        _instance_id: Option<&InstanceId>,
+    ) -> Result<Map<String, Value>, DatabaseError> {
+        Ok(Map::new())
+    }
+}
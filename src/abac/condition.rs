@@ -0,0 +1,103 @@
+use crate::abac::AbacRequest;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    /// La valeur de gauche figure dans la liste de droite.
+    In,
+    /// La liste (ou chaîne) de gauche contient la valeur de droite.
+    Contains,
+}
+
+/// Opérande d'une comparaison : un attribut de la demande ou une valeur littérale.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Operand {
+    Attribute { attr: String },
+    Value(Value),
+}
+
+impl Operand {
+    fn resolve(&self, request: &AbacRequest) -> Option<Value> {
+        match self {
+            Operand::Attribute { attr } => request.get_attribute(attr),
+            Operand::Value(value) => Some(value.clone()),
+        }
+    }
+}
+
+/// Condition d'une politique, par exemple en TOML :
+/// `condition = { compare = { attr = "subject.commune", op = "eq", value = { attr = "resource.commune" } } }`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    Compare {
+        attr: String,
+        op: Operator,
+        value: Operand,
+    },
+}
+
+impl Condition {
+    /// Un attribut absent rend la comparaison fausse.
+    pub fn evaluate(&self, request: &AbacRequest) -> bool {
+        match self {
+            Condition::All(conditions) => conditions.iter().all(|c| c.evaluate(request)),
+            Condition::Any(conditions) => conditions.iter().any(|c| c.evaluate(request)),
+            Condition::Not(condition) => !condition.evaluate(request),
+            Condition::Compare { attr, op, value } => {
+                match (request.get_attribute(attr), value.resolve(request)) {
+                    (Some(left), Some(right)) => compare(&left, *op, &right),
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+fn order(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Number(l), Value::Number(r)) => l.as_f64()?.partial_cmp(&r.as_f64()?),
+        (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+        _ => None,
+    }
+}
+
+fn equals(left: &Value, right: &Value) -> bool {
+    order(left, right).map_or(left == right, |o| o == Ordering::Equal)
+}
+
+fn compare(left: &Value, op: Operator, right: &Value) -> bool {
+    match op {
+        Operator::Eq => equals(left, right),
+        Operator::Ne => !equals(left, right),
+        Operator::Lt => order(left, right) == Some(Ordering::Less),
+        Operator::Lte => matches!(order(left, right), Some(Ordering::Less | Ordering::Equal)),
+        Operator::Gt => order(left, right) == Some(Ordering::Greater),
+        Operator::Gte => matches!(
+            order(left, right),
+            Some(Ordering::Greater | Ordering::Equal)
+        ),
+        Operator::In => match right {
+            Value::Array(values) => values.iter().any(|v| equals(left, v)),
+            _ => false,
+        },
+        Operator::Contains => match (left, right) {
+            (Value::Array(values), _) => values.iter().any(|v| equals(v, right)),
+            (Value::String(l), Value::String(r)) => l.contains(r.as_str()),
+            _ => false,
+        },
+    }
+}
@@ -0,0 +1,21 @@
+use crate::database::errors::DatabaseError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PolicyError {
+    /// Le fichier de politiques n'a pas pu être lu.
+    #[error("Failed to read policy file: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Le contenu ne décrit pas un `PolicySet` valide.
+    #[error("Invalid policy definition: {0}")]
+    Parse(String),
+
+    /// Extension de fichier autre que `.toml` ou `.json`.
+    #[error("Unsupported policy file format: {0}")]
+    UnsupportedFormat(String),
+
+    /// Échec lors de la récupération des attributs du sujet ou de la ressource.
+    #[error("Attribute resolution failed: {0}")]
+    Resolver(#[from] DatabaseError),
+}
@@ -0,0 +1,20 @@
+mod errors;
+pub use errors::PolicyError;
+
+mod abac_request;
+pub use abac_request::AbacRequest;
+
+mod condition;
+pub use condition::{Condition, Operand, Operator};
+
+mod policy;
+pub use policy::{Effect, Policy};
+
+mod policy_set;
+pub use policy_set::PolicySet;
+
+mod attribute_resolver;
+pub use attribute_resolver::AttributeResolver;
+
+mod policy_engine;
+pub use policy_engine::{PolicyDecision, PolicyEngine};
@@ -0,0 +1,49 @@
+use crate::abac::{AbacRequest, Condition};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    Allow,
+    Deny,
+}
+
+/// Règle déclarative : s'applique aux `resources` et `actions` listées
+/// (`"*"` pour toutes) lorsque sa `condition` est vraie.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Policy {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    pub effect: Effect,
+    #[serde(default = "wildcard")]
+    pub resources: Vec<String>,
+    #[serde(default = "wildcard")]
+    pub actions: Vec<String>,
+    /// Sans condition, la politique s'applique dès que ressource et action correspondent.
+    #[serde(default)]
+    pub condition: Option<Condition>,
+}
+
+fn wildcard() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+fn matches_any(patterns: &[String], value: &str) -> bool {
+    patterns.iter().any(|p| p == "*" || p == value)
+}
+
+impl Policy {
+    pub fn targets(&self, request: &AbacRequest) -> bool {
+        matches_any(&self.resources, request.get_resource_name())
+            && matches_any(&self.actions, request.get_action())
+    }
+
+    pub fn applies_to(&self, request: &AbacRequest) -> bool {
+        self.targets(request)
+            && self
+                .condition
+                .as_ref()
+                .is_none_or(|condition| condition.evaluate(request))
+    }
+}
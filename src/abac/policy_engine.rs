@@ -0,0 +1,86 @@
+use crate::abac::{AbacRequest, AttributeResolver, Effect, PolicyError, PolicySet};
+use crate::database::instance_id::InstanceId;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyDecision {
+    Allow,
+    /// Refus, avec l'identifiant de la politique responsable (`None` : effet par défaut).
+    Deny {
+        policy_id: Option<String>,
+    },
+}
+
+impl PolicyDecision {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, PolicyDecision::Allow)
+    }
+}
+
+/// Évalue un `PolicySet` : un refus l'emporte sur toute autorisation,
+/// et l'effet par défaut s'applique quand aucune politique ne correspond.
+///
+/// Enregistré dans `AppState`, il est consulté par `access_guard_middleware`
+/// après la vérification en base, qui doit aussi accorder l'accès.
+#[derive(Clone, Default)]
+pub struct PolicyEngine {
+    policies: PolicySet,
+    resolver: Option<Arc<dyn AttributeResolver>>,
+}
+
+impl PolicyEngine {
+    pub fn new(policies: PolicySet) -> Self {
+        Self {
+            policies,
+            resolver: None,
+        }
+    }
+
+    pub fn with_resolver(mut self, resolver: Arc<dyn AttributeResolver>) -> Self {
+        self.resolver = Some(resolver);
+        self
+    }
+
+    pub fn get_policies(&self) -> &PolicySet {
+        &self.policies
+    }
+
+    pub fn evaluate(&self, request: &AbacRequest) -> PolicyDecision {
+        let mut allowed = false;
+        for policy in self.policies.policies.iter() {
+            if !policy.applies_to(request) {
+                continue;
+            }
+            match policy.effect {
+                Effect::Deny => {
+                    return PolicyDecision::Deny {
+                        policy_id: Some(policy.id.clone()),
+                    }
+                }
+                Effect::Allow => allowed = true,
+            }
+        }
+        if allowed || self.policies.default_effect == Effect::Allow {
+            PolicyDecision::Allow
+        } else {
+            PolicyDecision::Deny { policy_id: None }
+        }
+    }
+
+    /// Complète la demande avec les attributs du `AttributeResolver` puis l'évalue.
+    pub async fn authorize(
+        &self,
+        mut request: AbacRequest,
+        instance_id: Option<&InstanceId>,
+    ) -> Result<PolicyDecision, PolicyError> {
+        if let Some(resolver) = &self.resolver {
+            let subject = resolver.subject_attributes(request.get_user_id()).await?;
+            let resource = resolver
+                .resource_attributes(request.get_resource_name(), instance_id)
+                .await?;
+            request.extend_subject(subject);
+            request.extend_resource(resource);
+        }
+        Ok(self.evaluate(&request))
+    }
+}
@@ -0,0 +1,59 @@
+use crate::abac::{Effect, Policy, PolicyError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Ensemble de politiques chargé depuis un fichier TOML ou JSON :
+///
+/// ```toml
+/// default_effect = "allow"
+///
+/// [[policies]]
+/// id = "office-hours"
+/// effect = "deny"
+/// resources = ["requests"]
+/// actions = ["write"]
+/// condition = { not = { compare = { attr = "context.hour", op = "in", value = [8, 9, 10, 11, 13, 14, 15, 16, 17] } } }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PolicySet {
+    /// Décision lorsqu'aucune politique ne s'applique. Par défaut `allow`,
+    /// les politiques venant restreindre ce que la base accorde déjà.
+    #[serde(default = "default_effect")]
+    pub default_effect: Effect,
+    #[serde(default)]
+    pub policies: Vec<Policy>,
+}
+
+fn default_effect() -> Effect {
+    Effect::Allow
+}
+
+impl Default for PolicySet {
+    fn default() -> Self {
+        Self {
+            default_effect: default_effect(),
+            policies: Vec::new(),
+        }
+    }
+}
+
+impl PolicySet {
+    pub fn from_toml(content: &str) -> Result<Self, PolicyError> {
+        toml::from_str(content).map_err(|e| PolicyError::Parse(e.to_string()))
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, PolicyError> {
+        serde_json::from_str(content).map_err(|e| PolicyError::Parse(e.to_string()))
+    }
+
+    /// Charge le fichier selon son extension (`.toml` ou `.json`).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, PolicyError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&content),
+            Some("json") => Self::from_json(&content),
+            _ => Err(PolicyError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::Serialize;
+use std::fmt::Display;
+
+/// Nature d'un événement de sécurité.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventType {
+    /// Token absent, invalide, expiré ou utilisateur inconnu
+    AuthenticationFailure,
+    /// Accès accordé à une route d'administration
+    AdminAccess,
+    /// Route d'administration refusée à un non-administrateur
+    AdminAccessDenied,
+    /// Droit refusé par la base, le moteur ReBAC, les politiques ABAC ou la commune
+    PermissionDenied,
+    /// Connexion refusée par le verrouillage anti force brute
+    LoginLocked,
+    /// Événement propre à une API
+    Other(String),
+}
+
+impl AuditEventType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuditEventType::AuthenticationFailure => "authentication_failure",
+            AuditEventType::AdminAccess => "admin_access",
+            AuditEventType::AdminAccessDenied => "admin_access_denied",
+            AuditEventType::PermissionDenied => "permission_denied",
+            AuditEventType::LoginLocked => "login_locked",
+            AuditEventType::Other(name) => name,
+        }
+    }
+}
+
+impl From<&str> for AuditEventType {
+    fn from(value: &str) -> Self {
+        match value {
+            "authentication_failure" => AuditEventType::AuthenticationFailure,
+            "admin_access" => AuditEventType::AdminAccess,
+            "admin_access_denied" => AuditEventType::AdminAccessDenied,
+            "permission_denied" => AuditEventType::PermissionDenied,
+            "login_locked" => AuditEventType::LoginLocked,
+            other => AuditEventType::Other(other.to_string()),
+        }
+    }
+}
+
+impl Display for AuditEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Événement de la piste d'audit. Ne doit jamais contenir de secret (token, mot de passe).
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AuditEvent {
+    pub event_type: AuditEventType,
+    /// Tronqué à la microseconde, la précision de `timestamptz`
+    pub occurred_at: DateTime<Utc>,
+    pub user_id: Option<u64>,
+    pub ip: Option<String>,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub resource: Option<String>,
+    pub action: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl AuditEvent {
+    pub fn new(event_type: AuditEventType) -> Self {
+        Self {
+            event_type,
+            occurred_at: Utc::now().trunc_subsecs(6),
+            user_id: None,
+            ip: None,
+            method: None,
+            path: None,
+            resource: None,
+            action: None,
+            reason: None,
+        }
+    }
+
+    pub fn with_occurred_at(mut self, occurred_at: DateTime<Utc>) -> Self {
+        self.occurred_at = occurred_at.trunc_subsecs(6);
+        self
+    }
+
+    pub fn with_user_id(mut self, user_id: u64) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn with_ip(mut self, ip: &str) -> Self {
+        self.ip = Some(ip.to_string());
+        self
+    }
+
+    pub fn with_request(mut self, method: &str, path: &str) -> Self {
+        self.method = Some(method.to_string());
+        self.path = Some(path.to_string());
+        self
+    }
+
+    pub fn with_target(mut self, resource: &str, action: &str) -> Self {
+        self.resource = Some(resource.to_string());
+        self.action = Some(action.to_string());
+        self
+    }
+
+    pub fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+        self
+    }
+
+    /// Représentation stable hachée dans la chaîne d'audit.
+    pub fn canonical(&self) -> String {
+        serde_json::json!([
+            self.event_type.as_str(),
+            self.occurred_at
+                .to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+            self.user_id,
+            self.ip,
+            self.method,
+            self.path,
+            self.resource,
+            self.action,
+            self.reason,
+        ])
+        .to_string()
+    }
+}
@@ -0,0 +1,54 @@
+use crate::audit::{AuditEvent, AuditSink};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// Nombre d'événements en attente d'écriture au-delà duquel les suivants sont abandonnés.
+pub const AUDIT_QUEUE_CAPACITY: usize = 1024;
+
+/// File bornée vidée par une tâche de fond qui écrit dans l'`AuditSink`.
+/// La tâche est lancée au premier événement, dans le runtime de la requête,
+/// et se termine quand l'`AppState` est libéré.
+pub(crate) struct AuditQueue {
+    sink: Arc<dyn AuditSink>,
+    sender: OnceLock<mpsc::Sender<AuditEvent>>,
+}
+
+impl AuditQueue {
+    pub(crate) fn new(sink: Arc<dyn AuditSink>) -> Self {
+        Self {
+            sink,
+            sender: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn sink(&self) -> &Arc<dyn AuditSink> {
+        &self.sink
+    }
+
+    /// Met l'événement en file sans attendre. File pleine : l'événement est abandonné
+    /// et journalisé, pour qu'un afflux de requêtes ne bloque pas sur l'écriture.
+    pub(crate) fn push(&self, event: AuditEvent) {
+        let sender = self.sender.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel(AUDIT_QUEUE_CAPACITY);
+            tokio::spawn(drain(self.sink.clone(), receiver));
+            sender
+        });
+        match sender.try_send(event) {
+            Ok(()) => {}
+            Err(TrySendError::Full(event)) => {
+                tracing::warn!(event_type = %event.event_type, "audit queue full, event dropped");
+            }
+            Err(TrySendError::Closed(event)) => {
+                tracing::error!(event_type = %event.event_type, "audit writer stopped, event dropped");
+            }
+        }
+    }
+}
+
+async fn drain(sink: Arc<dyn AuditSink>, mut receiver: mpsc::Receiver<AuditEvent>) {
+    while let Some(event) = receiver.recv().await {
+        if let Err(e) = sink.record(&event).await {
+            tracing::error!(event_type = %event.event_type, error = %e, "audit write error");
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use crate::audit::AuditEvent;
+use crate::database::errors::DatabaseError;
+use async_trait::async_trait;
+
+/// Destination des événements d'audit émis par les middlewares.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record(&self, event: &AuditEvent) -> Result<(), DatabaseError>;
+}
@@ -0,0 +1,13 @@
+use crate::audit::AuditEvent;
+use crate::pool::AppState;
+
+/// Transmet l'événement à l'`AuditSink` de l'`AppState`, s'il y en a un.
+/// Un échec d'écriture est journalisé sans faire échouer la requête.
+pub async fn emit_audit_event(state: &AppState, event: AuditEvent) {
+    let Some(sink) = state.audit_sink() else {
+        return;
+    };
+    if let Err(e) = sink.record(&event).await {
+        tracing::error!(event_type = %event.event_type, error = %e, "audit write error");
+    }
+}
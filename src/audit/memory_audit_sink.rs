@@ -0,0 +1,31 @@
+use crate::audit::{AuditEvent, AuditSink};
+use crate::database::errors::DatabaseError;
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex};
+
+/// Événements conservés en mémoire, pour les tests. Les clones partagent la même liste.
+#[derive(Clone, Default)]
+pub struct MemoryAuditSink {
+    events: Arc<Mutex<Vec<AuditEvent>>>,
+}
+
+impl MemoryAuditSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events.lock().map(|e| e.clone()).unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl AuditSink for MemoryAuditSink {
+    async fn record(&self, event: &AuditEvent) -> Result<(), DatabaseError> {
+        self.events
+            .lock()
+            .map_err(|_| DatabaseError::Internal("Audit sink poisoned".to_string()))?
+            .push(event.clone());
+        Ok(())
+    }
+}
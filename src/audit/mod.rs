@@ -0,0 +1,26 @@
+mod audit_event;
+pub use audit_event::{AuditEvent, AuditEventType};
+
+mod audit_sink;
+pub use audit_sink::AuditSink;
+
+mod memory_audit_sink;
+pub use memory_audit_sink::MemoryAuditSink;
+
+mod pg_audit_sink;
+pub use pg_audit_sink::{install_audit_log_table, verify_audit_chain, PgAuditSink, AUDIT_LOG_SQL};
+
+mod audit_queue;
+pub(crate) use audit_queue::AuditQueue;
+pub use audit_queue::AUDIT_QUEUE_CAPACITY;
+
+mod emit_audit_event;
+pub use emit_audit_event::emit_audit_event;
+
+mod queue_audit_event;
+pub use queue_audit_event::queue_audit_event;
+
+#[cfg(feature = "actix")]
+mod request_event;
+#[cfg(feature = "actix")]
+pub(crate) use request_event::request_event;
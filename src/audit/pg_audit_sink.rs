@@ -0,0 +1,117 @@
+use crate::audit::{AuditEvent, AuditSink};
+use crate::database::errors::DatabaseError;
+use crate::database::queries::{
+    append_audit_event_query, last_audit_hash_query, list_audit_events_query,
+};
+use crate::database::query_views::{
+    AppendAuditEventQueryView, LastAuditHashQueryView, ListAuditEventsQueryView,
+};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+
+/// Table d'audit en ajout seul : les triggers refusent UPDATE, DELETE et TRUNCATE.
+/// Idempotent : peut être rejoué par les migrations.
+pub const AUDIT_LOG_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS audit_log (
+    id bigserial PRIMARY KEY,
+    occurred_at timestamptz NOT NULL,
+    event_type text NOT NULL,
+    user_id bigint,
+    ip text,
+    method text,
+    path text,
+    resource text,
+    action text,
+    reason text,
+    prev_hash text,
+    hash text NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_user_idx ON audit_log (user_id, occurred_at);
+
+CREATE OR REPLACE FUNCTION audit_log_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+CREATE TRIGGER audit_log_append_only BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION audit_log_append_only();
+
+DROP TRIGGER IF EXISTS audit_log_no_truncate ON audit_log;
+CREATE TRIGGER audit_log_no_truncate BEFORE TRUNCATE ON audit_log
+    FOR EACH STATEMENT EXECUTE FUNCTION audit_log_append_only();
+"#;
+
+/// Crée la table `audit_log`. À réserver aux environnements où les
+/// migrations ne la créent pas déjà (tests, outils d'administration).
+pub async fn install_audit_log_table(pool: &PgPool) -> Result<(), DatabaseError> {
+    sqlx::raw_sql(AUDIT_LOG_SQL).execute(pool).await?;
+    Ok(())
+}
+
+/// Empreinte d'un événement chaînée à celle du précédent.
+fn chain_hash(prev_hash: Option<&str>, event: &AuditEvent) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.unwrap_or_default().as_bytes());
+    hasher.update(b"|");
+    hasher.update(event.canonical().as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Lignes lues par page lors de la vérification de la chaîne.
+const VERIFY_PAGE_SIZE: i64 = 1000;
+
+/**
+ * Recalcule la chaîne d'empreintes de `audit_log`, page par page dans l'ordre des `id`.
+ * Renvoie l'identifiant de la première ligne modifiée ou insérée hors chaîne,
+ * `None` si la piste est intacte.
+ */
+pub async fn verify_audit_chain(pool: &PgPool) -> Result<Option<i64>, DatabaseError> {
+    let mut prev_hash: Option<String> = None;
+    let mut from_id = 0;
+    loop {
+        let view = ListAuditEventsQueryView::new(from_id).with_limit(VERIFY_PAGE_SIZE);
+        let records = list_audit_events_query(view, pool.clone()).await?;
+        let page_len = records.len() as i64;
+
+        for record in records {
+            let expected = chain_hash(prev_hash.as_deref(), &record.event);
+            if record.prev_hash != prev_hash || record.hash != expected {
+                return Ok(Some(record.id));
+            }
+            from_id = record.id + 1;
+            prev_hash = Some(record.hash);
+        }
+        if page_len < VERIFY_PAGE_SIZE {
+            return Ok(None);
+        }
+    }
+}
+
+/// Écrit les événements dans `audit_log`, chacun chaîné au précédent.
+#[derive(Clone)]
+pub struct PgAuditSink {
+    pool: PgPool,
+}
+
+impl PgAuditSink {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditSink for PgAuditSink {
+    async fn record(&self, event: &AuditEvent) -> Result<(), DatabaseError> {
+        let mut tx = self.pool.begin().await?;
+        let prev_hash = last_audit_hash_query(LastAuditHashQueryView::new(), &mut tx).await?;
+        let hash = chain_hash(prev_hash.as_deref(), event);
+        let view = AppendAuditEventQueryView::new(event.clone(), prev_hash, hash);
+        append_audit_event_query(view, &mut tx).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use crate::audit::AuditEvent;
+use crate::pool::AppState;
+
+/// Comme `emit_audit_event`, sans attendre l'écriture : l'événement passe par la file
+/// bornée de l'`AppState`. À utiliser sur les chemins atteignables sans authentification.
+pub fn queue_audit_event(state: &AppState, event: AuditEvent) {
+    if let Some(queue) = state.audit_queue() {
+        queue.push(event);
+    }
+}
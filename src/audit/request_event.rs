@@ -0,0 +1,13 @@
+use crate::audit::{AuditEvent, AuditEventType};
+use crate::security::client_ip;
+use actix_web::HttpRequest;
+
+/// Événement pré-rempli avec la méthode, le chemin et l'IP du client : l'adresse du pair,
+/// ou celle annoncée par `X-Forwarded-For` si le pair est un proxy de confiance.
+pub(crate) fn request_event(event_type: AuditEventType, req: &HttpRequest) -> AuditEvent {
+    let event = AuditEvent::new(event_type).with_request(req.method().as_str(), req.path());
+    match client_ip(req) {
+        Some(ip) => event.with_ip(&ip),
+        None => event,
+    }
+}
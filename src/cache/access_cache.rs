@@ -0,0 +1,171 @@
+use super::TtlLruCache;
+use crate::database::instance_id::InstanceId;
+use std::fmt::Display;
+use std::time::Duration;
+
+const DEFAULT_CAPACITY: usize = 10_000;
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+const DEFAULT_REDIS_PREFIX: &str = "mairie360:access";
+
+/// Clé d'un résultat de `check_access` : (utilisateur, commune, ressource, action, instance).
+/// Une instance à `None` correspond à une vérification globale, une commune à `None`
+/// à une vérification sans filtre de commune (super-administrateur).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct AccessCacheKey {
+    user_id: u64,
+    tenant_id: Option<String>,
+    resource_name: String,
+    action: String,
+    instance_id: Option<InstanceId>,
+}
+
+impl AccessCacheKey {
+    pub fn new(
+        user_id: u64,
+        resource_name: &str,
+        action: &str,
+        instance_id: Option<InstanceId>,
+    ) -> Self {
+        Self {
+            user_id,
+            tenant_id: None,
+            resource_name: resource_name.to_string(),
+            action: action.to_string(),
+            instance_id,
+        }
+    }
+    /// Commune dans laquelle la décision a été prise : une même vérification
+    /// peut être accordée dans une commune et refusée dans une autre.
+    pub fn with_tenant(mut self, tenant_id: Option<&str>) -> Self {
+        self.tenant_id = tenant_id.map(str::to_string);
+        self
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.resource_name
+    }
+    pub fn get_action(&self) -> &str {
+        &self.action
+    }
+    pub fn get_instance_id(&self) -> Option<&InstanceId> {
+        self.instance_id.as_ref()
+    }
+}
+
+/// `<utilisateur>:<commune>:<ressource>:<action>:<instance>`, avec `-` sans commune
+/// et `global` sans instance.
+impl Display for AccessCacheKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tenant = self.tenant_id.as_deref().unwrap_or("-");
+        match &self.instance_id {
+            Some(id) => write!(
+                f,
+                "{}:{}:{}:{}:{}",
+                self.user_id, tenant, self.resource_name, self.action, id
+            ),
+            None => write!(
+                f,
+                "{}:{}:{}:{}:global",
+                self.user_id, tenant, self.resource_name, self.action
+            ),
+        }
+    }
+}
+
+/// Cache des décisions de `access_guard_middleware`.
+///
+/// Le niveau local (LRU + TTL) est toujours actif ; le niveau Redis, partagé entre
+/// les instances d'une API, s'active avec `with_redis_tier(true)` et utilise le pool
+/// Redis de `AppState`.
+pub struct AccessCache {
+    local: TtlLruCache<AccessCacheKey, i32>,
+    redis_tier: bool,
+    redis_prefix: String,
+}
+
+impl AccessCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            local: TtlLruCache::new(capacity, ttl),
+            redis_tier: false,
+            redis_prefix: DEFAULT_REDIS_PREFIX.to_string(),
+        }
+    }
+
+    /// Cache qui ne retient rien : chaque requête interroge la base.
+    pub fn disabled() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+
+    pub fn with_redis_tier(mut self, enabled: bool) -> Self {
+        self.redis_tier = enabled;
+        self
+    }
+
+    pub fn with_redis_prefix(mut self, prefix: &str) -> Self {
+        self.redis_prefix = prefix.to_string();
+        self
+    }
+
+    pub fn get_ttl(&self) -> Duration {
+        self.local.get_ttl()
+    }
+
+    pub fn is_redis_tier_enabled(&self) -> bool {
+        self.redis_tier && !self.local.get_ttl().is_zero()
+    }
+
+    pub fn get_local(&self, key: &AccessCacheKey) -> Option<i32> {
+        self.local.get(key)
+    }
+
+    pub fn insert_local(&self, key: AccessCacheKey, access_status: i32) {
+        self.local.insert(key, access_status);
+    }
+
+    pub fn invalidate_user_local(&self, user_id: u64) -> usize {
+        self.local.remove_where(|key| key.user_id == user_id)
+    }
+
+    pub fn invalidate_resource_local(&self, resource_name: &str) -> usize {
+        self.local
+            .remove_where(|key| key.resource_name == resource_name)
+    }
+
+    pub fn invalidate_instance_local(
+        &self,
+        resource_name: &str,
+        instance_id: &InstanceId,
+    ) -> usize {
+        self.local.remove_where(|key| {
+            key.resource_name == resource_name && key.instance_id.as_ref() == Some(instance_id)
+        })
+    }
+
+    pub fn clear_local(&self) {
+        self.local.clear();
+    }
+
+    pub fn local_len(&self) -> usize {
+        self.local.len()
+    }
+
+    pub fn redis_key(&self, key: &AccessCacheKey) -> String {
+        format!("{}:{}", self.redis_prefix, key)
+    }
+
+    pub(crate) fn get_redis_prefix(&self) -> &str {
+        &self.redis_prefix
+    }
+}
+
+impl Default for AccessCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, DEFAULT_TTL)
+    }
+}
@@ -0,0 +1,61 @@
+use super::AccessCacheKey;
+use crate::metrics::record_cache_lookup;
+use crate::pool::AppState;
+use crate::redis::instrument_redis;
+use deadpool_redis::redis::AsyncCommands;
+
+/// Cherche une décision d'accès d'abord dans le cache local, puis dans Redis si le
+/// niveau partagé est activé. Une erreur Redis est traitée comme un miss.
+pub async fn get_cached_access(state: &AppState, key: &AccessCacheKey) -> Option<i32> {
+    let cache = state.access_cache();
+    if let Some(status) = cache.get_local(key) {
+        record_cache_lookup("local", true);
+        return Some(status);
+    }
+    if !cache.is_redis_tier_enabled() {
+        record_cache_lookup("local", false);
+        return None;
+    }
+
+    let status = match state.get_redis_conn().await {
+        Ok(mut conn) => {
+            match instrument_redis("GET", conn.get::<_, Option<i32>>(cache.redis_key(key))).await {
+                Ok(status) => status,
+                Err(e) => {
+                    tracing::warn!(error = %e, "access cache Redis read error");
+                    None
+                }
+            }
+        }
+        Err(_) => None,
+    };
+    record_cache_lookup("redis", status.is_some());
+    if let Some(status) = status {
+        cache.insert_local(key.clone(), status);
+    }
+    status
+}
+
+/// Enregistre une décision d'accès dans le cache local et, si activé, dans Redis
+/// avec la même durée de vie.
+pub async fn store_cached_access(state: &AppState, key: AccessCacheKey, access_status: i32) {
+    let cache = state.access_cache();
+    let redis_key = cache.redis_key(&key);
+    cache.insert_local(key, access_status);
+    if !cache.is_redis_tier_enabled() {
+        return;
+    }
+
+    let Ok(mut conn) = state.get_redis_conn().await else {
+        return;
+    };
+    let ttl = cache.get_ttl().as_secs().max(1);
+    if let Err(e) = instrument_redis(
+        "SETEX",
+        conn.set_ex::<_, _, ()>(redis_key, access_status, ttl),
+    )
+    .await
+    {
+        tracing::warn!(error = %e, "access cache Redis write error");
+    }
+}
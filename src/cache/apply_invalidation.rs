@@ -0,0 +1,34 @@
+use super::{
+    invalidate_all_access, invalidate_instance_access, invalidate_resource_access,
+    invalidate_user_access, InvalidationEvent,
+};
+use crate::database::instance_id::InstanceId;
+use crate::pool::AppState;
+
+/// Évince du cache local (et de Redis si activé) les entrées visées par `event`,
+/// puis le relaie aux abonnés de `AppState::subscribe_invalidations`.
+pub async fn apply_invalidation(state: &AppState, event: &InvalidationEvent) {
+    let result = match event {
+        InvalidationEvent::User(user_id) => {
+            state.user_cache().invalidate_user(*user_id);
+            invalidate_user_access(state, *user_id).await
+        }
+        InvalidationEvent::Instance {
+            resource_name,
+            instance_id,
+        } => invalidate_instance_access(state, resource_name, &InstanceId::Int(*instance_id)).await,
+        InvalidationEvent::Resource(resource_name) => {
+            invalidate_resource_access(state, resource_name).await
+        }
+        InvalidationEvent::All => {
+            state.user_cache().clear();
+            invalidate_all_access(state).await
+        }
+    };
+    if let Err(e) = result {
+        tracing::warn!(%event, error = %e, "shared cache tier not purged");
+    }
+
+    // Aucun abonné n'est pas une erreur.
+    let _ = state.invalidation_sender().send(event.clone());
+}
@@ -0,0 +1,13 @@
+use crate::pool::AppStateError;
+use thiserror::Error;
+
+/// Le niveau Redis du cache n'a pas pu être purgé : les autres instances peuvent
+/// encore servir les entrées visées jusqu'à leur expiration.
+#[derive(Debug, Error)]
+pub enum InvalidationError {
+    #[error("Redis cache tier unavailable: {0}")]
+    Unavailable(#[from] AppStateError),
+
+    #[error("Redis error while purging the cache: {0}")]
+    Redis(#[from] redis::RedisError),
+}
@@ -0,0 +1,121 @@
+use crate::cache::InvalidationError;
+use crate::database::instance_id::InstanceId;
+use crate::pool::AppState;
+use crate::redis::instrument_redis;
+use deadpool_redis::redis::AsyncCommands;
+
+/// Échappe les caractères spéciaux des motifs `SCAN MATCH`.
+fn escape_pattern(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Segments `user:tenant:resource:action:instance` d'une clé du cache Redis
+/// (voir `AccessCacheKey`). L'identifiant d'instance, en dernier, peut contenir des `:`.
+fn key_segments<'a>(prefix: &str, key: &'a str) -> Option<Vec<&'a str>> {
+    let segments: Vec<&str> = key
+        .strip_prefix(prefix)?
+        .strip_prefix(':')?
+        .splitn(5, ':')
+        .collect();
+    (segments.len() == 5).then_some(segments)
+}
+
+/// Supprime les clés trouvées par `SCAN MATCH pattern` et retenues par `matches`.
+/// Le `*` des motifs Redis couvre aussi les `:` : le motif ne fait que dégrossir,
+/// `matches` vérifie chaque segment à sa position.
+async fn delete_redis_pattern(
+    state: &AppState,
+    pattern: String,
+    matches: impl Fn(&str) -> bool,
+) -> Result<(), InvalidationError> {
+    if !state.access_cache().is_redis_tier_enabled() {
+        return Ok(());
+    }
+    // Redis indisponible : le cache local est purgé mais pas le niveau partagé
+    let mut conn = state.get_redis_conn().await?;
+
+    let keys: Vec<String> = instrument_redis("SCAN", async {
+        let mut keys = Vec::new();
+        let mut iter = conn.scan_match::<_, String>(&pattern).await?;
+        while let Some(key) = iter.next_item().await {
+            let key = key?;
+            if matches(&key) {
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    })
+    .await?;
+    if !keys.is_empty() {
+        instrument_redis("DEL", conn.del::<_, ()>(keys)).await?;
+    }
+    Ok(())
+}
+
+/// À appeler après une modification des rôles (`user_roles`) ou des ACL d'un utilisateur.
+pub async fn invalidate_user_access(
+    state: &AppState,
+    user_id: u64,
+) -> Result<(), InvalidationError> {
+    let cache = state.access_cache();
+    cache.invalidate_user_local(user_id);
+    let pattern = format!("{}:{}:*", escape_pattern(cache.get_redis_prefix()), user_id);
+    delete_redis_pattern(state, pattern, |_| true).await
+}
+
+/// À appeler après une modification de `access_control` touchant toute une ressource.
+pub async fn invalidate_resource_access(
+    state: &AppState,
+    resource_name: &str,
+) -> Result<(), InvalidationError> {
+    let cache = state.access_cache();
+    cache.invalidate_resource_local(resource_name);
+    let prefix = cache.get_redis_prefix();
+    let pattern = format!(
+        "{}:*:*:{}:*",
+        escape_pattern(prefix),
+        escape_pattern(resource_name)
+    );
+    delete_redis_pattern(state, pattern, |key| {
+        key_segments(prefix, key).is_some_and(|segments| segments[2] == resource_name)
+    })
+    .await
+}
+
+/// À appeler après une modification de `access_control` sur une instance précise.
+pub async fn invalidate_instance_access(
+    state: &AppState,
+    resource_name: &str,
+    instance_id: &InstanceId,
+) -> Result<(), InvalidationError> {
+    let cache = state.access_cache();
+    cache.invalidate_instance_local(resource_name, instance_id);
+    let prefix = cache.get_redis_prefix();
+    let instance_id = instance_id.to_string();
+    let pattern = format!(
+        "{}:*:*:{}:*:{}",
+        escape_pattern(prefix),
+        escape_pattern(resource_name),
+        escape_pattern(&instance_id)
+    );
+    delete_redis_pattern(state, pattern, |key| {
+        key_segments(prefix, key)
+            .is_some_and(|segments| segments[2] == resource_name && segments[4] == instance_id)
+    })
+    .await
+}
+
+/// Vide entièrement le cache, par exemple après une modification des rôles eux-mêmes.
+pub async fn invalidate_all_access(state: &AppState) -> Result<(), InvalidationError> {
+    let cache = state.access_cache();
+    cache.clear_local();
+    let pattern = format!("{}:*", escape_pattern(cache.get_redis_prefix()));
+    delete_redis_pattern(state, pattern, |_| true).await
+}
@@ -0,0 +1,75 @@
+use serde::Deserialize;
+use std::fmt::Display;
+
+/// Contenu JSON publié par les triggers sur le canal `INVALIDATION_CHANNEL`.
+#[derive(Debug, Deserialize)]
+struct InvalidationPayload {
+    table: String,
+    user_id: Option<u64>,
+    resource_name: Option<String>,
+    instance_id: Option<i32>,
+}
+
+/// Entrées de cache à évincer suite à une modification en base.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InvalidationEvent {
+    /// Tout ce qui concerne un utilisateur (rôles, ACL personnelles, compte).
+    User(u64),
+    /// Les décisions portant sur une instance de ressource, tous utilisateurs confondus.
+    Instance {
+        resource_name: String,
+        instance_id: i32,
+    },
+    /// Les décisions portant sur une ressource entière.
+    Resource(String),
+    /// Tout le cache : notification illisible ou notifications potentiellement perdues.
+    All,
+}
+
+impl InvalidationEvent {
+    /// Interprète une notification. Une charge utile inconnue ou mal formée donne `All`
+    /// pour ne jamais garder une entrée périmée.
+    pub fn from_payload(payload: &str) -> Self {
+        let payload: InvalidationPayload = match serde_json::from_str(payload) {
+            Ok(p) => p,
+            Err(_) => return InvalidationEvent::All,
+        };
+
+        match (payload.table.as_str(), payload.user_id) {
+            ("users" | "user_roles" | "access_control", Some(user_id)) => {
+                InvalidationEvent::User(user_id)
+            }
+            ("access_control", None) => match (payload.resource_name, payload.instance_id) {
+                (Some(resource_name), Some(instance_id)) => InvalidationEvent::Instance {
+                    resource_name,
+                    instance_id,
+                },
+                (Some(resource_name), None) => InvalidationEvent::Resource(resource_name),
+                _ => InvalidationEvent::All,
+            },
+            // Tuple ReBAC : la relation peut être héritée par n'importe quel objet
+            ("relation_tuples", _) => InvalidationEvent::All,
+            _ => InvalidationEvent::All,
+        }
+    }
+}
+
+impl Display for InvalidationEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidationEvent::User(id) => write!(f, "InvalidationEvent: user_id = {}", id),
+            InvalidationEvent::Instance {
+                resource_name,
+                instance_id,
+            } => write!(
+                f,
+                "InvalidationEvent: resource_name = {}, instance_id = {}",
+                resource_name, instance_id
+            ),
+            InvalidationEvent::Resource(name) => {
+                write!(f, "InvalidationEvent: resource_name = {}", name)
+            }
+            InvalidationEvent::All => write!(f, "InvalidationEvent: all"),
+        }
+    }
+}
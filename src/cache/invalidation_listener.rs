@@ -0,0 +1,60 @@
+use super::{apply_invalidation, InvalidationEvent, INVALIDATION_CHANNEL};
+use crate::pool::AppState;
+use sqlx::postgres::PgListener;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(500);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Lance une tâche de fond écoutant `INVALIDATION_CHANNEL` et évinçant les entrées
+/// concernées des caches de `state`.
+///
+/// Chaque instance d'API lance son propre listener : une modification faite par n'importe
+/// quelle API est ainsi propagée à toutes. En cas de perte de connexion, les caches sont
+/// vidés (des notifications ont pu être manquées) et la connexion est rétablie avec un
+/// délai croissant.
+pub fn spawn_invalidation_listener(state: Arc<AppState>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut retry_delay = MIN_RETRY_DELAY;
+        loop {
+            if let Some(pool) = state.db_pool().cloned() {
+                match PgListener::connect_with(&pool).await {
+                    Ok(mut listener) => {
+                        if let Err(e) = listener.listen(INVALIDATION_CHANNEL).await {
+                            tracing::warn!(error = %e, "cache invalidation LISTEN failed");
+                        } else {
+                            retry_delay = MIN_RETRY_DELAY;
+                            loop {
+                                match listener.try_recv().await {
+                                    Ok(Some(notification)) => {
+                                        let event =
+                                            InvalidationEvent::from_payload(notification.payload());
+                                        apply_invalidation(&state, &event).await;
+                                    }
+                                    // Connexion perdue puis rétablie par sqlx : des
+                                    // notifications ont pu être manquées entre-temps.
+                                    Ok(None) => {
+                                        apply_invalidation(&state, &InvalidationEvent::All).await;
+                                    }
+                                    Err(e) => {
+                                        tracing::warn!(error = %e, "cache invalidation listener error");
+                                        break;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "cache invalidation listener connection failed")
+                    }
+                }
+                apply_invalidation(&state, &InvalidationEvent::All).await;
+            }
+
+            tokio::time::sleep(retry_delay).await;
+            retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+        }
+    })
+}
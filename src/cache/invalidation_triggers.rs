@@ -0,0 +1,66 @@
+use crate::database::errors::DatabaseError;
+use sqlx::PgPool;
+
+/// Canal Postgres sur lequel les triggers publient les invalidations.
+pub const INVALIDATION_CHANNEL: &str = "mairie360_cache_invalidation";
+
+/// Triggers publiant une notification à chaque modification de `access_control`,
+/// `user_roles` et `users`. Idempotent : peut être rejoué par les migrations.
+pub const INVALIDATION_TRIGGERS_SQL: &str = r#"
+CREATE OR REPLACE FUNCTION mairie360_cache_invalidation_payload(tbl text, rec jsonb)
+RETURNS jsonb AS $$
+BEGIN
+    IF tbl = 'users' THEN
+        RETURN jsonb_build_object('table', tbl, 'user_id', rec -> 'id');
+    ELSIF tbl = 'access_control' THEN
+        RETURN jsonb_build_object(
+            'table', tbl,
+            'user_id', rec -> 'user_id',
+            'resource_name', (SELECT name FROM resources WHERE id = (rec ->> 'resource_id')::int),
+            'instance_id', rec -> 'resource_instance_id'
+        );
+    ELSE
+        RETURN jsonb_build_object('table', tbl, 'user_id', rec -> 'user_id');
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION mairie360_notify_cache_invalidation()
+RETURNS trigger AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM pg_notify('mairie360_cache_invalidation',
+            mairie360_cache_invalidation_payload(TG_TABLE_NAME, to_jsonb(OLD))::text);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM pg_notify('mairie360_cache_invalidation',
+            mairie360_cache_invalidation_payload(TG_TABLE_NAME, to_jsonb(NEW))::text);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS mairie360_cache_invalidation ON access_control;
+CREATE TRIGGER mairie360_cache_invalidation
+    AFTER INSERT OR UPDATE OR DELETE ON access_control
+    FOR EACH ROW EXECUTE FUNCTION mairie360_notify_cache_invalidation();
+
+DROP TRIGGER IF EXISTS mairie360_cache_invalidation ON user_roles;
+CREATE TRIGGER mairie360_cache_invalidation
+    AFTER INSERT OR UPDATE OR DELETE ON user_roles
+    FOR EACH ROW EXECUTE FUNCTION mairie360_notify_cache_invalidation();
+
+DROP TRIGGER IF EXISTS mairie360_cache_invalidation ON users;
+CREATE TRIGGER mairie360_cache_invalidation
+    AFTER INSERT OR UPDATE OR DELETE ON users
+    FOR EACH ROW EXECUTE FUNCTION mairie360_notify_cache_invalidation();
+"#;
+
+/// Installe les triggers d'invalidation. À réserver aux environnements où les
+/// migrations ne les créent pas déjà (tests, outils d'administration).
+pub async fn install_invalidation_triggers(pool: &PgPool) -> Result<(), DatabaseError> {
+    sqlx::raw_sql(INVALIDATION_TRIGGERS_SQL)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
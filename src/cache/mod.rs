@@ -0,0 +1,34 @@
+mod ttl_lru_cache;
+pub use ttl_lru_cache::TtlLruCache;
+
+mod access_cache;
+pub use access_cache::{AccessCache, AccessCacheKey};
+
+mod access_cache_redis;
+pub use access_cache_redis::{get_cached_access, store_cached_access};
+
+mod errors;
+pub use errors::InvalidationError;
+
+mod apply_invalidation;
+pub use apply_invalidation::apply_invalidation;
+
+mod invalidate_access;
+pub use invalidate_access::{
+    invalidate_all_access, invalidate_instance_access, invalidate_resource_access,
+    invalidate_user_access,
+};
+
+mod invalidation_event;
+pub use invalidation_event::InvalidationEvent;
+
+mod invalidation_listener;
+pub use invalidation_listener::spawn_invalidation_listener;
+
+mod invalidation_triggers;
+pub use invalidation_triggers::{
+    install_invalidation_triggers, INVALIDATION_CHANNEL, INVALIDATION_TRIGGERS_SQL,
+};
+
+mod user_cache;
+pub use user_cache::UserCache;
@@ -0,0 +1,81 @@
+use lru::LruCache;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cache LRU en mémoire dont les entrées expirent après `ttl`.
+///
+/// Une capacité de 0 donne un cache désactivé : toutes les lectures sont des miss.
+pub struct TtlLruCache<K: Hash + Eq, V: Clone> {
+    entries: Option<Mutex<LruCache<K, (V, Instant)>>>,
+    ttl: Duration,
+}
+
+impl<K: Hash + Eq, V: Clone> TtlLruCache<K, V> {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: NonZeroUsize::new(capacity).map(|cap| Mutex::new(LruCache::new(cap))),
+            ttl,
+        }
+    }
+
+    pub fn get_ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut entries = self.entries.as_ref()?.lock().ok()?;
+        match entries.get(key) {
+            Some((value, inserted_at)) if inserted_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        if let Some(mut entries) = self.entries.as_ref().and_then(|e| e.lock().ok()) {
+            entries.put(key, (value, Instant::now()));
+        }
+    }
+
+    /// Supprime toutes les entrées dont la clé satisfait `predicate` et renvoie leur nombre.
+    pub fn remove_where<F: Fn(&K) -> bool>(&self, predicate: F) -> usize
+    where
+        K: Clone,
+    {
+        let Some(mut entries) = self.entries.as_ref().and_then(|e| e.lock().ok()) else {
+            return 0;
+        };
+        let matching: Vec<K> = entries
+            .iter()
+            .filter(|(key, _)| predicate(key))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &matching {
+            entries.pop(key);
+        }
+        matching.len()
+    }
+
+    pub fn clear(&self) {
+        if let Some(mut entries) = self.entries.as_ref().and_then(|e| e.lock().ok()) {
+            entries.clear();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries
+            .as_ref()
+            .and_then(|e| e.lock().ok())
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,45 @@
+use super::TtlLruCache;
+use std::time::Duration;
+
+const DEFAULT_CAPACITY: usize = 10_000;
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// Cache des informations par utilisateur consultées à chaque requête
+/// (pour l'instant le résultat de `is_admin`).
+pub struct UserCache {
+    is_admin: TtlLruCache<u64, bool>,
+}
+
+impl UserCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            is_admin: TtlLruCache::new(capacity, ttl),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+
+    pub fn get_is_admin(&self, user_id: u64) -> Option<bool> {
+        self.is_admin.get(&user_id)
+    }
+
+    pub fn insert_is_admin(&self, user_id: u64, is_admin: bool) {
+        self.is_admin.insert(user_id, is_admin);
+    }
+
+    pub fn invalidate_user(&self, user_id: u64) {
+        self.is_admin.remove_where(|id| *id == user_id);
+    }
+
+    pub fn clear(&self) {
+        self.is_admin.clear();
+    }
+}
+
+impl Default for UserCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY, DEFAULT_TTL)
+    }
+}
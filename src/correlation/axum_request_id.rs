@@ -0,0 +1,44 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+
+use crate::correlation::{with_request_id, RequestId, REQUEST_ID_HEADER, TRACEPARENT_HEADER};
+
+/**
+ * Équivalent axum de `RequestIdMiddleware`, à brancher avec
+ * `axum::middleware::from_fn(request_id_middleware)` comme couche la plus externe.
+ */
+pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
+    // Bloc séparé : `Request` n'est pas `Sync`, aucune référence ne doit survivre aux `await`
+    let request_id = {
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        RequestId::from_headers(header(REQUEST_ID_HEADER), header(TRACEPARENT_HEADER))
+    };
+    req.extensions_mut().insert(request_id.clone());
+
+    let span = tracing::info_span!(
+        "http.request",
+        request_id = request_id.as_str(),
+        trace_id = request_id.get_trace_parent().get_trace_id(),
+        method = %req.method(),
+        path = req.uri().path(),
+    );
+    #[cfg(feature = "telemetry")]
+    crate::telemetry::set_remote_parent(&span, request_id.get_trace_parent());
+    let header_value = HeaderValue::from_str(request_id.as_str()).ok();
+
+    let mut res = with_request_id(request_id, next.run(req).instrument(span)).await;
+    if let Some(value) = header_value {
+        res.headers_mut()
+            .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+    res
+}
@@ -0,0 +1,49 @@
+use crate::correlation::RequestId;
+#[cfg(feature = "telemetry")]
+use crate::correlation::{REQUEST_ID_HEADER, TRACEPARENT_HEADER};
+use std::future::Future;
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: RequestId;
+}
+
+/// Exécute `future` avec `request_id` comme identifiant de corrélation courant.
+/// Les middlewares l'appliquent au traitement de la requête ; à réutiliser pour
+/// les tâches lancées avec `tokio::spawn`, qui n'en héritent pas.
+pub async fn with_request_id<F: Future>(request_id: RequestId, future: F) -> F::Output {
+    CURRENT_REQUEST_ID.scope(request_id, future).await
+}
+
+/// Identifiant de la requête en cours de traitement, hors middleware `None`.
+pub fn current_request_id() -> Option<RequestId> {
+    CURRENT_REQUEST_ID.try_with(RequestId::clone).ok()
+}
+
+/// En-têtes de corrélation à ajouter à un appel sortant (vide hors requête), ex :
+/// `for (name, value) in outgoing_headers() { builder = builder.header(name, value); }`
+/// Avec la feature `telemetry`, le `traceparent` désigne le span exporté en cours.
+pub fn outgoing_headers() -> Vec<(&'static str, String)> {
+    let Some(request_id) = current_request_id() else {
+        return Vec::new();
+    };
+    #[cfg(feature = "telemetry")]
+    if let Some(trace_parent) = crate::telemetry::current_trace_parent() {
+        return vec![
+            (REQUEST_ID_HEADER, request_id.to_string()),
+            (TRACEPARENT_HEADER, trace_parent.to_string()),
+        ];
+    }
+    request_id.propagation_headers()
+}
+
+/// Ajoute les en-têtes de corrélation à un `HeaderMap` (http 1, celui de reqwest et hyper).
+#[cfg(feature = "axum")]
+pub fn inject_request_id(headers: &mut axum::http::HeaderMap) {
+    use axum::http::{HeaderName, HeaderValue};
+
+    for (name, value) in outgoing_headers() {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+}
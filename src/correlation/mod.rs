@@ -0,0 +1,20 @@
+mod trace_parent;
+pub use trace_parent::TraceParent;
+
+mod request_id;
+pub use request_id::{RequestId, REQUEST_ID_HEADER, TRACEPARENT_HEADER};
+
+mod current_request_id;
+#[cfg(feature = "axum")]
+pub use current_request_id::inject_request_id;
+pub use current_request_id::{current_request_id, outgoing_headers, with_request_id};
+
+#[cfg(feature = "actix")]
+mod request_id_middleware;
+#[cfg(feature = "actix")]
+pub use request_id_middleware::RequestIdMiddleware;
+
+#[cfg(feature = "axum")]
+mod axum_request_id;
+#[cfg(feature = "axum")]
+pub use axum_request_id::request_id_middleware;
@@ -0,0 +1,75 @@
+use crate::correlation::TraceParent;
+use std::fmt;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Longueur maximale acceptée pour un `X-Request-Id` reçu.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Identifiant de corrélation d'une requête, partagé entre les APIs mairie360.
+///
+/// Un `X-Request-Id` valide reçu est conservé tel quel ; à défaut, le `trace_id` du
+/// `traceparent` reçu est repris ; sinon un UUID est généré.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestId {
+    id: String,
+    trace_parent: TraceParent,
+}
+
+/// Refuse les valeurs vides, trop longues ou contenant autre chose que
+/// `[A-Za-z0-9-_.:]`, pour qu'un client ne puisse pas injecter de contenu dans les logs.
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+impl RequestId {
+    pub fn generate() -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            trace_parent: TraceParent::new_root(),
+        }
+    }
+
+    /// Construit l'identifiant à partir des en-têtes `X-Request-Id` et `traceparent` reçus.
+    pub fn from_headers(request_id: Option<&str>, traceparent: Option<&str>) -> Self {
+        let trace_parent = traceparent.and_then(TraceParent::parse);
+        let id = match (request_id.map(str::trim), &trace_parent) {
+            (Some(id), _) if is_valid_request_id(id) => id.to_string(),
+            (_, Some(trace_parent)) => trace_parent.get_trace_id().to_string(),
+            _ => Uuid::new_v4().to_string(),
+        };
+        Self {
+            id,
+            trace_parent: trace_parent.unwrap_or_else(TraceParent::new_root),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+
+    /// Contexte de trace reçu, ou trace racine créée pour cette requête.
+    pub fn get_trace_parent(&self) -> &TraceParent {
+        &self.trace_parent
+    }
+
+    /// En-têtes à ajouter aux appels sortants : `x-request-id` et un `traceparent` enfant.
+    pub fn propagation_headers(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (REQUEST_ID_HEADER, self.id.clone()),
+            (TRACEPARENT_HEADER, self.trace_parent.child().to_string()),
+        ]
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
@@ -0,0 +1,111 @@
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use tracing::Instrument;
+
+use crate::correlation::{with_request_id, RequestId, REQUEST_ID_HEADER, TRACEPARENT_HEADER};
+
+/**
+ * Middleware de corrélation : reprend ou génère l'identifiant de la requête,
+ * le place dans les extensions, ouvre le span `http.request` qui le porte et
+ * le renvoie dans l'en-tête `x-request-id` de la réponse.
+ * Il doit englober les autres middlewares (être déclaré en dernier avec `wrap`)
+ * pour que leurs logs et leurs corps d'erreur portent l'identifiant.
+ */
+#[derive(Clone, Default)]
+pub struct RequestIdMiddleware;
+
+impl RequestIdMiddleware {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestIdMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestIdMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddlewareService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestIdMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        let request_id =
+            RequestId::from_headers(header(REQUEST_ID_HEADER), header(TRACEPARENT_HEADER));
+        req.extensions_mut().insert(request_id.clone());
+
+        let span = tracing::info_span!(
+            "http.request",
+            request_id = request_id.as_str(),
+            trace_id = request_id.get_trace_parent().get_trace_id(),
+            method = %req.method(),
+            path = req.path(),
+        );
+        #[cfg(feature = "telemetry")]
+        crate::telemetry::set_remote_parent(&span, request_id.get_trace_parent());
+        let header_value = HeaderValue::from_str(request_id.as_str()).ok();
+        let insert_header = move |headers: &mut HeaderMap| {
+            if let Some(value) = header_value {
+                headers.insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+        };
+
+        Box::pin(with_request_id(
+            request_id,
+            async move {
+                match svc.call(req).await {
+                    Ok(mut res) => {
+                        insert_header(res.headers_mut());
+                        Ok(res)
+                    }
+                    // Réponse d'erreur construite dans le scope, pour que son corps porte l'identifiant
+                    Err(e) => {
+                        let mut res = e.error_response();
+                        insert_header(res.headers_mut());
+                        Err(InternalError::from_response(e, res).into())
+                    }
+                }
+            }
+            .instrument(span),
+        ))
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+use uuid::Uuid;
+
+/// En-tête W3C `traceparent` : `{version}-{trace_id}-{parent_id}-{flags}`.
+/// Voir https://www.w3.org/TR/trace-context/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceParent {
+    trace_id: String,
+    parent_id: String,
+    flags: u8,
+    /// Reçu d'un appelant, plutôt que créé localement
+    remote: bool,
+}
+
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len
+        && value
+            .bytes()
+            .all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn is_zero(value: &str) -> bool {
+    value.bytes().all(|b| b == b'0')
+}
+
+fn random_hex(len: usize) -> String {
+    let mut hex = Uuid::new_v4().simple().to_string();
+    hex.truncate(len);
+    hex
+}
+
+impl TraceParent {
+    /// Nouvelle trace racine, échantillonnée.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_hex(32),
+            parent_id: random_hex(16),
+            flags: 0x01,
+            remote: false,
+        }
+    }
+
+    /// Parse un en-tête `traceparent`. Renvoie `None` si le format est invalide,
+    /// si un identifiant est nul ou si la version est `ff`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.trim().split('-').collect();
+        let [version, trace_id, parent_id, flags, rest @ ..] = parts.as_slice() else {
+            return None;
+        };
+        // Les versions futures peuvent ajouter des champs, pas la version 00
+        if !is_lower_hex(version, 2) || *version == "ff" || (*version == "00" && !rest.is_empty()) {
+            return None;
+        }
+        if !is_lower_hex(trace_id, 32) || is_zero(trace_id) {
+            return None;
+        }
+        if !is_lower_hex(parent_id, 16) || is_zero(parent_id) || !is_lower_hex(flags, 2) {
+            return None;
+        }
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            parent_id: parent_id.to_string(),
+            flags: u8::from_str_radix(flags, 16).ok()?,
+            remote: true,
+        })
+    }
+
+    /// En-tête à transmettre à un service appelé : même trace, nouveau `parent_id`.
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            parent_id: random_hex(16),
+            flags: self.flags,
+            remote: false,
+        }
+    }
+
+    /// Contexte d'un span local, identifiants déjà validés.
+    #[cfg(feature = "telemetry")]
+    pub(crate) fn from_span(trace_id: String, span_id: String, sampled: bool) -> Self {
+        Self {
+            trace_id,
+            parent_id: span_id,
+            flags: u8::from(sampled),
+            remote: false,
+        }
+    }
+
+    pub fn get_trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    pub fn get_parent_id(&self) -> &str {
+        &self.parent_id
+    }
+
+    pub fn is_sampled(&self) -> bool {
+        self.flags & 0x01 == 0x01
+    }
+
+    pub fn is_remote(&self) -> bool {
+        self.remote
+    }
+}
+
+impl fmt::Display for TraceParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
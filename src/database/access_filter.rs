@@ -0,0 +1,127 @@
+use crate::database::queries::QueryError;
+use sqlx::{Postgres, QueryBuilder};
+
+/// Fragment SQL restreignant une requête de listing aux lignes accessibles,
+/// sous la forme `check_access(<user>, <resource>, <action>, <colonne>) = 1`.
+///
+/// Évite de récupérer une liste complète pour la filtrer ensuite côté API.
+pub struct AccessFilter {
+    user_id: u64,
+    resource_name: String,
+    action: String,
+    tenant_id: Option<String>,
+}
+
+fn check_column_name(column: &str) -> Result<(), QueryError> {
+    let valid = !column.is_empty()
+        && column
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err(QueryError::SyntaxError(format!(
+            "Invalid column name for access filter: {}",
+            column
+        )))
+    }
+}
+
+impl AccessFilter {
+    pub fn new(user_id: u64, resource_name: &str, action: &str) -> Self {
+        Self {
+            user_id,
+            resource_name: resource_name.to_string(),
+            action: action.to_string(),
+            tenant_id: None,
+        }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.resource_name
+    }
+    pub fn get_action(&self) -> &str {
+        &self.action
+    }
+    /// Restreint le filtre à l'utilisateur et aux instances de la commune, comme
+    /// `HasAccessQueryView::with_tenant` (`None` : pas de filtre). Une ligne absente
+    /// de `tenant_resources` est exclue.
+    pub fn with_tenant(mut self, tenant_id: Option<&str>) -> Self {
+        self.tenant_id = tenant_id.map(str::to_string);
+        self
+    }
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+
+    /// Fragment avec des placeholders numérotés à partir de `first_placeholder`.
+    /// Les valeurs à lier ensuite, dans l'ordre : `user_id as i32`, `resource_name`, `action`,
+    /// puis `tenant_id` si le filtre est restreint à une commune.
+    ///
+    /// `to_sql("d.id", 2)` donne `check_access($2, $3, $4, d.id) = 1`.
+    pub fn to_sql(
+        &self,
+        instance_column: &str,
+        first_placeholder: usize,
+    ) -> Result<String, QueryError> {
+        check_column_name(instance_column)?;
+        let check = format!(
+            "check_access(${}, ${}, ${}, {}) = 1",
+            first_placeholder,
+            first_placeholder + 1,
+            first_placeholder + 2,
+            instance_column
+        );
+        Ok(match self.tenant_id {
+            Some(_) => format!(
+                "{} AND EXISTS(SELECT 1 FROM users WHERE id = ${} AND tenant_id = ${}) \
+                 AND EXISTS(SELECT 1 FROM tenant_resources WHERE resource_name = ${} \
+                 AND instance_id = ({})::text AND tenant_id = ${})",
+                check,
+                first_placeholder,
+                first_placeholder + 3,
+                first_placeholder + 1,
+                instance_column,
+                first_placeholder + 3
+            ),
+            None => check,
+        })
+    }
+
+    /// Ajoute le fragment à un `QueryBuilder`, qui se charge de la numérotation et des binds.
+    pub fn push_to(
+        &self,
+        builder: &mut QueryBuilder<Postgres>,
+        instance_column: &str,
+    ) -> Result<(), QueryError> {
+        check_column_name(instance_column)?;
+        builder
+            .push("check_access(")
+            .push_bind(self.user_id as i32)
+            .push(", ")
+            .push_bind(self.resource_name.clone())
+            .push(", ")
+            .push_bind(self.action.clone())
+            .push(", ")
+            .push(instance_column)
+            .push(") = 1");
+        if let Some(tenant_id) = &self.tenant_id {
+            // Hors de sa commune, l'utilisateur ou la ligne est traité comme sans droit
+            builder
+                .push(" AND EXISTS(SELECT 1 FROM users WHERE id = ")
+                .push_bind(self.user_id as i32)
+                .push(" AND tenant_id = ")
+                .push_bind(tenant_id.clone())
+                .push(") AND EXISTS(SELECT 1 FROM tenant_resources WHERE resource_name = ")
+                .push_bind(self.resource_name.clone())
+                .push(" AND instance_id = (")
+                .push(instance_column)
+                .push(")::text AND tenant_id = ")
+                .push_bind(tenant_id.clone())
+                .push(")");
+        }
+        Ok(())
+    }
+}
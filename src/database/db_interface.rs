@@ -8,4 +8,10 @@ pub trait QueryResultView {
 
 pub trait DatabaseQueryView: Send {
     fn get_request(&self) -> String;
+
+    /// Nom de la vue (ex: `HasAccessQueryView`), porté par le span de la requête.
+    fn get_view_name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
 }
@@ -30,7 +30,7 @@ pub enum DatabaseError {
 
 impl From<sqlx::Error> for DatabaseError {
     fn from(err: sqlx::Error) -> Self {
-        eprintln!("Database error log: {}", err);
+        tracing::debug!(error = %err, "database driver error");
 
         match err {
             // 1. Gestion spécifique de l'absence de ligne (Pour tes tests About/Login)
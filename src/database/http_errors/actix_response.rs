@@ -0,0 +1,30 @@
+use super::ErrorBody;
+use crate::database::errors::DatabaseError;
+use crate::database::queries::QueryError;
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+fn to_status_code(body: &ErrorBody) -> StatusCode {
+    StatusCode::from_u16(body.get_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+impl ResponseError for DatabaseError {
+    fn status_code(&self) -> StatusCode {
+        to_status_code(&ErrorBody::from(self))
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let body = ErrorBody::from(self);
+        HttpResponse::build(to_status_code(&body)).json(body)
+    }
+}
+
+impl ResponseError for QueryError {
+    fn status_code(&self) -> StatusCode {
+        to_status_code(&ErrorBody::from(self))
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let body = ErrorBody::from(self);
+        HttpResponse::build(to_status_code(&body)).json(body)
+    }
+}
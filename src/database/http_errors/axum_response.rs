@@ -0,0 +1,28 @@
+use super::ErrorBody;
+use crate::database::errors::DatabaseError;
+use crate::database::queries::QueryError;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+impl IntoResponse for ErrorBody {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.get_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, Json(self)).into_response()
+    }
+}
+
+impl IntoResponse for DatabaseError {
+    fn into_response(self) -> Response {
+        ErrorBody::from(&self).into_response()
+    }
+}
+
+impl IntoResponse for QueryError {
+    fn into_response(self) -> Response {
+        ErrorBody::from(&self).into_response()
+    }
+}
@@ -0,0 +1,109 @@
+use crate::correlation::current_request_id;
+use crate::database::errors::DatabaseError;
+use crate::database::queries::QueryError;
+use crate::lockout::LoginError;
+use serde::Serialize;
+
+/// Corps JSON renvoyé au client quand une erreur de base de données remonte jusqu'au handler.
+///
+/// Seuls un code stable et un message générique sont exposés : le message brut du driver
+/// (noms de tables, contraintes, valeurs) reste côté serveur. L'identifiant de corrélation
+/// de la requête en cours y est joint pour retrouver les logs correspondants.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ErrorBody {
+    #[serde(skip)]
+    status: u16,
+    error: &'static str,
+    message: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+impl ErrorBody {
+    fn new(status: u16, error: &'static str, message: &'static str) -> Self {
+        Self {
+            status,
+            error,
+            message,
+            request_id: current_request_id().map(|id| id.to_string()),
+        }
+    }
+
+    pub fn get_status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn get_error(&self) -> &'static str {
+        self.error
+    }
+
+    pub fn get_message(&self) -> &'static str {
+        self.message
+    }
+
+    pub fn get_request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+}
+
+impl From<&QueryError> for ErrorBody {
+    fn from(err: &QueryError) -> Self {
+        match err {
+            QueryError::InvalidEmailFormat(_) => {
+                ErrorBody::new(400, "invalid_email_format", "Invalid email format")
+            }
+            QueryError::InvalidId(_) => ErrorBody::new(400, "invalid_id", "Invalid identifier"),
+            QueryError::NoResults => ErrorBody::new(404, "not_found", "Resource not found"),
+            QueryError::ConstraintViolation(_) => ErrorBody::new(
+                409,
+                "conflict",
+                "The request conflicts with an existing resource",
+            ),
+            // Même réponse pour un email inconnu et un mauvais mot de passe :
+            // on ne révèle pas quels comptes existent.
+            QueryError::InvalidPassword(_) | QueryError::EmailNotFound(_) => {
+                ErrorBody::new(401, "invalid_credentials", "Invalid credentials")
+            }
+            QueryError::SyntaxError(_)
+            | QueryError::MappingError(_)
+            | QueryError::AffectedRowsMismatch { .. }
+            | QueryError::ExecutionFailed(_) => {
+                ErrorBody::new(500, "internal_error", "Internal server error")
+            }
+        }
+    }
+}
+
+impl From<&LoginError> for ErrorBody {
+    fn from(err: &LoginError) -> Self {
+        match err {
+            LoginError::Locked { .. } => ErrorBody::new(
+                429,
+                "too_many_attempts",
+                "Too many failed login attempts, try again later",
+            ),
+            LoginError::Credentials(e) => ErrorBody::from(e),
+        }
+    }
+}
+
+impl From<&DatabaseError> for ErrorBody {
+    fn from(err: &DatabaseError) -> Self {
+        match err {
+            DatabaseError::Query(query_error) => ErrorBody::from(query_error),
+            DatabaseError::Timeout => {
+                ErrorBody::new(503, "timeout", "The database did not answer in time")
+            }
+            DatabaseError::ConnectionFailed(_)
+            | DatabaseError::ConnectionClosed
+            | DatabaseError::NotInitialized => {
+                ErrorBody::new(503, "service_unavailable", "Database unavailable")
+            }
+            DatabaseError::DriverError(_)
+            | DatabaseError::ConfigError(_)
+            | DatabaseError::Internal(_) => {
+                ErrorBody::new(500, "internal_error", "Internal server error")
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod error_body;
+pub use error_body::ErrorBody;
+
+#[cfg(feature = "actix")]
+mod actix_response;
+
+#[cfg(feature = "axum")]
+mod axum_response;
@@ -0,0 +1,113 @@
+use crate::database::queries::QueryError;
+use std::fmt::Display;
+use uuid::Uuid;
+
+/// Identifiant d'une instance de ressource, tel que passé à `check_access`.
+///
+/// Seule la variante `Int` correspond à la signature historique
+/// `check_access(int, text, text, int)` ; les autres variantes supposent que la base
+/// fournit la surcharge correspondante (`bigint`, `uuid` ou `text`).
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum InstanceId {
+    Int(i32),
+    BigInt(i64),
+    Uuid(Uuid),
+    Str(String),
+}
+
+/// Type attendu pour un identifiant d'instance lu dans une requête HTTP.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InstanceIdKind {
+    #[default]
+    Int,
+    BigInt,
+    Uuid,
+    Str,
+}
+
+impl InstanceIdKind {
+    pub fn parse(&self, raw: &str) -> Result<InstanceId, QueryError> {
+        let invalid = || QueryError::InvalidId(raw.to_string());
+        match self {
+            InstanceIdKind::Int => raw.parse().map(InstanceId::Int).map_err(|_| invalid()),
+            InstanceIdKind::BigInt => raw.parse().map(InstanceId::BigInt).map_err(|_| invalid()),
+            InstanceIdKind::Uuid => Uuid::parse_str(raw)
+                .map(InstanceId::Uuid)
+                .map_err(|_| invalid()),
+            InstanceIdKind::Str if raw.is_empty() => Err(invalid()),
+            InstanceIdKind::Str => Ok(InstanceId::Str(raw.to_string())),
+        }
+    }
+}
+
+impl InstanceId {
+    pub fn get_kind(&self) -> InstanceIdKind {
+        match self {
+            InstanceId::Int(_) => InstanceIdKind::Int,
+            InstanceId::BigInt(_) => InstanceIdKind::BigInt,
+            InstanceId::Uuid(_) => InstanceIdKind::Uuid,
+            InstanceId::Str(_) => InstanceIdKind::Str,
+        }
+    }
+
+    /// Type Postgres utilisé pour caster le paramètre lié.
+    pub fn get_sql_type(&self) -> &'static str {
+        match self {
+            InstanceId::Int(_) => "int",
+            InstanceId::BigInt(_) => "bigint",
+            InstanceId::Uuid(_) => "uuid",
+            InstanceId::Str(_) => "text",
+        }
+    }
+}
+
+impl From<i32> for InstanceId {
+    fn from(id: i32) -> Self {
+        InstanceId::Int(id)
+    }
+}
+
+impl From<i64> for InstanceId {
+    fn from(id: i64) -> Self {
+        InstanceId::BigInt(id)
+    }
+}
+
+/// Garde la variante `Int` tant que la valeur tient dans un `i32`.
+impl From<u64> for InstanceId {
+    fn from(id: u64) -> Self {
+        match i32::try_from(id) {
+            Ok(id) => InstanceId::Int(id),
+            Err(_) => InstanceId::BigInt(id as i64),
+        }
+    }
+}
+
+impl From<Uuid> for InstanceId {
+    fn from(id: Uuid) -> Self {
+        InstanceId::Uuid(id)
+    }
+}
+
+impl From<&str> for InstanceId {
+    fn from(id: &str) -> Self {
+        InstanceId::Str(id.to_string())
+    }
+}
+
+impl From<String> for InstanceId {
+    fn from(id: String) -> Self {
+        InstanceId::Str(id)
+    }
+}
+
+impl Display for InstanceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstanceId::Int(id) => write!(f, "{}", id),
+            InstanceId::BigInt(id) => write!(f, "{}", id),
+            InstanceId::Uuid(id) => write!(f, "{}", id),
+            InstanceId::Str(id) => write!(f, "{}", id),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::database::errors::DatabaseError;
+use std::future::Future;
+use std::time::Instant;
+use tracing::Instrument;
+
+/// Exécute une requête dans un span `db.query` portant le nom de la vue, et trace
+/// sa latence. Ni la requête SQL ni les valeurs liées ne sont journalisées.
+pub(crate) async fn instrument_query<T, F>(
+    query_view: &'static str,
+    query: F,
+) -> Result<T, DatabaseError>
+where
+    F: Future<Output = Result<T, DatabaseError>>,
+{
+    let span = tracing::debug_span!(
+        "db.query",
+        query_view,
+        otel.kind = "client",
+        otel.status_code = tracing::field::Empty,
+        db.system.name = "postgresql",
+    );
+    async move {
+        let started = Instant::now();
+        let result = query.await;
+        let elapsed = started.elapsed();
+        let latency_ms = elapsed.as_secs_f64() * 1000.0;
+        match &result {
+            Ok(_) => tracing::debug!(latency_ms, "query completed"),
+            Err(e) => {
+                tracing::Span::current().record("otel.status_code", "ERROR");
+                tracing::warn!(latency_ms, error = %e, "query failed");
+            }
+        }
+        crate::metrics::record_query(query_view, elapsed, result.as_ref().err());
+        #[cfg(feature = "telemetry")]
+        crate::telemetry::record_query(query_view, elapsed, result.is_ok());
+        result
+    }
+    .instrument(span)
+    .await
+}
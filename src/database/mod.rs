@@ -1,5 +1,11 @@
+pub mod access_filter;
 pub mod db_interface;
 pub mod errors;
+pub mod http_errors;
+pub mod instance_id;
+mod instrument_query;
+pub(crate) use instrument_query::instrument_query;
 pub mod queries;
 pub mod queries_result_views;
 pub mod query_views;
+pub mod rls_transaction;
@@ -0,0 +1,29 @@
+use crate::database::query_views::AppendAuditEventQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgConnection;
+
+pub async fn append_audit_event_query(
+    view: AppendAuditEventQueryView,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let event = view.get_event();
+        sqlx::query(&view.get_request())
+            .bind(event.occurred_at)
+            .bind(event.event_type.as_str())
+            .bind(event.user_id.map(|id| id as i64))
+            .bind(event.ip.as_deref())
+            .bind(event.method.as_deref())
+            .bind(event.path.as_deref())
+            .bind(event.resource.as_deref())
+            .bind(event.action.as_deref())
+            .bind(event.reason.as_deref())
+            .bind(view.get_prev_hash())
+            .bind(view.get_hash())
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,22 @@
+use crate::database::query_views::AssignInstanceTenantQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+/// Rattache une instance de ressource à une commune, condition pour qu'elle soit
+/// accessible aux vérifications filtrées par commune.
+pub async fn assign_instance_tenant_query(
+    view: AssignInstanceTenantQueryView,
+    pool: PgPool,
+) -> Result<(), DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        sqlx::query(&view.get_request())
+            .bind(view.get_resource_name())
+            .bind(view.get_instance_id().to_string())
+            .bind(view.get_tenant_id())
+            .execute(&pool)
+            .await?;
+
+        Ok(())
+    })
+    .await
+}
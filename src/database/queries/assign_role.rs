@@ -0,0 +1,19 @@
+use crate::database::query_views::AssignRoleQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+pub async fn assign_role_query(
+    view: AssignRoleQueryView,
+    pool: PgPool,
+) -> Result<bool, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let result = sqlx::query(&view.get_request())
+            .bind(view.get_user_id() as i32)
+            .bind(view.get_role_id() as i32)
+            .execute(&pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    })
+    .await
+}
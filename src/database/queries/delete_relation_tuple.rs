@@ -0,0 +1,26 @@
+use crate::database::query_views::DeleteRelationTupleQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+pub async fn delete_relation_tuple_query(
+    view: DeleteRelationTupleQueryView,
+    pool: PgPool,
+) -> Result<bool, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let tuple = view.get_tuple();
+        let (subject_namespace, subject_id, subject_relation) = tuple.get_subject().to_parts();
+
+        let result = sqlx::query(&view.get_request())
+            .bind(tuple.get_object().get_namespace())
+            .bind(tuple.get_object().get_object_id())
+            .bind(tuple.get_relation())
+            .bind(subject_namespace)
+            .bind(subject_id)
+            .bind(subject_relation)
+            .execute(&pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    })
+    .await
+}
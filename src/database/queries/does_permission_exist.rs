@@ -0,0 +1,19 @@
+use crate::database::query_views::DoesPermissionExistQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+pub async fn does_permission_exist_query(
+    view: DoesPermissionExistQueryView,
+    pool: PgPool,
+) -> Result<i32, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let result = sqlx::query_scalar::<_, i32>(&view.get_request())
+            .bind(view.get_resource_name())
+            .bind(view.get_action())
+            .fetch_one(&pool)
+            .await?;
+
+        Ok(result)
+    })
+    .await
+}
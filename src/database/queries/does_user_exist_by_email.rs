@@ -1,5 +1,6 @@
 use crate::database::db_interface::DatabaseQueryView;
 use crate::database::errors::DatabaseError;
+use crate::database::instrument_query;
 use crate::database::queries::QueryError;
 use crate::database::query_views::DoesUserExistByEmailQueryView;
 use sqlx::PgPool;
@@ -8,16 +9,19 @@ pub async fn does_user_exist_by_email_query(
     view: DoesUserExistByEmailQueryView,
     pool: PgPool,
 ) -> Result<bool, DatabaseError> {
-    if !view.get_email().contains('@') {
-        return Err(DatabaseError::Query(QueryError::InvalidEmailFormat(
-            view.get_email().to_string(),
-        )));
-    }
+    instrument_query(view.get_view_name(), async move {
+        if !view.get_email().contains('@') {
+            return Err(DatabaseError::Query(QueryError::InvalidEmailFormat(
+                view.get_email().to_string(),
+            )));
+        }
 
-    let result = sqlx::query_scalar::<_, bool>(&view.get_request())
-        .bind(view.get_email())
-        .fetch_one(&pool)
-        .await?;
+        let result = sqlx::query_scalar::<_, bool>(&view.get_request())
+            .bind(view.get_email())
+            .fetch_one(&pool)
+            .await?;
 
-    Ok(result)
+        Ok(result)
+    })
+    .await
 }
@@ -1,15 +1,20 @@
 use crate::database::query_views::DoesUserExistByIdQueryView;
-use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError};
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
 use sqlx::PgPool;
 
 pub async fn does_user_exist_by_id_query(
     view: DoesUserExistByIdQueryView,
     pool: PgPool,
 ) -> Result<bool, DatabaseError> {
-    let result = sqlx::query_scalar::<_, bool>(&view.get_request())
-        .bind(view.get_id() as i32)
-        .fetch_one(&pool)
-        .await?;
+    instrument_query(view.get_view_name(), async move {
+        let request = view.get_request();
+        let mut query = sqlx::query_scalar::<_, bool>(&request).bind(view.get_id() as i32);
+        if let Some(tenant_id) = view.get_tenant_id() {
+            query = query.bind(tenant_id.to_string());
+        }
+        let result = query.fetch_one(&pool).await?;
 
-    Ok(result)
+        Ok(result)
+    })
+    .await
 }
@@ -0,0 +1,36 @@
+use crate::database::instance_id::InstanceId;
+use crate::database::queries::QueryError;
+use crate::database::query_views::GrantPermissionQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+/// Accorde l'action sur la ressource (ou sur une instance) à l'utilisateur.
+/// Renvoie `false` si le droit existait déjà, `NoResults` si la permission est inconnue.
+pub async fn grant_permission_query(
+    view: GrantPermissionQueryView,
+    pool: PgPool,
+) -> Result<bool, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let request = view.get_request();
+        let query = sqlx::query_as::<_, (bool, bool)>(&request)
+            .bind(view.get_user_id() as i32)
+            .bind(view.get_resource_name())
+            .bind(view.get_action());
+
+        let query = match view.get_instance_id() {
+            Some(InstanceId::Int(id)) => query.bind(*id),
+            Some(InstanceId::BigInt(id)) => query.bind(*id),
+            Some(InstanceId::Uuid(id)) => query.bind(*id),
+            Some(InstanceId::Str(id)) => query.bind(id.clone()),
+            None => query.bind(None::<i32>),
+        };
+
+        let (permission_exists, inserted) = query.fetch_one(&pool).await?;
+
+        if !permission_exists {
+            return Err(QueryError::NoResults.into());
+        }
+        Ok(inserted)
+    })
+    .await
+}
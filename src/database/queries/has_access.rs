@@ -1,18 +1,35 @@
+use crate::database::instance_id::InstanceId;
 use crate::database::query_views::HasAccessQueryView;
-use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError};
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
 use sqlx::PgPool;
 
 pub async fn has_access_query(
     view: HasAccessQueryView,
     pool: PgPool,
 ) -> Result<i32, DatabaseError> {
-    let result = sqlx::query_scalar::<_, i32>(&view.get_request())
-        .bind(view.get_user_id() as i32)
-        .bind(view.get_resource_name())
-        .bind(view.get_action())
-        .bind(view.get_instance_id() as i32)
-        .fetch_one(&pool)
-        .await?;
+    instrument_query(view.get_view_name(), async move {
+        let request = view.get_request();
+        let query = sqlx::query_scalar::<_, i32>(&request)
+            .bind(view.get_user_id() as i32)
+            .bind(view.get_resource_name())
+            .bind(view.get_action());
 
-    Ok(result)
+        let query = match view.get_instance_id() {
+            Some(InstanceId::Int(id)) => query.bind(*id),
+            Some(InstanceId::BigInt(id)) => query.bind(*id),
+            Some(InstanceId::Uuid(id)) => query.bind(*id),
+            Some(InstanceId::Str(id)) => query.bind(id.clone()),
+            None => query.bind(None::<i32>),
+        };
+
+        let query = match view.get_tenant_id() {
+            Some(tenant_id) => query.bind(tenant_id.to_string()),
+            None => query,
+        };
+
+        let result = query.fetch_one(&pool).await?;
+
+        Ok(result)
+    })
+    .await
 }
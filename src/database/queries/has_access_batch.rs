@@ -0,0 +1,49 @@
+use crate::database::instance_id::InstanceId;
+use crate::database::queries::QueryError;
+use crate::database::query_views::HasAccessBatchQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+/// Renvoie, dans l'ordre d'entrée, les instances auxquelles l'utilisateur a accès,
+/// en un seul aller-retour vers la base. Des identifiants de types incompatibles
+/// (ex: `Uuid` et `Int`) sont refusés (`InvalidId`).
+pub async fn has_access_batch_query(
+    view: HasAccessBatchQueryView,
+    pool: PgPool,
+) -> Result<Vec<InstanceId>, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        if view.get_instance_ids().is_empty() {
+            return Ok(Vec::new());
+        }
+        if view.get_sql_type().is_none() {
+            return Err(QueryError::InvalidId(format!(
+                "mixed instance id kinds: {:?}",
+                view.get_instance_ids()
+            ))
+            .into());
+        }
+
+        let instance_ids = view
+            .get_instance_ids()
+            .iter()
+            .map(InstanceId::to_string)
+            .collect::<Vec<String>>();
+
+        let request = view.get_request();
+        let mut query = sqlx::query_scalar::<_, i64>(&request)
+            .bind(view.get_user_id() as i32)
+            .bind(view.get_resource_name())
+            .bind(view.get_action())
+            .bind(instance_ids);
+        if let Some(tenant_id) = view.get_tenant_id() {
+            query = query.bind(tenant_id.to_string());
+        }
+        let allowed = query.fetch_all(&pool).await?;
+
+        Ok(allowed
+            .into_iter()
+            .filter_map(|ord| view.get_instance_ids().get((ord - 1) as usize).cloned())
+            .collect())
+    })
+    .await
+}
@@ -0,0 +1,22 @@
+use crate::database::query_views::LastAuditHashQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgConnection;
+
+/// À exécuter dans la transaction qui écrit l'événement suivant.
+pub async fn last_audit_hash_query(
+    view: LastAuditHashQueryView,
+    conn: &mut PgConnection,
+) -> Result<Option<String>, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        sqlx::query(&view.get_lock_request())
+            .execute(&mut *conn)
+            .await?;
+
+        let hash = sqlx::query_scalar::<_, String>(&view.get_request())
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Ok(hash)
+    })
+    .await
+}
@@ -0,0 +1,72 @@
+use crate::audit::{AuditEvent, AuditEventType};
+use crate::database::queries_result_views::AuditRecord;
+use crate::database::query_views::ListAuditEventsQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+type AuditRow = (
+    i64,
+    DateTime<Utc>,
+    String,
+    Option<i64>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+);
+
+pub async fn list_audit_events_query(
+    view: ListAuditEventsQueryView,
+    pool: PgPool,
+) -> Result<Vec<AuditRecord>, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let rows = sqlx::query_as::<_, AuditRow>(&view.get_request())
+            .bind(view.get_from_id())
+            .bind(view.get_limit())
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    id,
+                    occurred_at,
+                    event_type,
+                    user_id,
+                    ip,
+                    method,
+                    path,
+                    resource,
+                    action,
+                    reason,
+                    prev_hash,
+                    hash,
+                )| {
+                    AuditRecord {
+                        id,
+                        event: AuditEvent {
+                            event_type: AuditEventType::from(event_type.as_str()),
+                            occurred_at,
+                            user_id: user_id.map(|id| id as u64),
+                            ip,
+                            method,
+                            path,
+                            resource,
+                            action,
+                            reason,
+                        },
+                        prev_hash,
+                        hash,
+                    }
+                },
+            )
+            .collect())
+    })
+    .await
+}
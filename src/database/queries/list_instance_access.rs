@@ -0,0 +1,35 @@
+use crate::database::instance_id::InstanceId;
+use crate::database::query_views::ListInstanceAccessQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+/// Identifiants des utilisateurs ayant l'action sur l'instance.
+pub async fn list_instance_access_query(
+    view: ListInstanceAccessQueryView,
+    pool: PgPool,
+) -> Result<Vec<u64>, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let request = view.get_request();
+        let query = sqlx::query_scalar::<_, i32>(&request)
+            .bind(view.get_resource_name())
+            .bind(view.get_action());
+
+        let query = match view.get_instance_id() {
+            Some(InstanceId::Int(id)) => query.bind(*id),
+            Some(InstanceId::BigInt(id)) => query.bind(*id),
+            Some(InstanceId::Uuid(id)) => query.bind(*id),
+            Some(InstanceId::Str(id)) => query.bind(id.clone()),
+            None => query.bind(None::<i32>),
+        };
+
+        let query = match view.get_tenant_id() {
+            Some(tenant_id) => query.bind(tenant_id.to_string()),
+            None => query,
+        };
+
+        let user_ids = query.fetch_all(&pool).await?;
+
+        Ok(user_ids.into_iter().map(|id| id as u64).collect())
+    })
+    .await
+}
@@ -0,0 +1,18 @@
+use crate::database::query_views::ListRelationObjectsQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+pub async fn list_relation_objects_query(
+    view: ListRelationObjectsQueryView,
+    pool: PgPool,
+) -> Result<Vec<String>, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let ids = sqlx::query_scalar::<_, String>(&view.get_request())
+            .bind(view.get_namespace())
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(ids)
+    })
+    .await
+}
@@ -0,0 +1,26 @@
+use crate::database::queries_result_views::EffectivePermission;
+use crate::database::query_views::ListUserPermissionsQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+pub async fn list_user_permissions_query(
+    view: ListUserPermissionsQueryView,
+    pool: PgPool,
+) -> Result<Vec<EffectivePermission>, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let rows = sqlx::query_as::<_, (String, String, Option<i32>)>(&view.get_request())
+            .bind(view.get_user_id() as i32)
+            .fetch_all(&pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(resource_name, action, instance_id)| EffectivePermission {
+                resource_name,
+                action,
+                instance_id: instance_id.map(|id| id as u64),
+            })
+            .collect())
+    })
+    .await
+}
@@ -1,3 +1,6 @@
+mod does_permission_exist;
+pub use does_permission_exist::does_permission_exist_query;
+
 mod does_user_exist_by_id;
 pub use does_user_exist_by_id::does_user_exist_by_id_query;
 
@@ -13,5 +16,56 @@ pub use is_session_token_valid::is_session_token_valid_query;
 mod has_access;
 pub use has_access::has_access_query;
 
+mod has_access_batch;
+pub use has_access_batch::has_access_batch_query;
+
 mod is_admin;
 pub use is_admin::is_admin_query;
+
+mod read_relation_subjects;
+pub use read_relation_subjects::read_relation_subjects_query;
+
+mod list_relation_objects;
+pub use list_relation_objects::list_relation_objects_query;
+
+mod write_relation_tuple;
+pub use write_relation_tuple::write_relation_tuple_query;
+
+mod delete_relation_tuple;
+pub use delete_relation_tuple::delete_relation_tuple_query;
+
+mod grant_permission;
+pub use grant_permission::grant_permission_query;
+
+mod revoke_permission;
+pub use revoke_permission::revoke_permission_query;
+
+mod assign_role;
+pub use assign_role::assign_role_query;
+
+mod assign_instance_tenant;
+pub use assign_instance_tenant::assign_instance_tenant_query;
+
+mod remove_role;
+pub use remove_role::remove_role_query;
+
+mod list_user_permissions;
+pub use list_user_permissions::list_user_permissions_query;
+
+mod list_instance_access;
+pub use list_instance_access::list_instance_access_query;
+
+mod set_rls_context;
+pub use set_rls_context::set_rls_context_query;
+
+mod append_audit_event;
+pub use append_audit_event::append_audit_event_query;
+
+mod last_audit_hash;
+pub use last_audit_hash::last_audit_hash_query;
+
+mod list_audit_events;
+pub use list_audit_events::list_audit_events_query;
+
+mod ping;
+pub use ping::ping_query;
@@ -0,0 +1,12 @@
+use crate::database::query_views::PingQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+pub async fn ping_query(view: PingQueryView, pool: PgPool) -> Result<(), DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        sqlx::query(&view.get_request()).execute(&pool).await?;
+
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,27 @@
+use crate::database::queries::QueryError;
+use crate::database::query_views::ReadRelationSubjectsQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use crate::rebac::SubjectRef;
+use sqlx::PgPool;
+
+pub async fn read_relation_subjects_query(
+    view: ReadRelationSubjectsQueryView,
+    pool: PgPool,
+) -> Result<Vec<SubjectRef>, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let rows = sqlx::query_as::<_, (String, String, String)>(&view.get_request())
+            .bind(view.get_object().get_namespace())
+            .bind(view.get_object().get_object_id())
+            .bind(view.get_relation())
+            .fetch_all(&pool)
+            .await?;
+
+        rows.iter()
+            .map(|(namespace, id, relation)| {
+                SubjectRef::from_parts(namespace, id, relation)
+                    .map_err(|e| QueryError::MappingError(e.to_string()).into())
+            })
+            .collect()
+    })
+    .await
+}
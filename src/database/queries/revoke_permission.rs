@@ -0,0 +1,31 @@
+use crate::database::instance_id::InstanceId;
+use crate::database::query_views::RevokePermissionQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgPool;
+
+/// Retire le droit accordé par `grant_permission_query`. Renvoie `false` s'il n'existait pas.
+pub async fn revoke_permission_query(
+    view: RevokePermissionQueryView,
+    pool: PgPool,
+) -> Result<bool, DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        let request = view.get_request();
+        let query = sqlx::query(&request)
+            .bind(view.get_user_id() as i32)
+            .bind(view.get_resource_name())
+            .bind(view.get_action());
+
+        let query = match view.get_instance_id() {
+            Some(InstanceId::Int(id)) => query.bind(*id),
+            Some(InstanceId::BigInt(id)) => query.bind(*id),
+            Some(InstanceId::Uuid(id)) => query.bind(*id),
+            Some(InstanceId::Str(id)) => query.bind(id.clone()),
+            None => query.bind(None::<i32>),
+        };
+
+        let result = query.execute(&pool).await?;
+
+        Ok(result.rows_affected() > 0)
+    })
+    .await
+}
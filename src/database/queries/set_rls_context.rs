@@ -0,0 +1,21 @@
+use crate::database::query_views::SetRlsContextQueryView;
+use crate::database::{db_interface::DatabaseQueryView, errors::DatabaseError, instrument_query};
+use sqlx::PgConnection;
+
+/// Positionne `app.user_id` et `app.tenant_id` (chaîne vide sans commune) jusqu'à
+/// la fin de la transaction ouverte sur `conn`.
+pub async fn set_rls_context_query(
+    view: SetRlsContextQueryView,
+    conn: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    instrument_query(view.get_view_name(), async move {
+        sqlx::query(&view.get_request())
+            .bind(view.get_user_id().to_string())
+            .bind(view.get_tenant_id().unwrap_or_default().to_string())
+            .execute(conn)
+            .await?;
+
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,10 @@
+use crate::audit::AuditEvent;
+
+/// Ligne de la table `audit_log`, renvoyée par `list_audit_events_query`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditRecord {
+    pub id: i64,
+    pub event: AuditEvent,
+    pub prev_hash: Option<String>,
+    pub hash: String,
+}
@@ -0,0 +1,8 @@
+/// Droit effectif d'un utilisateur, renvoyé par `list_user_permissions_query`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectivePermission {
+    pub resource_name: String,
+    pub action: String,
+    /// `None` : droit sur toutes les instances de la ressource.
+    pub instance_id: Option<u64>,
+}
@@ -1 +1,5 @@
+mod effective_permission;
+pub use effective_permission::EffectivePermission;
 
+mod audit_record;
+pub use audit_record::AuditRecord;
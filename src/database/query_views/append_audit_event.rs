@@ -0,0 +1,47 @@
+use crate::audit::AuditEvent;
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+pub struct AppendAuditEventQueryView {
+    event: AuditEvent,
+    prev_hash: Option<String>,
+    hash: String,
+}
+
+impl AppendAuditEventQueryView {
+    pub fn new(event: AuditEvent, prev_hash: Option<String>, hash: String) -> Self {
+        Self {
+            event,
+            prev_hash,
+            hash,
+        }
+    }
+    pub fn get_event(&self) -> &AuditEvent {
+        &self.event
+    }
+    pub fn get_prev_hash(&self) -> Option<&str> {
+        self.prev_hash.as_deref()
+    }
+    pub fn get_hash(&self) -> &str {
+        &self.hash
+    }
+}
+
+impl DatabaseQueryView for AppendAuditEventQueryView {
+    fn get_request(&self) -> String {
+        "INSERT INTO audit_log
+            (occurred_at, event_type, user_id, ip, method, path, resource, action, reason, prev_hash, hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
+            .to_string()
+    }
+}
+
+impl Display for AppendAuditEventQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AppendAuditEventQueryView: event_type = {}, user_id = {:?}, hash = {}",
+            self.event.event_type, self.event.user_id, self.hash
+        )
+    }
+}
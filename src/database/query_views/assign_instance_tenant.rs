@@ -0,0 +1,51 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::database::instance_id::InstanceId;
+use std::fmt::Display;
+
+pub struct AssignInstanceTenantQueryView {
+    p_resource_name: String,
+    p_instance_id: InstanceId,
+    tenant_id: String,
+}
+
+impl AssignInstanceTenantQueryView {
+    pub fn new(
+        p_resource_name: &str,
+        p_instance_id: impl Into<InstanceId>,
+        tenant_id: &str,
+    ) -> Self {
+        Self {
+            p_resource_name: p_resource_name.to_string(),
+            p_instance_id: p_instance_id.into(),
+            tenant_id: tenant_id.to_string(),
+        }
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.p_resource_name
+    }
+    pub fn get_instance_id(&self) -> &InstanceId {
+        &self.p_instance_id
+    }
+    pub fn get_tenant_id(&self) -> &str {
+        &self.tenant_id
+    }
+}
+
+impl DatabaseQueryView for AssignInstanceTenantQueryView {
+    /// Une instance n'appartient qu'à une commune : la réassigner remplace la précédente.
+    fn get_request(&self) -> String {
+        "INSERT INTO tenant_resources (resource_name, instance_id, tenant_id) VALUES ($1, $2, $3)
+        ON CONFLICT (resource_name, instance_id) DO UPDATE SET tenant_id = EXCLUDED.tenant_id"
+            .to_string()
+    }
+}
+
+impl Display for AssignInstanceTenantQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AssignInstanceTenantQueryView: resource_name = {}, instance_id = {}, tenant_id = {}",
+            self.p_resource_name, self.p_instance_id, self.tenant_id
+        )
+    }
+}
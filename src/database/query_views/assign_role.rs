@@ -0,0 +1,36 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+pub struct AssignRoleQueryView {
+    user_id: u64,
+    role_id: u64,
+}
+
+impl AssignRoleQueryView {
+    pub fn new(user_id: u64, role_id: u64) -> Self {
+        Self { user_id, role_id }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_role_id(&self) -> u64 {
+        self.role_id
+    }
+}
+
+impl DatabaseQueryView for AssignRoleQueryView {
+    fn get_request(&self) -> String {
+        "INSERT INTO user_roles (user_id, role_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+            .to_string()
+    }
+}
+
+impl Display for AssignRoleQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AssignRoleQueryView: user_id = {}, role_id = {}",
+            self.user_id, self.role_id
+        )
+    }
+}
@@ -0,0 +1,31 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::rebac::RelationTuple;
+use std::fmt::Display;
+
+pub struct DeleteRelationTupleQueryView {
+    tuple: RelationTuple,
+}
+
+impl DeleteRelationTupleQueryView {
+    pub fn new(tuple: RelationTuple) -> Self {
+        Self { tuple }
+    }
+    pub fn get_tuple(&self) -> &RelationTuple {
+        &self.tuple
+    }
+}
+
+impl DatabaseQueryView for DeleteRelationTupleQueryView {
+    fn get_request(&self) -> String {
+        "DELETE FROM relation_tuples
+            WHERE namespace = $1 AND object_id = $2 AND relation = $3
+            AND subject_namespace = $4 AND subject_id = $5 AND subject_relation = $6"
+            .to_string()
+    }
+}
+
+impl Display for DeleteRelationTupleQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DeleteRelationTupleQueryView: tuple = {}", self.tuple)
+    }
+}
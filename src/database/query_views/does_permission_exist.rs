@@ -0,0 +1,49 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+pub struct DoesPermissionExistQueryView {
+    p_resource_name: String,
+    p_action: String,
+}
+
+impl DoesPermissionExistQueryView {
+    pub fn new(p_resource_name: &str, p_action: &str) -> Self {
+        Self {
+            p_resource_name: p_resource_name.to_string(),
+            p_action: p_action.to_string(),
+        }
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.p_resource_name
+    }
+    pub fn get_action(&self) -> &str {
+        &self.p_action
+    }
+}
+
+impl DatabaseQueryView for DoesPermissionExistQueryView {
+    /// Même convention que `check_access` : -1 ressource inconnue, 0 action inconnue, 1 ok.
+    /// Une action `read` est aussi satisfaite par sa variante globale `read_all`.
+    fn get_request(&self) -> String {
+        "SELECT CASE
+            WHEN NOT EXISTS(SELECT 1 FROM resources WHERE name = $1) THEN -1
+            WHEN EXISTS(
+                SELECT 1 FROM permissions p
+                JOIN resources r ON r.id = p.resource_id
+                WHERE r.name = $1 AND (p.action = $2 OR p.action = $2 || '_all')
+            ) THEN 1
+            ELSE 0
+        END"
+        .to_string()
+    }
+}
+
+impl Display for DoesPermissionExistQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DoesPermissionExistQueryView: resource_name = {}, action = {}",
+            self.p_resource_name, self.p_action
+        )
+    }
+}
@@ -3,26 +3,48 @@ use std::fmt::Display;
 
 pub struct DoesUserExistByIdQueryView {
     id: u64,
+    tenant_id: Option<String>,
 }
 
 impl DoesUserExistByIdQueryView {
     pub fn new(id: u64) -> Self {
-        Self { id }
+        Self {
+            id,
+            tenant_id: None,
+        }
     }
 
     pub fn get_id(&self) -> u64 {
         self.id
     }
+
+    /// Restreint la requête aux utilisateurs de la commune (`None` : pas de filtre).
+    pub fn with_tenant(mut self, tenant_id: Option<&str>) -> Self {
+        self.tenant_id = tenant_id.map(str::to_string);
+        self
+    }
+
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
 }
 
 impl DatabaseQueryView for DoesUserExistByIdQueryView {
     fn get_request(&self) -> String {
-        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) AS does_user_exist".to_string()
+        match self.tenant_id {
+            Some(_) => "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND tenant_id = $2) AS does_user_exist"
+                .to_string(),
+            None => "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) AS does_user_exist".to_string(),
+        }
     }
 }
 
 impl Display for DoesUserExistByIdQueryView {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "DoesUserExistByIdQueryView: id = {}", self.id)
+        write!(
+            f,
+            "DoesUserExistByIdQueryView: id = {}, tenant_id = {:?}",
+            self.id, self.tenant_id
+        )
     }
 }
@@ -0,0 +1,84 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::database::instance_id::InstanceId;
+use std::fmt::Display;
+
+pub struct GrantPermissionQueryView {
+    user_id: u64,
+    p_resource_name: String,
+    p_action: String,
+    p_instance_id: Option<InstanceId>,
+}
+
+impl GrantPermissionQueryView {
+    pub fn new(
+        user_id: u64,
+        p_resource_name: &str,
+        p_action: &str,
+        p_instance_id: Option<InstanceId>,
+    ) -> Self {
+        Self {
+            user_id,
+            p_resource_name: p_resource_name.to_string(),
+            p_action: p_action.to_string(),
+            p_instance_id,
+        }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.p_resource_name
+    }
+    pub fn get_action(&self) -> &str {
+        &self.p_action
+    }
+    pub fn get_instance_id(&self) -> Option<&InstanceId> {
+        self.p_instance_id.as_ref()
+    }
+}
+
+impl DatabaseQueryView for GrantPermissionQueryView {
+    /// Renvoie (permission connue, ligne insérée). `$4` NULL = droit sur toutes les instances.
+    fn get_request(&self) -> String {
+        let sql_type = self
+            .p_instance_id
+            .as_ref()
+            .map(InstanceId::get_sql_type)
+            .unwrap_or("int");
+        format!(
+            "WITH perm AS (
+            SELECT r.id AS resource_id, p.id AS permission_id
+            FROM resources r JOIN permissions p ON p.resource_id = r.id
+            WHERE r.name = $2 AND p.action = $3
+            LIMIT 1
+        ), inserted AS (
+            INSERT INTO access_control (user_id, resource_id, permission_id, resource_instance_id)
+            SELECT $1, perm.resource_id, perm.permission_id, $4::{} FROM perm
+            WHERE NOT EXISTS (
+                SELECT 1 FROM access_control ac
+                WHERE ac.user_id = $1 AND ac.resource_id = perm.resource_id
+                AND ac.permission_id = perm.permission_id
+                AND ac.resource_instance_id IS NOT DISTINCT FROM $4::{}
+            )
+            ON CONFLICT DO NOTHING
+            RETURNING 1
+        )
+        SELECT EXISTS(SELECT 1 FROM perm), EXISTS(SELECT 1 FROM inserted)",
+            sql_type, sql_type
+        )
+    }
+}
+
+impl Display for GrantPermissionQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instance_id = match &self.p_instance_id {
+            Some(id) => id.to_string(),
+            None => "NULL".to_string(),
+        };
+        write!(
+            f,
+            "GrantPermissionQueryView: user_id = {}, resource_name = {}, action = {}, instance_id = {}",
+            self.user_id, self.p_resource_name, self.p_action, instance_id
+        )
+    }
+}
@@ -1,20 +1,38 @@
 use crate::database::db_interface::DatabaseQueryView;
+use crate::database::instance_id::InstanceId;
 use std::fmt::Display;
 
 pub struct HasAccessQueryView {
     user_id: u64,
     p_resource_name: String,
     p_action: String,
-    p_instance_id: u64,
+    p_instance_id: Option<InstanceId>,
+    tenant_id: Option<String>,
 }
 
 impl HasAccessQueryView {
-    pub fn new(user_id: u64, p_resource_name: &str, p_action: &str, p_instance_id: u64) -> Self {
+    pub fn new(
+        user_id: u64,
+        p_resource_name: &str,
+        p_action: &str,
+        p_instance_id: impl Into<InstanceId>,
+    ) -> Self {
         Self {
             user_id,
             p_resource_name: p_resource_name.to_string(),
             p_action: p_action.to_string(),
-            p_instance_id,
+            p_instance_id: Some(p_instance_id.into()),
+            tenant_id: None,
+        }
+    }
+    /// Vérification globale sur la ressource (instance = NULL).
+    pub fn new_global(user_id: u64, p_resource_name: &str, p_action: &str) -> Self {
+        Self {
+            user_id,
+            p_resource_name: p_resource_name.to_string(),
+            p_action: p_action.to_string(),
+            p_instance_id: None,
+            tenant_id: None,
         }
     }
     pub fn get_user_id(&self) -> u64 {
@@ -26,23 +44,57 @@ impl HasAccessQueryView {
     pub fn get_action(&self) -> &str {
         &self.p_action
     }
-    pub fn get_instance_id(&self) -> u64 {
-        self.p_instance_id
+    pub fn get_instance_id(&self) -> Option<&InstanceId> {
+        self.p_instance_id.as_ref()
+    }
+    /// Restreint la requête aux utilisateurs et aux instances de la commune
+    /// (`None` : pas de filtre). Une instance absente de `tenant_resources` est refusée.
+    pub fn with_tenant(mut self, tenant_id: Option<&str>) -> Self {
+        self.tenant_id = tenant_id.map(str::to_string);
+        self
+    }
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
     }
 }
 
 impl DatabaseQueryView for HasAccessQueryView {
     fn get_request(&self) -> String {
-        "SELECT check_access($1, $2, $3, $4)".to_string()
+        let sql_type = self
+            .p_instance_id
+            .as_ref()
+            .map(InstanceId::get_sql_type)
+            .unwrap_or("int");
+        let instance_filter = match self.p_instance_id {
+            Some(_) => format!(
+                " AND EXISTS(SELECT 1 FROM tenant_resources WHERE resource_name = $2 \
+                 AND instance_id = ($4::{})::text AND tenant_id = $5)",
+                sql_type
+            ),
+            None => String::new(),
+        };
+        match self.tenant_id {
+            // Hors de sa commune, l'utilisateur ou l'instance est traité comme sans droit
+            Some(_) => format!(
+                "SELECT CASE WHEN EXISTS(SELECT 1 FROM users WHERE id = $1 AND tenant_id = $5){} \
+                 THEN check_access($1, $2, $3, $4::{}) ELSE 0 END",
+                instance_filter, sql_type
+            ),
+            None => format!("SELECT check_access($1, $2, $3, $4::{})", sql_type),
+        }
     }
 }
 
 impl Display for HasAccessQueryView {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instance_id = match &self.p_instance_id {
+            Some(id) => id.to_string(),
+            None => "NULL".to_string(),
+        };
         write!(
             f,
             "HasAccessQueryView: user_id = {}, resource_name = {}, action = {}, instance_id = {}",
-            self.user_id, self.p_resource_name, self.p_action, self.p_instance_id
+            self.user_id, self.p_resource_name, self.p_action, instance_id
         )
     }
 }
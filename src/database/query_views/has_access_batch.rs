@@ -0,0 +1,108 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::database::instance_id::{InstanceId, InstanceIdKind};
+use std::fmt::Display;
+
+pub struct HasAccessBatchQueryView {
+    user_id: u64,
+    p_resource_name: String,
+    p_action: String,
+    p_instance_ids: Vec<InstanceId>,
+    tenant_id: Option<String>,
+}
+
+impl HasAccessBatchQueryView {
+    pub fn new(
+        user_id: u64,
+        p_resource_name: &str,
+        p_action: &str,
+        p_instance_ids: impl IntoIterator<Item = impl Into<InstanceId>>,
+    ) -> Self {
+        Self {
+            user_id,
+            p_resource_name: p_resource_name.to_string(),
+            p_action: p_action.to_string(),
+            p_instance_ids: p_instance_ids.into_iter().map(Into::into).collect(),
+            tenant_id: None,
+        }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.p_resource_name
+    }
+    pub fn get_action(&self) -> &str {
+        &self.p_action
+    }
+    pub fn get_instance_ids(&self) -> &[InstanceId] {
+        &self.p_instance_ids
+    }
+    /// Type Postgres commun aux instances : `int` et `bigint` se combinent en `bigint`,
+    /// les autres types ne se mélangent pas (`None`).
+    pub fn get_sql_type(&self) -> Option<&'static str> {
+        let mut kinds = self.p_instance_ids.iter().map(InstanceId::get_kind);
+        let first = kinds.next().unwrap_or_default();
+        kinds
+            .try_fold(first, |common, kind| match (common, kind) {
+                (a, b) if a == b => Some(a),
+                (InstanceIdKind::Int, InstanceIdKind::BigInt)
+                | (InstanceIdKind::BigInt, InstanceIdKind::Int) => Some(InstanceIdKind::BigInt),
+                _ => None,
+            })
+            .map(|kind| match kind {
+                InstanceIdKind::Int => "int",
+                InstanceIdKind::BigInt => "bigint",
+                InstanceIdKind::Uuid => "uuid",
+                InstanceIdKind::Str => "text",
+            })
+    }
+    /// Restreint la requête aux utilisateurs et aux instances de la commune
+    /// (`None` : pas de filtre). Une instance absente de `tenant_resources` est refusée.
+    pub fn with_tenant(mut self, tenant_id: Option<&str>) -> Self {
+        self.tenant_id = tenant_id.map(str::to_string);
+        self
+    }
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+}
+
+impl DatabaseQueryView for HasAccessBatchQueryView {
+    /// Les identifiants sont liés en `text[]` puis castés vers leur type commun ;
+    /// la requête renvoie la position (1-based) des instances accessibles.
+    fn get_request(&self) -> String {
+        let sql_type = self.get_sql_type().unwrap_or("int");
+        let tenant_filter = match self.tenant_id {
+            Some(_) => format!(
+                " AND EXISTS(SELECT 1 FROM users WHERE id = $1 AND tenant_id = $5)
+            AND EXISTS(SELECT 1 FROM tenant_resources tr WHERE tr.resource_name = $2
+                AND tr.instance_id = (ids.id::{})::text AND tr.tenant_id = $5)",
+                sql_type
+            ),
+            None => String::new(),
+        };
+        format!(
+            "SELECT ids.ord FROM unnest($4::text[]) WITH ORDINALITY AS ids(id, ord)
+            WHERE check_access($1, $2, $3, ids.id::{}) = 1{}
+            ORDER BY ids.ord",
+            sql_type, tenant_filter
+        )
+    }
+}
+
+impl Display for HasAccessBatchQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HasAccessBatchQueryView: user_id = {}, resource_name = {}, action = {}, instance_ids = [{}]",
+            self.user_id,
+            self.p_resource_name,
+            self.p_action,
+            self.p_instance_ids
+                .iter()
+                .map(InstanceId::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
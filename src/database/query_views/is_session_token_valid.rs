@@ -44,8 +44,8 @@ impl Display for IsSessionTokenValidQueryView {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "IsSessionTokenValidQueryView: user_id = {}, session_token = {}, ip_address = {}",
-            self.user_id, self.session_token, self.ip_address
+            "IsSessionTokenValidQueryView: user_id = {}, session_token = <redacted>, ip_address = {}",
+            self.user_id, self.ip_address
         )
     }
 }
@@ -0,0 +1,30 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+/// Empreinte du dernier événement d'audit, lue sous un verrou transactionnel
+/// pour que deux écritures concurrentes ne se chaînent pas au même parent.
+#[derive(Default)]
+pub struct LastAuditHashQueryView;
+
+impl LastAuditHashQueryView {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Verrou pris avant la lecture, relâché à la fin de la transaction.
+    pub fn get_lock_request(&self) -> String {
+        "SELECT pg_advisory_xact_lock(hashtext('audit_log'))".to_string()
+    }
+}
+
+impl DatabaseQueryView for LastAuditHashQueryView {
+    fn get_request(&self) -> String {
+        "SELECT hash FROM audit_log ORDER BY id DESC LIMIT 1".to_string()
+    }
+}
+
+impl Display for LastAuditHashQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LastAuditHashQueryView")
+    }
+}
@@ -0,0 +1,46 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+/// Événements d'audit à partir de l'identifiant `from_id`, dans l'ordre d'écriture.
+pub struct ListAuditEventsQueryView {
+    from_id: i64,
+    limit: Option<i64>,
+}
+
+impl ListAuditEventsQueryView {
+    pub fn new(from_id: i64) -> Self {
+        Self {
+            from_id,
+            limit: None,
+        }
+    }
+    /// Au plus `limit` événements (`LIMIT NULL` sinon, soit toute la suite).
+    pub fn with_limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+    pub fn get_from_id(&self) -> i64 {
+        self.from_id
+    }
+    pub fn get_limit(&self) -> Option<i64> {
+        self.limit
+    }
+}
+
+impl DatabaseQueryView for ListAuditEventsQueryView {
+    fn get_request(&self) -> String {
+        "SELECT id, occurred_at, event_type, user_id, ip, method, path, resource, action, reason, prev_hash, hash
+            FROM audit_log WHERE id >= $1 ORDER BY id LIMIT $2"
+            .to_string()
+    }
+}
+
+impl Display for ListAuditEventsQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ListAuditEventsQueryView: from_id = {}, limit = {:?}",
+            self.from_id, self.limit
+        )
+    }
+}
@@ -0,0 +1,87 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::database::instance_id::InstanceId;
+use std::fmt::Display;
+
+pub struct ListInstanceAccessQueryView {
+    p_resource_name: String,
+    p_action: String,
+    p_instance_id: Option<InstanceId>,
+    tenant_id: Option<String>,
+}
+
+impl ListInstanceAccessQueryView {
+    pub fn new(
+        p_resource_name: &str,
+        p_action: &str,
+        p_instance_id: impl Into<InstanceId>,
+    ) -> Self {
+        Self {
+            p_resource_name: p_resource_name.to_string(),
+            p_action: p_action.to_string(),
+            p_instance_id: Some(p_instance_id.into()),
+            tenant_id: None,
+        }
+    }
+    /// Utilisateurs ayant l'action sur toutes les instances de la ressource.
+    pub fn new_global(p_resource_name: &str, p_action: &str) -> Self {
+        Self {
+            p_resource_name: p_resource_name.to_string(),
+            p_action: p_action.to_string(),
+            p_instance_id: None,
+            tenant_id: None,
+        }
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.p_resource_name
+    }
+    pub fn get_action(&self) -> &str {
+        &self.p_action
+    }
+    pub fn get_instance_id(&self) -> Option<&InstanceId> {
+        self.p_instance_id.as_ref()
+    }
+    /// Restreint la requête aux utilisateurs et aux instances de la commune
+    /// (`None` : pas de filtre). Une instance absente de `tenant_resources` n'a aucun accès.
+    pub fn with_tenant(mut self, tenant_id: Option<&str>) -> Self {
+        self.tenant_id = tenant_id.map(str::to_string);
+        self
+    }
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+}
+
+impl DatabaseQueryView for ListInstanceAccessQueryView {
+    /// S'appuie sur `check_access` pour appliquer exactement les mêmes règles que le middleware.
+    fn get_request(&self) -> String {
+        let sql_type = self
+            .p_instance_id
+            .as_ref()
+            .map(InstanceId::get_sql_type)
+            .unwrap_or("int");
+        let tenant_filter = match (&self.tenant_id, &self.p_instance_id) {
+            (Some(_), Some(_)) => format!(
+                "u.tenant_id = $4 AND EXISTS(SELECT 1 FROM tenant_resources tr \
+                 WHERE tr.resource_name = $1 AND tr.instance_id = ($3::{})::text \
+                 AND tr.tenant_id = $4) AND ",
+                sql_type
+            ),
+            (Some(_), None) => "u.tenant_id = $4 AND ".to_string(),
+            (None, _) => String::new(),
+        };
+        format!(
+            "SELECT u.id FROM users u WHERE {}check_access(u.id, $1, $2, $3::{}) = 1 ORDER BY u.id",
+            tenant_filter, sql_type
+        )
+    }
+}
+
+impl Display for ListInstanceAccessQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ListInstanceAccessQueryView: resource_name = {}, action = {}, instance_id = {:?}",
+            self.p_resource_name, self.p_action, self.p_instance_id
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+pub struct ListRelationObjectsQueryView {
+    namespace: String,
+}
+
+impl ListRelationObjectsQueryView {
+    pub fn new(namespace: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+        }
+    }
+    pub fn get_namespace(&self) -> &str {
+        &self.namespace
+    }
+}
+
+impl DatabaseQueryView for ListRelationObjectsQueryView {
+    fn get_request(&self) -> String {
+        "SELECT DISTINCT object_id FROM relation_tuples WHERE namespace = $1 ORDER BY object_id"
+            .to_string()
+    }
+}
+
+impl Display for ListRelationObjectsQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ListRelationObjectsQueryView: namespace = {}",
+            self.namespace
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+pub struct ListUserPermissionsQueryView {
+    user_id: u64,
+}
+
+impl ListUserPermissionsQueryView {
+    pub fn new(user_id: u64) -> Self {
+        Self { user_id }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+}
+
+impl DatabaseQueryView for ListUserPermissionsQueryView {
+    /// Droits globaux évalués par `check_access` (rôles compris), puis droits par instance
+    /// issus de `access_control`.
+    fn get_request(&self) -> String {
+        "SELECT r.name, p.action, NULL::int AS instance_id
+            FROM permissions p JOIN resources r ON r.id = p.resource_id
+            WHERE check_access($1, r.name, p.action, NULL::int) = 1
+        UNION
+        SELECT r.name, p.action, ac.resource_instance_id
+            FROM access_control ac
+            JOIN resources r ON r.id = ac.resource_id
+            JOIN permissions p ON p.id = ac.permission_id
+            WHERE ac.user_id = $1 AND ac.resource_instance_id IS NOT NULL
+        ORDER BY 1, 2, 3 NULLS FIRST"
+            .to_string()
+    }
+}
+
+impl Display for ListUserPermissionsQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ListUserPermissionsQueryView: user_id = {}",
+            self.user_id
+        )
+    }
+}
@@ -1,3 +1,6 @@
+mod does_permission_exist;
+pub use does_permission_exist::DoesPermissionExistQueryView;
+
 mod does_user_exist_by_email;
 pub use does_user_exist_by_email::DoesUserExistByEmailQueryView;
 
@@ -10,5 +13,56 @@ pub use is_session_token_valid::IsSessionTokenValidQueryView;
 mod has_access;
 pub use has_access::HasAccessQueryView;
 
+mod has_access_batch;
+pub use has_access_batch::HasAccessBatchQueryView;
+
 mod is_admin;
 pub use is_admin::IsAdminQueryView;
+
+mod read_relation_subjects;
+pub use read_relation_subjects::ReadRelationSubjectsQueryView;
+
+mod list_relation_objects;
+pub use list_relation_objects::ListRelationObjectsQueryView;
+
+mod write_relation_tuple;
+pub use write_relation_tuple::WriteRelationTupleQueryView;
+
+mod delete_relation_tuple;
+pub use delete_relation_tuple::DeleteRelationTupleQueryView;
+
+mod grant_permission;
+pub use grant_permission::GrantPermissionQueryView;
+
+mod revoke_permission;
+pub use revoke_permission::RevokePermissionQueryView;
+
+mod assign_role;
+pub use assign_role::AssignRoleQueryView;
+
+mod assign_instance_tenant;
+pub use assign_instance_tenant::AssignInstanceTenantQueryView;
+
+mod remove_role;
+pub use remove_role::RemoveRoleQueryView;
+
+mod list_user_permissions;
+pub use list_user_permissions::ListUserPermissionsQueryView;
+
+mod list_instance_access;
+pub use list_instance_access::ListInstanceAccessQueryView;
+
+mod set_rls_context;
+pub use set_rls_context::SetRlsContextQueryView;
+
+mod append_audit_event;
+pub use append_audit_event::AppendAuditEventQueryView;
+
+mod last_audit_hash;
+pub use last_audit_hash::LastAuditHashQueryView;
+
+mod list_audit_events;
+pub use list_audit_events::ListAuditEventsQueryView;
+
+mod ping;
+pub use ping::PingQueryView;
@@ -0,0 +1,24 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+/// Requête minimale vérifiant que Postgres répond, pour les sondes de disponibilité.
+#[derive(Default)]
+pub struct PingQueryView;
+
+impl PingQueryView {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl DatabaseQueryView for PingQueryView {
+    fn get_request(&self) -> String {
+        "SELECT 1".to_string()
+    }
+}
+
+impl Display for PingQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PingQueryView")
+    }
+}
@@ -0,0 +1,41 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::rebac::ObjectRef;
+use std::fmt::Display;
+
+pub struct ReadRelationSubjectsQueryView {
+    object: ObjectRef,
+    relation: String,
+}
+
+impl ReadRelationSubjectsQueryView {
+    pub fn new(object: ObjectRef, relation: &str) -> Self {
+        Self {
+            object,
+            relation: relation.to_string(),
+        }
+    }
+    pub fn get_object(&self) -> &ObjectRef {
+        &self.object
+    }
+    pub fn get_relation(&self) -> &str {
+        &self.relation
+    }
+}
+
+impl DatabaseQueryView for ReadRelationSubjectsQueryView {
+    fn get_request(&self) -> String {
+        "SELECT subject_namespace, subject_id, subject_relation FROM relation_tuples
+            WHERE namespace = $1 AND object_id = $2 AND relation = $3"
+            .to_string()
+    }
+}
+
+impl Display for ReadRelationSubjectsQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ReadRelationSubjectsQueryView: object = {}, relation = {}",
+            self.object, self.relation
+        )
+    }
+}
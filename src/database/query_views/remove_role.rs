@@ -0,0 +1,35 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+pub struct RemoveRoleQueryView {
+    user_id: u64,
+    role_id: u64,
+}
+
+impl RemoveRoleQueryView {
+    pub fn new(user_id: u64, role_id: u64) -> Self {
+        Self { user_id, role_id }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_role_id(&self) -> u64 {
+        self.role_id
+    }
+}
+
+impl DatabaseQueryView for RemoveRoleQueryView {
+    fn get_request(&self) -> String {
+        "DELETE FROM user_roles WHERE user_id = $1 AND role_id = $2".to_string()
+    }
+}
+
+impl Display for RemoveRoleQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RemoveRoleQueryView: user_id = {}, role_id = {}",
+            self.user_id, self.role_id
+        )
+    }
+}
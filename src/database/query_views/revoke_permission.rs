@@ -0,0 +1,71 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::database::instance_id::InstanceId;
+use std::fmt::Display;
+
+pub struct RevokePermissionQueryView {
+    user_id: u64,
+    p_resource_name: String,
+    p_action: String,
+    p_instance_id: Option<InstanceId>,
+}
+
+impl RevokePermissionQueryView {
+    pub fn new(
+        user_id: u64,
+        p_resource_name: &str,
+        p_action: &str,
+        p_instance_id: Option<InstanceId>,
+    ) -> Self {
+        Self {
+            user_id,
+            p_resource_name: p_resource_name.to_string(),
+            p_action: p_action.to_string(),
+            p_instance_id,
+        }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_resource_name(&self) -> &str {
+        &self.p_resource_name
+    }
+    pub fn get_action(&self) -> &str {
+        &self.p_action
+    }
+    pub fn get_instance_id(&self) -> Option<&InstanceId> {
+        self.p_instance_id.as_ref()
+    }
+}
+
+impl DatabaseQueryView for RevokePermissionQueryView {
+    /// `$4` NULL ne retire que le droit global, pas les droits par instance.
+    fn get_request(&self) -> String {
+        let sql_type = self
+            .p_instance_id
+            .as_ref()
+            .map(InstanceId::get_sql_type)
+            .unwrap_or("int");
+        format!(
+            "DELETE FROM access_control ac
+            USING resources r, permissions p
+            WHERE r.id = ac.resource_id AND p.id = ac.permission_id
+            AND ac.user_id = $1 AND r.name = $2 AND p.action = $3
+            AND ac.resource_instance_id IS NOT DISTINCT FROM $4::{}",
+            sql_type
+        )
+    }
+}
+
+impl Display for RevokePermissionQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instance_id = match &self.p_instance_id {
+            Some(id) => id.to_string(),
+            None => "NULL".to_string(),
+        };
+        write!(
+            f,
+            "RevokePermissionQueryView: user_id = {}, resource_name = {}, action = {}, instance_id = {}",
+            self.user_id, self.p_resource_name, self.p_action, instance_id
+        )
+    }
+}
@@ -0,0 +1,42 @@
+use crate::database::db_interface::DatabaseQueryView;
+use std::fmt::Display;
+
+/// Variables de session lues par les politiques RLS de Postgres
+/// (`current_setting('app.user_id', true)`), limitées à la transaction courante.
+pub struct SetRlsContextQueryView {
+    user_id: u64,
+    tenant_id: Option<String>,
+}
+
+impl SetRlsContextQueryView {
+    pub fn new(user_id: u64, tenant_id: Option<&str>) -> Self {
+        Self {
+            user_id,
+            tenant_id: tenant_id.map(str::to_string),
+        }
+    }
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+}
+
+impl DatabaseQueryView for SetRlsContextQueryView {
+    /// `set_config(..., true)` équivaut à `SET LOCAL`, qui n'accepte pas de paramètres liés.
+    fn get_request(&self) -> String {
+        "SELECT set_config('app.user_id', $1, true), set_config('app.tenant_id', $2, true)"
+            .to_string()
+    }
+}
+
+impl Display for SetRlsContextQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SetRlsContextQueryView: user_id = {}, tenant_id = {:?}",
+            self.user_id, self.tenant_id
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use crate::database::db_interface::DatabaseQueryView;
+use crate::rebac::RelationTuple;
+use std::fmt::Display;
+
+pub struct WriteRelationTupleQueryView {
+    tuple: RelationTuple,
+}
+
+impl WriteRelationTupleQueryView {
+    pub fn new(tuple: RelationTuple) -> Self {
+        Self { tuple }
+    }
+    pub fn get_tuple(&self) -> &RelationTuple {
+        &self.tuple
+    }
+}
+
+impl DatabaseQueryView for WriteRelationTupleQueryView {
+    fn get_request(&self) -> String {
+        "INSERT INTO relation_tuples
+            (namespace, object_id, relation, subject_namespace, subject_id, subject_relation)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT DO NOTHING"
+            .to_string()
+    }
+}
+
+impl Display for WriteRelationTupleQueryView {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WriteRelationTupleQueryView: tuple = {}", self.tuple)
+    }
+}
@@ -0,0 +1,65 @@
+use crate::database::errors::DatabaseError;
+use crate::database::queries::set_rls_context_query;
+use crate::database::query_views::SetRlsContextQueryView;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction propre à une requête HTTP, ouverte avec `app.user_id` et
+/// `app.tenant_id` positionnés pour que les politiques RLS de Postgres filtrent
+/// les lignes à la place de l'API.
+///
+/// Les requêtes s'exécutent sur `&mut *tx`. Sans `commit`, la transaction est
+/// annulée à la fin du handler.
+pub struct RlsTransaction {
+    tx: Transaction<'static, Postgres>,
+    user_id: u64,
+    tenant_id: Option<String>,
+}
+
+impl RlsTransaction {
+    pub async fn begin(
+        pool: &PgPool,
+        user_id: u64,
+        tenant_id: Option<&str>,
+    ) -> Result<Self, DatabaseError> {
+        let mut tx = pool.begin().await?;
+        set_rls_context_query(SetRlsContextQueryView::new(user_id, tenant_id), &mut tx).await?;
+        Ok(Self {
+            tx,
+            user_id,
+            tenant_id: tenant_id.map(str::to_string),
+        })
+    }
+
+    pub fn get_user_id(&self) -> u64 {
+        self.user_id
+    }
+
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.tenant_id.as_deref()
+    }
+
+    pub async fn commit(self) -> Result<(), DatabaseError> {
+        self.tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn rollback(self) -> Result<(), DatabaseError> {
+        self.tx.rollback().await?;
+        Ok(())
+    }
+}
+
+impl Deref for RlsTransaction {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tx
+    }
+}
+
+impl DerefMut for RlsTransaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.tx
+    }
+}
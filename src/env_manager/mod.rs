@@ -2,3 +2,5 @@ mod get_critical_env_var;
 pub use get_critical_env_var::get_critical_env_var;
 mod get_env_var;
 pub use get_env_var::get_env_var;
+mod parse_env_var;
+pub use parse_env_var::{parse_env_var, InvalidEnvVar};
@@ -0,0 +1,23 @@
+use crate::env_manager::get_env_var;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+#[error("Invalid value for environment variable '{name}': '{value}'")]
+pub struct InvalidEnvVar {
+    pub name: String,
+    pub value: String,
+}
+
+/// Lit et convertit une variable optionnelle : absente donne `Ok(None)`,
+/// présente mais invalide donne une erreur plutôt qu'une valeur par défaut silencieuse.
+pub fn parse_env_var<T: FromStr>(name: &str) -> Result<Option<T>, InvalidEnvVar> {
+    get_env_var(name)
+        .map(|value| {
+            value.trim().parse().map_err(|_| InvalidEnvVar {
+                name: name.to_string(),
+                value,
+            })
+        })
+        .transpose()
+}
@@ -0,0 +1,24 @@
+use crate::health::HealthReport;
+use crate::pool::AppState;
+use actix_web::{http::StatusCode, web::Data, HttpRequest, HttpResponse};
+
+fn report_response(report: &HealthReport) -> HttpResponse {
+    let status =
+        StatusCode::from_u16(report.http_status()).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+    HttpResponse::build(status).json(report)
+}
+
+/// `.route("/health/live", web::get().to(actix_liveness_handler))`. Derrière `JwtMiddleware`,
+/// déclarer les sondes avec `AppState::with_public_path("/health")`.
+pub async fn actix_liveness_handler() -> HttpResponse {
+    report_response(&HealthReport::live())
+}
+
+/// `.route("/health/ready", web::get().to(actix_readiness_handler))`, avec l'`AppState`
+/// enregistré dans l'App.
+pub async fn actix_readiness_handler(req: HttpRequest) -> HttpResponse {
+    let Some(state) = req.app_data::<Data<AppState>>() else {
+        return HttpResponse::InternalServerError().body("AppState missing");
+    };
+    report_response(&state.health_checks().readiness(state).await)
+}
@@ -0,0 +1,26 @@
+use crate::health::HealthReport;
+use crate::pool::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+
+impl IntoResponse for HealthReport {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.http_status()).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+        (status, Json(self)).into_response()
+    }
+}
+
+/// `.route("/health/live", get(axum_liveness_handler))`
+pub async fn axum_liveness_handler() -> HealthReport {
+    HealthReport::live()
+}
+
+/// `.route("/health/ready", get(axum_readiness_handler))`, avec un état dont on peut
+/// extraire `Arc<AppState>`.
+pub async fn axum_readiness_handler(State(state): State<Arc<AppState>>) -> HealthReport {
+    state.health_checks().readiness(&state).await
+}
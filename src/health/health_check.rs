@@ -0,0 +1,12 @@
+use crate::pool::AppState;
+use async_trait::async_trait;
+
+/// Vérification d'une dépendance, enregistrée dans les `HealthChecks` de l'`AppState`.
+/// Le message d'erreur est renvoyé tel quel dans la réponse : il ne doit contenir
+/// ni URL ni identifiants.
+#[async_trait]
+pub trait HealthCheck: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn check(&self, state: &AppState) -> Result<(), String>;
+}
@@ -0,0 +1,90 @@
+use crate::health::{
+    DependencyReport, HealthCheck, HealthReport, HealthStatus, PostgresCheck, RedisCheck,
+};
+use crate::pool::AppState;
+use futures_util::future::join_all;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Clone)]
+struct RegisteredCheck {
+    check: Arc<dyn HealthCheck>,
+    required: bool,
+}
+
+/// Dépendances vérifiées par la sonde de disponibilité.
+///
+/// Par défaut Postgres est requis et Redis optionnel (la limitation de débit et le
+/// verrouillage des connexions ont un repli en mémoire).
+#[derive(Clone)]
+pub struct HealthChecks {
+    checks: Vec<RegisteredCheck>,
+    timeout: Duration,
+}
+
+impl Default for HealthChecks {
+    fn default() -> Self {
+        Self::new()
+            .with_required(PostgresCheck)
+            .with_optional(RedisCheck)
+    }
+}
+
+impl HealthChecks {
+    /// Aucune vérification : à compléter avec `with_required` / `with_optional`.
+    pub fn new() -> Self {
+        Self {
+            checks: Vec::new(),
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    pub fn with_required(mut self, check: impl HealthCheck + 'static) -> Self {
+        self.checks.push(RegisteredCheck {
+            check: Arc::new(check),
+            required: true,
+        });
+        self
+    }
+
+    pub fn with_optional(mut self, check: impl HealthCheck + 'static) -> Self {
+        self.checks.push(RegisteredCheck {
+            check: Arc::new(check),
+            required: false,
+        });
+        self
+    }
+
+    /// Délai au-delà duquel une vérification est considérée en échec.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Exécute toutes les vérifications en parallèle.
+    pub async fn readiness(&self, state: &AppState) -> HealthReport {
+        let reports = join_all(self.checks.iter().map(|registered| async move {
+            let started = Instant::now();
+            let result = tokio::time::timeout(self.timeout, registered.check.check(state))
+                .await
+                .unwrap_or_else(|_| Err("timeout".to_string()));
+            let name = registered.check.name().to_string();
+            if let Err(error) = &result {
+                tracing::warn!(dependency = %name, error = %error, "health check failed");
+            }
+            DependencyReport {
+                name,
+                status: if result.is_ok() {
+                    HealthStatus::Up
+                } else {
+                    HealthStatus::Down
+                },
+                required: registered.required,
+                latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+                error: result.err(),
+            }
+        }))
+        .await;
+        HealthReport::from_checks(reports)
+    }
+}
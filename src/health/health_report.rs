@@ -0,0 +1,59 @@
+use crate::health::HealthStatus;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DependencyReport {
+    pub name: String,
+    pub status: HealthStatus,
+    pub required: bool,
+    pub latency_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Corps JSON des sondes `/health/live` et `/health/ready`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub checks: Vec<DependencyReport>,
+}
+
+impl HealthReport {
+    /// Le processus répond : aucune dépendance n'est vérifiée.
+    pub fn live() -> Self {
+        Self {
+            status: HealthStatus::Up,
+            checks: Vec::new(),
+        }
+    }
+
+    /// `Down` dès qu'une dépendance requise l'est, `Degraded` si seule une optionnelle l'est.
+    pub fn from_checks(checks: Vec<DependencyReport>) -> Self {
+        let down = |required: bool| {
+            checks
+                .iter()
+                .any(|check| check.required == required && check.status == HealthStatus::Down)
+        };
+        let status = if down(true) {
+            HealthStatus::Down
+        } else if down(false) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Up
+        };
+        Self { status, checks }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Down
+    }
+
+    /// 200 si le service est prêt (même dégradé), 503 sinon.
+    pub fn http_status(&self) -> u16 {
+        if self.is_ready() {
+            200
+        } else {
+            503
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use serde::Serialize;
+
+/// `Degraded` : une dépendance optionnelle est indisponible, le service reste prêt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Degraded,
+    Down,
+}
@@ -0,0 +1,27 @@
+mod health_status;
+pub use health_status::HealthStatus;
+
+mod health_check;
+pub use health_check::HealthCheck;
+
+mod postgres_check;
+pub use postgres_check::PostgresCheck;
+
+mod redis_check;
+pub use redis_check::RedisCheck;
+
+mod health_report;
+pub use health_report::{DependencyReport, HealthReport};
+
+mod health_checks;
+pub use health_checks::HealthChecks;
+
+#[cfg(feature = "actix")]
+mod actix_health_handlers;
+#[cfg(feature = "actix")]
+pub use actix_health_handlers::{actix_liveness_handler, actix_readiness_handler};
+
+#[cfg(feature = "axum")]
+mod axum_health_handlers;
+#[cfg(feature = "axum")]
+pub use axum_health_handlers::{axum_liveness_handler, axum_readiness_handler};
@@ -0,0 +1,25 @@
+use crate::database::queries::ping_query;
+use crate::database::query_views::PingQueryView;
+use crate::health::HealthCheck;
+use crate::pool::AppState;
+use async_trait::async_trait;
+
+/// Exécute `SELECT 1` sur le pool Postgres de l'`AppState`.
+pub struct PostgresCheck;
+
+#[async_trait]
+impl HealthCheck for PostgresCheck {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    async fn check(&self, state: &AppState) -> Result<(), String> {
+        let pool = state
+            .db_pool()
+            .cloned()
+            .ok_or_else(|| "pool not initialized".to_string())?;
+        ping_query(PingQueryView::new(), pool)
+            .await
+            .map_err(|_| "query failed".to_string())
+    }
+}
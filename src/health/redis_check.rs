@@ -0,0 +1,28 @@
+use crate::health::HealthCheck;
+use crate::pool::{AppState, AppStateError};
+use crate::redis::instrument_redis;
+use async_trait::async_trait;
+
+/// Envoie `PING` sur une connexion du pool Redis de l'`AppState`.
+pub struct RedisCheck;
+
+#[async_trait]
+impl HealthCheck for RedisCheck {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn check(&self, state: &AppState) -> Result<(), String> {
+        let mut conn = state.get_redis_conn().await.map_err(|e| match e {
+            AppStateError::PoolMissing { .. } => "pool not initialized".to_string(),
+            _ => "connection failed".to_string(),
+        })?;
+        instrument_redis(
+            "PING",
+            deadpool_redis::redis::cmd("PING").query_async::<String>(&mut conn),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|_| "command failed".to_string())
+    }
+}
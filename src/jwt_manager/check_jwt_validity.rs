@@ -1,8 +1,8 @@
 use crate::database::queries::does_user_exist_by_id_query;
 use crate::database::query_views::DoesUserExistByIdQueryView;
-use crate::jwt_manager::get_timeout_from_jwt;
-use crate::jwt_manager::get_user_id_from_jwt;
+use crate::jwt_manager::decode_jwt::decode_jwt;
 use crate::jwt_manager::verify_jwt_timeout;
+use crate::jwt_manager::Claims;
 use sqlx::PgPool;
 
 #[derive(Debug, PartialEq)]
@@ -15,61 +15,64 @@ pub enum JWTCheckError {
 }
 
 pub async fn check_jwt_validity(jwt: &str, pool: PgPool) -> Result<(), JWTCheckError> {
+    check_jwt_claims(jwt, pool).await.map(|_| ())
+}
+
+/// Valide le token comme `check_jwt_validity` et renvoie ses claims.
+/// Un `sub` non numérique ou hors de la plage `int` est refusé (`InvalidToken`).
+/// Le token n'est jamais journalisé.
+#[tracing::instrument(name = "jwt.check", skip_all, fields(user_id = tracing::field::Empty))]
+pub async fn check_jwt_claims(jwt: &str, pool: PgPool) -> Result<Claims, JWTCheckError> {
     if jwt.is_empty() {
-        eprintln!("No JWT token provided.");
+        tracing::debug!("no JWT token provided");
         return Err(JWTCheckError::NoTokenProvided);
     }
-    let user_id = match get_user_id_from_jwt(&jwt) {
-        Some(id) => id,
-        None => {
-            eprintln!("Failed to decode JWT token.");
+    let claims = match decode_jwt(jwt) {
+        Ok(claims) => claims,
+        Err(_) => {
+            tracing::debug!("failed to decode JWT token");
             return Err(JWTCheckError::InvalidToken);
         }
     };
 
-    let parsed_user_id: usize = match user_id.parse() {
-        Ok(id) => id,
-        Err(_) => {
-            eprintln!("Failed to parse user ID from JWT.");
+    let parsed_user_id = match claims.parse_user_id() {
+        Some(id) => id,
+        None => {
+            tracing::warn!("JWT subject is not a valid user id");
             return Err(JWTCheckError::InvalidToken);
         }
     };
 
+    tracing::Span::current().record("user_id", parsed_user_id);
+
+    // Un token rattaché à une commune n'est valide que pour un utilisateur de cette commune
     let query_view: DoesUserExistByIdQueryView =
-        DoesUserExistByIdQueryView::new(parsed_user_id as u64);
+        DoesUserExistByIdQueryView::new(parsed_user_id).with_tenant(claims.get_tenant());
 
     let result = does_user_exist_by_id_query(query_view, pool).await;
 
     let exist = match result {
         Ok(res) => res,
         Err(e) => {
-            eprintln!("Database query error: {}", e);
+            tracing::error!(error = %e, "user lookup failed during JWT check");
             return Err(JWTCheckError::DatabaseError);
         }
     };
 
-    if exist {
-        let timeout: usize = match get_timeout_from_jwt(&jwt) {
-            Some(t) => t,
-            None => {
-                eprintln!("Failed to retrieve timeout from JWT.");
-                return Err(JWTCheckError::InvalidToken);
-            }
-        };
+    if !exist {
+        tracing::info!("JWT subject does not match an existing user");
+        return Err(JWTCheckError::UnknownUser);
+    }
 
-        match verify_jwt_timeout(timeout) {
-            Ok(true) => Ok(()),
-            Ok(false) => {
-                eprintln!("JWT token is expired.");
-                Err(JWTCheckError::ExpiredToken)
-            }
-            Err(e) => {
-                eprintln!("Error verifying JWT timeout: {}", e);
-                Err(JWTCheckError::InvalidToken)
-            }
+    match verify_jwt_timeout(claims.get_expiration()) {
+        Ok(true) => Ok(claims),
+        Ok(false) => {
+            tracing::debug!("JWT token is expired");
+            Err(JWTCheckError::ExpiredToken)
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "error verifying JWT expiration");
+            Err(JWTCheckError::InvalidToken)
         }
-    } else {
-        eprintln!("User does not exist with ID: {}", user_id);
-        return Err(JWTCheckError::UnknownUser);
     }
 }
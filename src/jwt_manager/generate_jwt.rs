@@ -5,6 +5,12 @@ use jsonwebtoken::{encode, EncodingKey, Header};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn generate_jwt(user_id_str: &str) -> Result<String, jsonwebtoken::errors::Error> {
+    generate_jwt_with_claims(Claims::new(user_id_str.to_owned(), 0))
+}
+
+/// Signe `claims` (session, rôles, scopes, commune...) après avoir fixé
+/// son expiration à partir de `JWT_TIMEOUT`.
+pub fn generate_jwt_with_claims(claims: Claims) -> Result<String, jsonwebtoken::errors::Error> {
     let secret: Vec<u8> = get_jwt_secret()?;
     let timeout = get_jwt_timeout()?;
 
@@ -13,7 +19,7 @@ pub fn generate_jwt(user_id_str: &str) -> Result<String, jsonwebtoken::errors::E
         .unwrap()
         .as_secs() as usize
         + timeout; // Token valid for the configured JWT timeout duration
-    let claims = Claims::new(user_id_str.to_owned(), expiration);
+    let claims = claims.with_expiration(expiration);
     let token = encode(
         &Header::default(),
         &claims,
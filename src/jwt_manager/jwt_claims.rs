@@ -1,9 +1,48 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Claims {
     sub: String,
     exp: usize,
+    /// Identifiant de la session ouverte au login
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sid: Option<String>,
+    /// Identifiant unique du token (révocation, audit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    jti: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    roles: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    scopes: Vec<String>,
+    /// Commune à laquelle le token est rattaché
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tenant: Option<String>,
+    /// Méthodes d'authentification ayant produit le token (RFC 8176, ex: ["pwd", "otp"]).
+    /// Une chaîne seule est aussi acceptée, comme l'émettent certains fournisseurs SSO.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "string_or_list"
+    )]
+    amr: Option<Vec<String>>,
+}
+
+fn string_or_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Option::<StringOrList>::deserialize(deserializer)? {
+        Some(StringOrList::One(value)) => Some(vec![value]),
+        Some(StringOrList::Many(values)) => Some(values),
+        None => None,
+    })
 }
 
 impl Claims {
@@ -11,16 +50,101 @@ impl Claims {
         Claims {
             sub: user_id,
             exp: expiration,
+            sid: None,
+            jti: None,
+            roles: Vec::new(),
+            scopes: Vec::new(),
+            tenant: None,
+            amr: None,
         }
     }
 
+    pub fn with_expiration(mut self, expiration: usize) -> Self {
+        self.exp = expiration;
+        self
+    }
+
+    pub fn with_session_id(mut self, session_id: &str) -> Self {
+        self.sid = Some(session_id.to_string());
+        self
+    }
+
+    pub fn with_token_id(mut self, token_id: &str) -> Self {
+        self.jti = Some(token_id.to_string());
+        self
+    }
+
+    pub fn with_roles(mut self, roles: &[&str]) -> Self {
+        self.roles = roles.iter().map(|r| r.to_string()).collect();
+        self
+    }
+
+    pub fn with_scopes(mut self, scopes: &[&str]) -> Self {
+        self.scopes = scopes.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    pub fn with_tenant(mut self, tenant: &str) -> Self {
+        self.tenant = Some(tenant.to_string());
+        self
+    }
+
+    pub fn with_auth_method(mut self, auth_method: &str) -> Self {
+        self.amr = Some(vec![auth_method.to_string()]);
+        self
+    }
+
+    pub fn with_auth_methods(mut self, auth_methods: &[&str]) -> Self {
+        self.amr = Some(auth_methods.iter().map(|m| m.to_string()).collect());
+        self
+    }
+
     pub fn get_user_id(&self) -> &str {
         &self.sub
     }
 
+    /// `None` si `sub` n'est pas un identifiant numérique valide. Les requêtes lient
+    /// l'identifiant en `int` : une valeur au-delà de `i32::MAX` est refusée plutôt que
+    /// tronquée en l'identifiant d'un autre utilisateur.
+    pub fn parse_user_id(&self) -> Option<u64> {
+        self.sub
+            .parse::<u64>()
+            .ok()
+            .filter(|id| i32::try_from(*id).is_ok())
+    }
+
     pub fn get_expiration(&self) -> usize {
         self.exp
     }
+
+    pub fn get_session_id(&self) -> Option<&str> {
+        self.sid.as_deref()
+    }
+
+    pub fn get_token_id(&self) -> Option<&str> {
+        self.jti.as_deref()
+    }
+
+    pub fn get_roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    pub fn get_scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    pub fn get_tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    /// Première méthode du claim `amr`, celle qui a ouvert la session.
+    pub fn get_auth_method(&self) -> Option<&str> {
+        self.get_auth_methods().first().map(String::as_str)
+    }
+
+    pub fn get_auth_methods(&self) -> &[String] {
+        self.amr.as_deref().unwrap_or_default()
+    }
 }
 
 impl std::fmt::Display for Claims {
@@ -2,15 +2,18 @@ mod check_jwt_timeout;
 pub use check_jwt_timeout::verify_jwt_timeout;
 
 mod check_jwt_validity;
+pub use check_jwt_validity::check_jwt_claims;
 pub use check_jwt_validity::check_jwt_validity;
 pub use check_jwt_validity::JWTCheckError;
 
 mod decode_jwt;
 
 mod generate_jwt;
-pub use generate_jwt::generate_jwt;
+pub use generate_jwt::{generate_jwt, generate_jwt_with_claims};
 
+#[cfg(feature = "actix")]
 mod get_jwt_from_request;
+#[cfg(feature = "actix")]
 pub use get_jwt_from_request::get_jwt_from_request;
 
 mod get_jwt_secret;
@@ -26,3 +29,4 @@ mod get_user_id_from_jwt;
 pub use get_user_id_from_jwt::get_user_id_from_jwt;
 
 mod jwt_claims;
+pub use jwt_claims::Claims;
@@ -1,7 +1,21 @@
+pub mod abac;
+pub mod audit;
+pub mod cache;
+pub mod correlation;
 pub mod database;
 pub mod env_manager;
+pub mod health;
+#[doc(hidden)]
+pub use inventory;
 pub mod jwt_manager;
+pub mod lockout;
+pub mod metrics;
 pub mod pool;
+pub mod rate_limit;
+pub mod rebac;
 mod redis;
 pub mod security;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod tenant;
 pub mod test_setup;
@@ -0,0 +1,24 @@
+use crate::database::http_errors::ErrorBody;
+use crate::lockout::LoginError;
+use actix_web::{
+    http::{header::RETRY_AFTER, StatusCode},
+    HttpResponse, ResponseError,
+};
+
+impl ResponseError for LoginError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            LoginError::Locked { .. } => StatusCode::TOO_MANY_REQUESTS,
+            LoginError::Credentials(e) => e.status_code(),
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            LoginError::Locked { retry_after } => HttpResponse::TooManyRequests()
+                .insert_header((RETRY_AFTER, retry_after.as_secs().max(1)))
+                .json(ErrorBody::from(self)),
+            LoginError::Credentials(e) => e.error_response(),
+        }
+    }
+}
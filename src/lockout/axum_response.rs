@@ -0,0 +1,17 @@
+use crate::database::http_errors::ErrorBody;
+use crate::lockout::LoginError;
+use axum::http::header::RETRY_AFTER;
+use axum::response::{IntoResponse, Response};
+
+impl IntoResponse for LoginError {
+    fn into_response(self) -> Response {
+        match self {
+            LoginError::Locked { retry_after } => (
+                [(RETRY_AFTER, retry_after.as_secs().max(1).to_string())],
+                ErrorBody::from(&self),
+            )
+                .into_response(),
+            LoginError::Credentials(e) => e.into_response(),
+        }
+    }
+}
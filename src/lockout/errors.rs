@@ -0,0 +1,23 @@
+use crate::database::errors::DatabaseError;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum LoginError {
+    /// Trop d'échecs récents pour ce compte ou cette IP : nouvel essai après `retry_after`.
+    #[error("Too many failed login attempts, retry in {}s", retry_after.as_secs())]
+    Locked { retry_after: Duration },
+
+    /// Erreur renvoyée par la vérification des identifiants.
+    #[error(transparent)]
+    Credentials(#[from] DatabaseError),
+}
+
+impl LoginError {
+    pub fn get_retry_after(&self) -> Option<Duration> {
+        match self {
+            LoginError::Locked { retry_after } => Some(*retry_after),
+            LoginError::Credentials(_) => None,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::audit::{emit_audit_event, AuditEvent, AuditEventType};
+use crate::database::errors::DatabaseError;
+use crate::database::queries::QueryError;
+use crate::lockout::{
+    record_attempt_success, release_login_attempt, reserve_login_attempt, LoginError,
+};
+use crate::pool::AppState;
+use std::future::Future;
+
+/**
+ * Encadre la vérification des identifiants d'une route de connexion : refuse la
+ * tentative si le compte ou l'IP est bloqué, compte les échecs
+ * (`InvalidPassword`, `EmailNotFound`) et remet le compte à zéro en cas de succès.
+ * Les autres erreurs (base indisponible...) ne sont pas comptées.
+ *
+ * La tentative est réservée avant `verify` (voir `reserve_login_attempt`) : des
+ * requêtes parallèles ne contournent pas le blocage.
+ *
+ * `verify` n'est pas exécuté si la tentative est refusée.
+ */
+pub async fn guard_login<T, F>(
+    state: &AppState,
+    account: &str,
+    ip: Option<&str>,
+    verify: F,
+) -> Result<T, LoginError>
+where
+    F: Future<Output = Result<T, DatabaseError>>,
+{
+    let attempt = match reserve_login_attempt(state, account, ip).await {
+        Ok(attempt) => attempt,
+        Err(e) => {
+            // L'identifiant saisi n'est pas tracé : ce peut être un mot de passe mal placé
+            let mut event = AuditEvent::new(AuditEventType::LoginLocked).with_reason("lockout");
+            if let Some(ip) = ip {
+                event = event.with_ip(ip);
+            }
+            emit_audit_event(state, event).await;
+            return Err(e);
+        }
+    };
+
+    match verify.await {
+        Ok(value) => {
+            record_attempt_success(state, attempt).await;
+            Ok(value)
+        }
+        // Échec déjà compté par la réservation
+        Err(
+            e @ DatabaseError::Query(QueryError::InvalidPassword(_) | QueryError::EmailNotFound(_)),
+        ) => Err(LoginError::Credentials(e)),
+        Err(e) => {
+            release_login_attempt(state, attempt).await;
+            Err(LoginError::Credentials(e))
+        }
+    }
+}
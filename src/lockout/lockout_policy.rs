@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+/// Politique anti force brute appliquée aux tentatives de connexion.
+///
+/// Après `free_attempts` échecs sur un compte, chaque nouvel essai doit attendre un
+/// délai qui double à chaque échec (`base_delay`, plafonné à `max_delay`). À
+/// `max_failures` échecs, le compte est verrouillé pendant `lockout_duration`.
+/// Une IP n'a pas de délais progressifs (plusieurs usagers derrière un même NAT) :
+/// elle est verrouillée à `ip_max_failures` échecs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockoutPolicy {
+    pub free_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub max_failures: u32,
+    pub ip_max_failures: u32,
+    pub lockout_duration: Duration,
+    /// Durée de vie du compteur d'échecs depuis le premier échec
+    pub failure_window: Duration,
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            free_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            max_failures: 10,
+            ip_max_failures: 50,
+            lockout_duration: Duration::from_secs(15 * 60),
+            failure_window: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+impl LockoutPolicy {
+    pub fn with_free_attempts(mut self, free_attempts: u32) -> Self {
+        self.free_attempts = free_attempts;
+        self
+    }
+
+    pub fn with_delays(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_lockout(mut self, max_failures: u32, lockout_duration: Duration) -> Self {
+        self.max_failures = max_failures;
+        self.lockout_duration = lockout_duration;
+        self
+    }
+
+    pub fn with_ip_max_failures(mut self, ip_max_failures: u32) -> Self {
+        self.ip_max_failures = ip_max_failures;
+        self
+    }
+
+    pub fn with_failure_window(mut self, failure_window: Duration) -> Self {
+        self.failure_window = failure_window;
+        self
+    }
+
+    /// Blocage imposé à un compte après `failures` échecs consécutifs.
+    pub fn account_delay(&self, failures: u32) -> Option<Duration> {
+        if failures >= self.max_failures {
+            return Some(self.lockout_duration);
+        }
+        let extra = failures.checked_sub(self.free_attempts)?.checked_sub(1);
+        extra.map(|n| {
+            self.base_delay
+                .saturating_mul(2u32.saturating_pow(n))
+                .min(self.max_delay)
+        })
+    }
+
+    /// Blocage imposé à une IP après `failures` échecs.
+    pub fn ip_delay(&self, failures: u32) -> Option<Duration> {
+        (failures >= self.ip_max_failures).then_some(self.lockout_duration)
+    }
+}
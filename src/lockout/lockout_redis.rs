@@ -0,0 +1,170 @@
+use crate::lockout::login_attempt::Reservation;
+use crate::lockout::{LockoutSubject, LoginAttempt, LoginError};
+use crate::pool::AppState;
+use crate::redis::instrument_redis;
+use deadpool_redis::redis::{AsyncCommands, Script};
+use std::time::Duration;
+
+/// Refuse la tentative si le sujet est bloqué ; sinon la compte comme un échec et pose
+/// le blocage qui en résulte, en une seule opération pour les requêtes concurrentes.
+/// KEYS : compteur, blocage. ARGV : fenêtre, puis blocage (ms, 0 : aucun) après
+/// 1, 2... tentatives, le dernier valant pour les suivantes.
+const RESERVE_ATTEMPT_SCRIPT: &str = r#"
+local ttl = redis.call('PTTL', KEYS[2])
+if ttl > 0 then
+  return {0, ttl}
+end
+local attempts = redis.call('INCR', KEYS[1])
+if attempts == 1 then
+  redis.call('PEXPIRE', KEYS[1], ARGV[1])
+end
+local delay = tonumber(ARGV[math.min(attempts, #ARGV - 1) + 1])
+if delay > 0 then
+  redis.call('SET', KEYS[2], attempts, 'PX', delay)
+end
+return {attempts, 0}
+"#;
+
+/// Annule une tentative réservée, et le blocage qu'elle a posé.
+/// KEYS : compteur, blocage. ARGV : rang de la tentative.
+const RELEASE_ATTEMPT_SCRIPT: &str = r#"
+if redis.call('DECR', KEYS[1]) <= 0 then
+  redis.call('DEL', KEYS[1])
+end
+if redis.call('GET', KEYS[2]) == ARGV[1] then
+  redis.call('DEL', KEYS[2])
+end
+return 1
+"#;
+
+fn subjects(account: &str, ip: Option<&str>) -> Vec<LockoutSubject> {
+    let mut subjects = vec![LockoutSubject::Account(account.to_string())];
+    if let Some(ip) = ip {
+        subjects.push(LockoutSubject::Ip(ip.to_string()));
+    }
+    subjects
+}
+
+async fn reserve(state: &AppState, subject: LockoutSubject) -> Result<Reservation, Duration> {
+    let lockout = state.login_lockout();
+    if let Ok(mut conn) = state.get_redis_conn().await {
+        let script = Script::new(RESERVE_ATTEMPT_SCRIPT);
+        let mut invocation = script.key(lockout.failures_key(&subject));
+        invocation
+            .key(lockout.blocked_key(&subject))
+            .arg(lockout.get_policy().failure_window.as_millis() as u64);
+        for delay in subject.delays_ms(lockout.get_policy()) {
+            invocation.arg(delay);
+        }
+        let reserved: Result<(u32, u64), _> =
+            instrument_redis("EVALSHA", invocation.invoke_async(&mut conn)).await;
+        match reserved {
+            Ok((0, ttl)) => return Err(Duration::from_millis(ttl)),
+            Ok((attempt, _)) => {
+                return Ok(Reservation {
+                    subject,
+                    attempt,
+                    in_redis: true,
+                })
+            }
+            Err(e) => tracing::warn!(error = %e, "login lockout Redis write error"),
+        }
+    }
+    lockout.memory_reserve(&subject).map(|attempt| Reservation {
+        subject,
+        attempt,
+        in_redis: false,
+    })
+}
+
+async fn release(state: &AppState, reservation: Reservation) {
+    let lockout = state.login_lockout();
+    if !reservation.in_redis {
+        lockout.memory_release(&reservation.subject, reservation.attempt);
+        return;
+    }
+    let Ok(mut conn) = state.get_redis_conn().await else {
+        return;
+    };
+    let released: Result<(), _> = instrument_redis(
+        "EVALSHA",
+        Script::new(RELEASE_ATTEMPT_SCRIPT)
+            .key(lockout.failures_key(&reservation.subject))
+            .key(lockout.blocked_key(&reservation.subject))
+            .arg(reservation.attempt)
+            .invoke_async(&mut conn),
+    )
+    .await;
+    if let Err(e) = released {
+        tracing::warn!(error = %e, "login lockout Redis write error");
+    }
+}
+
+async fn reset(state: &AppState, subject: &LockoutSubject) {
+    let lockout = state.login_lockout();
+    lockout.memory_reset(subject);
+    if let Ok(mut conn) = state.get_redis_conn().await {
+        let keys = [lockout.failures_key(subject), lockout.blocked_key(subject)];
+        if let Err(e) = instrument_redis("DEL", conn.del::<_, ()>(&keys)).await {
+            tracing::warn!(error = %e, "login lockout Redis delete error");
+        }
+    }
+}
+
+/**
+ * Réserve une tentative de connexion pour le compte et l'IP : refusée si l'un d'eux est
+ * bloqué, sinon comptée d'avance comme un échec. Des requêtes concurrentes ne peuvent
+ * donc pas vérifier plus d'identifiants que la politique n'en autorise.
+ *
+ * Une route de connexion passe par `guard_login`, qui réserve la tentative, vérifie
+ * les identifiants puis la solde (`record_attempt_success` ou `release_login_attempt`).
+ */
+pub async fn reserve_login_attempt(
+    state: &AppState,
+    account: &str,
+    ip: Option<&str>,
+) -> Result<LoginAttempt, LoginError> {
+    let mut reservations = Vec::new();
+    let mut retry_after: Option<Duration> = None;
+    for subject in subjects(account, ip) {
+        match reserve(state, subject).await {
+            Ok(reservation) => reservations.push(reservation),
+            Err(blocked) => {
+                retry_after = Some(retry_after.map_or(blocked, |current| current.max(blocked)))
+            }
+        }
+    }
+    match retry_after {
+        Some(retry_after) => {
+            release_login_attempt(state, LoginAttempt { reservations }).await;
+            Err(LoginError::Locked { retry_after })
+        }
+        None => Ok(LoginAttempt { reservations }),
+    }
+}
+
+/// Annule une tentative qui n'a pas échoué pour de mauvais identifiants (base indisponible...).
+pub async fn release_login_attempt(state: &AppState, attempt: LoginAttempt) {
+    for reservation in attempt.reservations {
+        release(state, reservation).await;
+    }
+}
+
+/// Connexion réussie : remet à zéro les échecs du compte et annule la tentative de l'IP.
+pub async fn record_attempt_success(state: &AppState, attempt: LoginAttempt) {
+    for reservation in attempt.reservations {
+        match &reservation.subject {
+            LockoutSubject::Account(_) => reset(state, &reservation.subject).await,
+            LockoutSubject::Ip(_) => release(state, reservation).await,
+        }
+    }
+}
+
+/// Déverrouillage par un administrateur.
+pub async fn unlock_account(state: &AppState, account: &str) {
+    reset(state, &LockoutSubject::Account(account.to_string())).await;
+}
+
+pub async fn unlock_ip(state: &AppState, ip: &str) {
+    reset(state, &LockoutSubject::Ip(ip.to_string())).await;
+}
@@ -0,0 +1,15 @@
+use crate::lockout::LockoutSubject;
+
+/// Tentative réservée sur un compte ou une IP : son rang et l'endroit où elle est comptée.
+pub(crate) struct Reservation {
+    pub(crate) subject: LockoutSubject,
+    pub(crate) attempt: u32,
+    pub(crate) in_redis: bool,
+}
+
+/// Tentative de connexion réservée par `reserve_login_attempt`, déjà comptée comme un
+/// échec pour le compte et l'IP. À passer à `record_attempt_success` ou à
+/// `release_login_attempt` si les identifiants n'ont pas été refusés.
+pub struct LoginAttempt {
+    pub(crate) reservations: Vec<Reservation>,
+}
@@ -0,0 +1,165 @@
+use crate::lockout::LockoutPolicy;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Compte ou IP dont les échecs de connexion sont comptés.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LockoutSubject {
+    /// Identifiant de connexion (email), comparé sans tenir compte de la casse
+    Account(String),
+    Ip(String),
+}
+
+impl LockoutSubject {
+    /// Identifiant du compteur. L'email est haché pour ne pas apparaître dans Redis.
+    pub fn key(&self) -> String {
+        match self {
+            LockoutSubject::Account(account) => {
+                let normalized = account.trim().to_lowercase();
+                format!(
+                    "account:{}",
+                    hex::encode(Sha256::digest(normalized.as_bytes()))
+                )
+            }
+            LockoutSubject::Ip(ip) => format!("ip:{}", ip),
+        }
+    }
+
+    /// Blocage imposé par la politique après `failures` échecs.
+    pub fn delay(&self, policy: &LockoutPolicy, failures: u32) -> Option<Duration> {
+        match self {
+            LockoutSubject::Account(_) => policy.account_delay(failures),
+            LockoutSubject::Ip(_) => policy.ip_delay(failures),
+        }
+    }
+
+    /// Blocages en millisecondes (0 : aucun) après 1, 2... échecs, jusqu'au
+    /// verrouillage, qui vaut aussi pour les échecs suivants.
+    pub(crate) fn delays_ms(&self, policy: &LockoutPolicy) -> Vec<u64> {
+        let last = match self {
+            LockoutSubject::Account(_) => policy.max_failures,
+            LockoutSubject::Ip(_) => policy.ip_max_failures,
+        };
+        (1..=last.max(1))
+            .map(|failures| {
+                self.delay(policy, failures)
+                    .map_or(0, |delay| (delay.as_millis() as u64).max(1))
+            })
+            .collect()
+    }
+}
+
+struct MemoryEntry {
+    failures: u32,
+    window_end: Instant,
+    blocked_until: Option<Instant>,
+    /// Rang de l'échec qui a posé `blocked_until`
+    blocked_by: u32,
+}
+
+/// Configuration anti force brute de l'`AppState`.
+///
+/// Les compteurs vivent dans Redis ; s'il est absent ou en erreur, des compteurs
+/// en mémoire, propres à l'instance, prennent le relais.
+pub struct LoginLockout {
+    policy: LockoutPolicy,
+    redis_prefix: String,
+    memory: Mutex<HashMap<String, MemoryEntry>>,
+}
+
+impl Default for LoginLockout {
+    fn default() -> Self {
+        Self::new(LockoutPolicy::default())
+    }
+}
+
+impl LoginLockout {
+    pub fn new(policy: LockoutPolicy) -> Self {
+        Self {
+            policy,
+            redis_prefix: "login_lockout".to_string(),
+            memory: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_redis_prefix(mut self, prefix: &str) -> Self {
+        self.redis_prefix = prefix.to_string();
+        self
+    }
+
+    pub fn get_policy(&self) -> &LockoutPolicy {
+        &self.policy
+    }
+
+    pub(crate) fn failures_key(&self, subject: &LockoutSubject) -> String {
+        format!("{}:failures:{}", self.redis_prefix, subject.key())
+    }
+
+    pub(crate) fn blocked_key(&self, subject: &LockoutSubject) -> String {
+        format!("{}:blocked:{}", self.redis_prefix, subject.key())
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<String, MemoryEntry>> {
+        match self.memory.lock() {
+            Ok(entries) => entries,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Compte un échec et pose le blocage qui en résulte ; renvoie son rang.
+    fn memory_count_failure(
+        &self,
+        entries: &mut HashMap<String, MemoryEntry>,
+        subject: &LockoutSubject,
+    ) -> u32 {
+        let now = Instant::now();
+        entries.retain(|_, entry| {
+            entry.window_end > now || entry.blocked_until.is_some_and(|until| until > now)
+        });
+
+        let entry = entries.entry(subject.key()).or_insert(MemoryEntry {
+            failures: 0,
+            window_end: now + self.policy.failure_window,
+            blocked_until: None,
+            blocked_by: 0,
+        });
+        entry.failures += 1;
+        if let Some(delay) = subject.delay(&self.policy, entry.failures) {
+            entry.blocked_until = Some(now + delay);
+            entry.blocked_by = entry.failures;
+        }
+        entry.failures
+    }
+
+    /// Refuse si le sujet est bloqué, sinon compte la tentative comme un échec,
+    /// sous le même verrou. Renvoie le rang de la tentative.
+    pub(crate) fn memory_reserve(&self, subject: &LockoutSubject) -> Result<u32, Duration> {
+        let mut entries = self.entries();
+        let now = Instant::now();
+        if let Some(blocked) = entries
+            .get(&subject.key())
+            .and_then(|entry| entry.blocked_until)
+            .and_then(|until| until.checked_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+        {
+            return Err(blocked);
+        }
+        Ok(self.memory_count_failure(&mut entries, subject))
+    }
+
+    /// Annule la tentative de rang `attempt`, et le blocage qu'elle a posé.
+    pub(crate) fn memory_release(&self, subject: &LockoutSubject, attempt: u32) {
+        if let Some(entry) = self.entries().get_mut(&subject.key()) {
+            entry.failures = entry.failures.saturating_sub(1);
+            if entry.blocked_by == attempt {
+                entry.blocked_until = None;
+            }
+        }
+    }
+
+    pub(crate) fn memory_reset(&self, subject: &LockoutSubject) {
+        self.entries().remove(&subject.key());
+    }
+}
@@ -0,0 +1,25 @@
+mod errors;
+pub use errors::LoginError;
+
+mod lockout_policy;
+pub use lockout_policy::LockoutPolicy;
+
+mod login_lockout;
+pub use login_lockout::{LockoutSubject, LoginLockout};
+
+mod login_attempt;
+pub use login_attempt::LoginAttempt;
+
+mod lockout_redis;
+pub use lockout_redis::{
+    record_attempt_success, release_login_attempt, reserve_login_attempt, unlock_account, unlock_ip,
+};
+
+mod guard_login;
+pub use guard_login::guard_login;
+
+#[cfg(feature = "actix")]
+mod actix_response;
+
+#[cfg(feature = "axum")]
+mod axum_response;
@@ -0,0 +1,13 @@
+use crate::metrics::{render_metrics, METRICS_CONTENT_TYPE};
+use crate::pool::AppState;
+use actix_web::{HttpRequest, HttpResponse};
+
+/// Handler actix de `/metrics` : `.route("/metrics", web::get().to(actix_metrics_handler))`.
+/// L'occupation des pools n'est relevée que si l'`AppState` est enregistré dans l'App.
+/// Derrière `JwtMiddleware`, déclarer la route avec `AppState::with_public_path("/metrics")`.
+pub async fn actix_metrics_handler(req: HttpRequest) -> HttpResponse {
+    let state = req.app_data::<actix_web::web::Data<AppState>>();
+    HttpResponse::Ok()
+        .content_type(METRICS_CONTENT_TYPE)
+        .body(render_metrics(state.map(|state| state.get_ref())))
+}
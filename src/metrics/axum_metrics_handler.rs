@@ -0,0 +1,15 @@
+use crate::metrics::{render_metrics, METRICS_CONTENT_TYPE};
+use crate::pool::AppState;
+use axum::extract::State;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+
+/// Handler axum de `/metrics` : `.route("/metrics", get(axum_metrics_handler))`, avec un
+/// état dont on peut extraire `Arc<AppState>`.
+pub async fn axum_metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, METRICS_CONTENT_TYPE)],
+        render_metrics(Some(&state)),
+    )
+}
@@ -0,0 +1,91 @@
+use once_cell::sync::Lazy;
+use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry};
+
+/// Bornes des histogrammes de durée (secondes), de 1 ms à 5 s.
+const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+#[cfg_attr(not(feature = "actix"), allow(dead_code))]
+pub(crate) struct LibMetrics {
+    pub(crate) registry: Registry,
+    pub(crate) auth_outcomes: IntCounterVec,
+    pub(crate) access_check_duration: HistogramVec,
+    pub(crate) access_cache_lookups: IntCounterVec,
+    pub(crate) db_query_duration: HistogramVec,
+    pub(crate) db_query_errors: IntCounterVec,
+    pub(crate) pool_connections: IntGaugeVec,
+}
+
+fn counter(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let counter = IntCounterVec::new(Opts::new(name, help), labels).expect("invalid counter");
+    registry
+        .register(Box::new(counter.clone()))
+        .expect("duplicate metric");
+    counter
+}
+
+fn histogram(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> HistogramVec {
+    let opts = HistogramOpts::new(name, help).buckets(DURATION_BUCKETS.to_vec());
+    let histogram = HistogramVec::new(opts, labels).expect("invalid histogram");
+    registry
+        .register(Box::new(histogram.clone()))
+        .expect("duplicate metric");
+    histogram
+}
+
+pub(crate) static METRICS: Lazy<LibMetrics> = Lazy::new(|| {
+    let registry = Registry::new();
+    let pool_connections = IntGaugeVec::new(
+        Opts::new(
+            "pool_connections",
+            "Connexions des pools de l'AppState, par état (idle, active, max)",
+        ),
+        &["pool", "state"],
+    )
+    .expect("invalid gauge");
+    registry
+        .register(Box::new(pool_connections.clone()))
+        .expect("duplicate metric");
+
+    LibMetrics {
+        auth_outcomes: counter(
+            &registry,
+            "auth_outcomes_total",
+            "Résultats de l'authentification JWT (success ou variante de JWTCheckError)",
+            &["outcome"],
+        ),
+        access_check_duration: histogram(
+            &registry,
+            "access_check_duration_seconds",
+            "Durée des vérifications des middlewares de sécurité",
+            &["middleware", "decision"],
+        ),
+        access_cache_lookups: counter(
+            &registry,
+            "access_cache_lookups_total",
+            "Consultations du cache des décisions d'accès",
+            &["tier", "result"],
+        ),
+        db_query_duration: histogram(
+            &registry,
+            "db_query_duration_seconds",
+            "Durée des requêtes exécutées via les query views",
+            &["query_view"],
+        ),
+        db_query_errors: counter(
+            &registry,
+            "db_query_errors_total",
+            "Requêtes en erreur, par query view et type d'erreur",
+            &["query_view", "error"],
+        ),
+        pool_connections,
+        registry,
+    }
+});
+
+/// Registre des métriques de la librairie, servi par les handlers `/metrics`.
+/// Les services peuvent y enregistrer leurs propres métriques.
+pub fn metrics_registry() -> &'static Registry {
+    &METRICS.registry
+}
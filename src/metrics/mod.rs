@@ -0,0 +1,20 @@
+mod lib_metrics;
+pub use lib_metrics::metrics_registry;
+
+mod recorders;
+#[cfg(feature = "actix")]
+pub(crate) use recorders::{record_access_check, record_auth_outcome};
+pub(crate) use recorders::{record_cache_lookup, record_query};
+
+mod render_metrics;
+pub use render_metrics::{render_metrics, METRICS_CONTENT_TYPE};
+
+#[cfg(feature = "actix")]
+mod actix_metrics_handler;
+#[cfg(feature = "actix")]
+pub use actix_metrics_handler::actix_metrics_handler;
+
+#[cfg(feature = "axum")]
+mod axum_metrics_handler;
+#[cfg(feature = "axum")]
+pub use axum_metrics_handler::axum_metrics_handler;
@@ -0,0 +1,92 @@
+use crate::database::errors::DatabaseError;
+use crate::metrics::lib_metrics::METRICS;
+use crate::pool::AppState;
+use deadpool_redis::Pool;
+use std::time::Duration;
+
+fn database_error_kind(error: &DatabaseError) -> &'static str {
+    match error {
+        DatabaseError::ConnectionFailed(_) => "connection_failed",
+        DatabaseError::ConnectionClosed => "connection_closed",
+        DatabaseError::NotInitialized => "not_initialized",
+        DatabaseError::DriverError(_) => "driver",
+        DatabaseError::ConfigError(_) => "config",
+        DatabaseError::Timeout => "timeout",
+        DatabaseError::Internal(_) => "internal",
+        DatabaseError::Query(_) => "query",
+    }
+}
+
+/// `outcome` : `success` ou le nom de la variante de `JWTCheckError`.
+#[cfg(feature = "actix")]
+pub(crate) fn record_auth_outcome(outcome: &str) {
+    METRICS.auth_outcomes.with_label_values(&[outcome]).inc();
+}
+
+#[cfg(feature = "actix")]
+pub(crate) fn record_access_check(middleware: &'static str, elapsed: Duration, allowed: bool) {
+    let decision = if allowed { "allow" } else { "deny" };
+    METRICS
+        .access_check_duration
+        .with_label_values(&[middleware, decision])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// `tier` : `local` ou `redis` ; un miss global est compté sur le dernier niveau consulté.
+pub(crate) fn record_cache_lookup(tier: &'static str, hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    METRICS
+        .access_cache_lookups
+        .with_label_values(&[tier, result])
+        .inc();
+}
+
+pub(crate) fn record_query(
+    query_view: &'static str,
+    elapsed: Duration,
+    error: Option<&DatabaseError>,
+) {
+    METRICS
+        .db_query_duration
+        .with_label_values(&[query_view])
+        .observe(elapsed.as_secs_f64());
+    if let Some(error) = error {
+        METRICS
+            .db_query_errors
+            .with_label_values(&[query_view, database_error_kind(error)])
+            .inc();
+    }
+}
+
+/// Relève l'occupation des pools au moment du scrape.
+pub(crate) fn record_pool_utilisation(state: &AppState) {
+    let gauge = |pool: &str, state: &str, value: usize| {
+        METRICS
+            .pool_connections
+            .with_label_values(&[pool, state])
+            .set(value as i64);
+    };
+    if let Some(pool) = state.db_pool() {
+        let idle = pool.num_idle();
+        gauge("postgres", "idle", idle);
+        gauge(
+            "postgres",
+            "active",
+            (pool.size() as usize).saturating_sub(idle),
+        );
+        gauge(
+            "postgres",
+            "max",
+            pool.options().get_max_connections() as usize,
+        );
+    }
+    if let Some(status) = state.redis_pool().map(Pool::status) {
+        gauge("redis", "idle", status.available);
+        gauge(
+            "redis",
+            "active",
+            status.size.saturating_sub(status.available),
+        );
+        gauge("redis", "max", status.max_size);
+    }
+}
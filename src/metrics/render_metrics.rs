@@ -0,0 +1,19 @@
+use crate::metrics::lib_metrics::METRICS;
+use crate::metrics::recorders::record_pool_utilisation;
+use crate::pool::AppState;
+use prometheus::{Encoder, TextEncoder};
+
+/// Type de contenu du format texte Prometheus.
+pub const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Sérialise le registre au format texte Prometheus, après relevé des pools de `state`.
+pub fn render_metrics(state: Option<&AppState>) -> String {
+    if let Some(state) = state {
+        record_pool_utilisation(state);
+    }
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&METRICS.registry.gather(), &mut buffer) {
+        tracing::error!(error = %e, "metrics encoding error");
+    }
+    String::from_utf8(buffer).unwrap_or_default()
+}
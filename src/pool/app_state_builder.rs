@@ -0,0 +1,237 @@
+use crate::cache::{AccessCache, UserCache};
+use crate::env_manager::{get_env_var, InvalidEnvVar};
+use crate::health::HealthChecks;
+use crate::lockout::LoginLockout;
+use crate::pool::{
+    spawn_postgres_reconnect, AppState, Extensions, PostgresPoolConfig, RedisPoolConfig,
+    StartupError, INVALIDATION_CHANNEL_CAPACITY,
+};
+use crate::security::TrustedProxies;
+use crate::tenant::TenantConfig;
+use deadpool_redis::{Config, CreatePoolError, Pool, Runtime};
+use sqlx::postgres::PgConnectOptions;
+use sqlx::PgPool;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(500);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Construit un `AppState` à partir des URL et des réglages des pools.
+///
+/// Une URL vide signifie que la dépendance n'est pas utilisée : son pool reste à `None`.
+/// Deux modes de démarrage :
+/// - `build` (dégradé) : le service démarre même si PostgreSQL est injoignable, et une
+///   tâche de fond retente la connexion avec un délai croissant jusqu'à installer le pool ;
+/// - `try_build` (fail-fast) : renvoie une `StartupError` si une dépendance configurée
+///   ne répond pas.
+#[derive(Clone)]
+pub struct AppStateBuilder {
+    redis_url: String,
+    postgres_url: String,
+    redis_config: RedisPoolConfig,
+    postgres_config: PostgresPoolConfig,
+    retry_delays: (Duration, Duration),
+}
+
+impl Default for AppStateBuilder {
+    fn default() -> Self {
+        Self {
+            redis_url: String::new(),
+            postgres_url: String::new(),
+            redis_config: RedisPoolConfig::default(),
+            postgres_config: PostgresPoolConfig::default(),
+            retry_delays: (MIN_RETRY_DELAY, MAX_RETRY_DELAY),
+        }
+    }
+}
+
+// Les URL contiennent les mots de passe : seule leur présence est affichée
+impl fmt::Debug for AppStateBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppStateBuilder")
+            .field("redis_url", &redacted_url(&self.redis_url))
+            .field("postgres_url", &redacted_url(&self.postgres_url))
+            .field("redis_config", &self.redis_config)
+            .field("postgres_config", &self.postgres_config)
+            .field("retry_delays", &self.retry_delays)
+            .finish()
+    }
+}
+
+fn redacted_url(url: &str) -> &'static str {
+    if url.is_empty() {
+        ""
+    } else {
+        "<redacted>"
+    }
+}
+
+impl AppStateBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lit `REDIS_URL`, `DATABASE_URL` et les réglages des pools
+    /// (voir `PostgresPoolConfig::from_env` et `RedisPoolConfig::from_env`).
+    pub fn from_env() -> Result<Self, InvalidEnvVar> {
+        Ok(Self {
+            redis_url: get_env_var("REDIS_URL").unwrap_or_default(),
+            postgres_url: get_env_var("DATABASE_URL").unwrap_or_default(),
+            redis_config: RedisPoolConfig::from_env()?,
+            postgres_config: PostgresPoolConfig::from_env()?,
+            ..Self::default()
+        })
+    }
+
+    pub fn with_redis_url(mut self, redis_url: impl Into<String>) -> Self {
+        self.redis_url = redis_url.into();
+        self
+    }
+
+    pub fn with_postgres_url(mut self, postgres_url: impl Into<String>) -> Self {
+        self.postgres_url = postgres_url.into();
+        self
+    }
+
+    pub fn with_redis_config(mut self, redis_config: RedisPoolConfig) -> Self {
+        self.redis_config = redis_config;
+        self
+    }
+
+    pub fn with_postgres_config(mut self, postgres_config: PostgresPoolConfig) -> Self {
+        self.postgres_config = postgres_config;
+        self
+    }
+
+    /// Délais entre deux tentatives de reconnexion en mode dégradé : le premier,
+    /// doublé à chaque échec jusqu'au second (défaut : 500 ms puis jusqu'à 30 s).
+    pub fn with_retry_delays(
+        mut self,
+        min_retry_delay: Duration,
+        max_retry_delay: Duration,
+    ) -> Self {
+        self.retry_delays = (min_retry_delay, max_retry_delay);
+        self
+    }
+
+    pub fn redis_config(&self) -> &RedisPoolConfig {
+        &self.redis_config
+    }
+
+    pub fn postgres_config(&self) -> &PostgresPoolConfig {
+        &self.postgres_config
+    }
+
+    /// Mode dégradé : n'échoue jamais, les dépendances absentes restent à `None`.
+    ///
+    /// Le pool Redis se reconnecte de lui-même à chaque `get` ; seul le pool PostgreSQL,
+    /// qui exige une première connexion, est complété par une tâche de fond.
+    pub async fn build(self) -> AppState {
+        // --- Initialisation Redis ---
+        // Les URL ne sont pas journalisées : elles contiennent les mots de passe
+        let redis_pool = self.create_redis_pool().unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Redis pool creation failed");
+            None
+        });
+
+        // --- Initialisation PostgreSQL ---
+        let db_pool = Arc::new(OnceLock::new());
+        match self.postgres_connect_options() {
+            Ok(Some(options)) => {
+                match self
+                    .postgres_config
+                    .pool_options()
+                    .connect_with(options.clone())
+                    .await
+                {
+                    Ok(pool) => {
+                        let _ = db_pool.set(pool);
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "PostgreSQL connection failed, retrying in background");
+                        spawn_postgres_reconnect(
+                            Arc::downgrade(&db_pool),
+                            self.postgres_config.clone(),
+                            options,
+                            self.retry_delays,
+                        );
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "Invalid PostgreSQL configuration"),
+        }
+
+        Self::into_state(redis_pool, db_pool)
+    }
+
+    /// Mode fail-fast : vérifie que chaque dépendance configurée répond avant de
+    /// renvoyer l'`AppState`.
+    pub async fn try_build(self) -> Result<AppState, StartupError> {
+        let redis_pool = self.create_redis_pool()?;
+        if let Some(pool) = &redis_pool {
+            pool.get().await?;
+        }
+
+        let db_pool = Arc::new(OnceLock::new());
+        if let Some(options) = self.postgres_connect_options()? {
+            let pool = self
+                .postgres_config
+                .pool_options()
+                .connect_with(options)
+                .await?;
+            let _ = db_pool.set(pool);
+        }
+
+        Ok(Self::into_state(redis_pool, db_pool))
+    }
+
+    fn create_redis_pool(&self) -> Result<Option<Pool>, CreatePoolError> {
+        if self.redis_url.is_empty() {
+            return Ok(None);
+        }
+        let redis_cfg = Config {
+            pool: Some(self.redis_config.to_pool_config()),
+            ..Config::from_url(self.redis_url.as_str())
+        };
+        redis_cfg.create_pool(Some(Runtime::Tokio1)).map(Some)
+    }
+
+    fn postgres_connect_options(&self) -> Result<Option<PgConnectOptions>, sqlx::Error> {
+        if self.postgres_url.is_empty() {
+            return Ok(None);
+        }
+        self.postgres_config
+            .connect_options(&self.postgres_url)
+            .map(Some)
+    }
+
+    fn into_state(redis_pool: Option<Pool>, db_pool: Arc<OnceLock<PgPool>>) -> AppState {
+        tracing::info!(
+            redis = redis_pool.is_some(),
+            postgres = db_pool.get().is_some(),
+            "AppState initialized"
+        );
+
+        AppState {
+            redis_pool,
+            db_pool,
+            access_cache: AccessCache::default(),
+            user_cache: UserCache::default(),
+            rebac_engine: None,
+            policy_engine: None,
+            tenant_config: TenantConfig::default(),
+            login_lockout: LoginLockout::default(),
+            trusted_proxies: TrustedProxies::default(),
+            public_paths: Vec::new(),
+            optional_auth_paths: Vec::new(),
+            health_checks: HealthChecks::default(),
+            audit_queue: None,
+            invalidation_sender: broadcast::channel(INVALIDATION_CHANNEL_CAPACITY).0,
+            extensions: Extensions::default(),
+        }
+    }
+}
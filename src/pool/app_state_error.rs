@@ -0,0 +1,68 @@
+use crate::database::errors::DatabaseError;
+use thiserror::Error;
+
+/// Échec d'obtention d'une connexion depuis un pool de l'`AppState`.
+///
+/// `pool` vaut `"postgres"` ou `"redis"`.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum AppStateError {
+    #[error("{pool} pool is not initialized")]
+    PoolMissing { pool: &'static str },
+
+    #[error("Timed out waiting for a {pool} connection")]
+    Timeout { pool: &'static str },
+
+    #[error("{pool} pool is closed")]
+    PoolClosed { pool: &'static str },
+
+    #[error("Failed to open a {pool} connection: {message}")]
+    ConnectionFailed { pool: &'static str, message: String },
+}
+
+impl AppStateError {
+    pub fn get_pool(&self) -> &'static str {
+        match self {
+            AppStateError::PoolMissing { pool }
+            | AppStateError::Timeout { pool }
+            | AppStateError::PoolClosed { pool }
+            | AppStateError::ConnectionFailed { pool, .. } => pool,
+        }
+    }
+
+    pub(crate) fn from_sqlx(err: sqlx::Error) -> Self {
+        let pool = "postgres";
+        match err {
+            sqlx::Error::PoolTimedOut => AppStateError::Timeout { pool },
+            sqlx::Error::PoolClosed => AppStateError::PoolClosed { pool },
+            _ => AppStateError::ConnectionFailed {
+                pool,
+                message: err.to_string(),
+            },
+        }
+    }
+
+    pub(crate) fn from_deadpool(err: deadpool_redis::PoolError) -> Self {
+        let pool = "redis";
+        match err {
+            deadpool_redis::PoolError::Timeout(_) => AppStateError::Timeout { pool },
+            deadpool_redis::PoolError::Closed => AppStateError::PoolClosed { pool },
+            _ => AppStateError::ConnectionFailed {
+                pool,
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<AppStateError> for DatabaseError {
+    fn from(err: AppStateError) -> Self {
+        match err {
+            AppStateError::PoolMissing { .. } => DatabaseError::NotInitialized,
+            AppStateError::Timeout { .. } => DatabaseError::Timeout,
+            AppStateError::PoolClosed { .. } => DatabaseError::ConnectionClosed,
+            AppStateError::ConnectionFailed { .. } => {
+                DatabaseError::ConnectionFailed(err.to_string())
+            }
+        }
+    }
+}
@@ -1,52 +1,279 @@
+mod app_state_builder;
+pub use app_state_builder::AppStateBuilder;
+mod app_state_error;
+pub use app_state_error::AppStateError;
+mod extensions;
+pub use extensions::Extensions;
+mod postgres_pool_config;
+pub use postgres_pool_config::PostgresPoolConfig;
 pub mod redis;
-use deadpool_redis::{Config, Pool, Runtime};
-use sqlx::postgres::PgPoolOptions;
+mod redis_pool_config;
+pub use redis_pool_config::RedisPoolConfig;
+mod spawn_postgres_reconnect;
+use spawn_postgres_reconnect::spawn_postgres_reconnect;
+mod startup_error;
+pub use startup_error::StartupError;
+
+use crate::abac::PolicyEngine;
+use crate::audit::{AuditQueue, AuditSink};
+use crate::cache::{AccessCache, InvalidationEvent, UserCache};
+use crate::database::errors::DatabaseError;
+use crate::health::HealthChecks;
+use crate::lockout::LoginLockout;
+use crate::rebac::RebacEngine;
+use crate::security::TrustedProxies;
+use crate::tenant::TenantConfig;
+use deadpool_redis::Pool;
 use sqlx::PgPool;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::broadcast;
+
+const INVALIDATION_CHANNEL_CAPACITY: usize = 256;
 
 pub struct AppState {
     redis_pool: Option<Pool>,
-    pub db_pool: Option<PgPool>,
+    db_pool: Arc<OnceLock<PgPool>>,
+    access_cache: AccessCache,
+    user_cache: UserCache,
+    rebac_engine: Option<Arc<RebacEngine>>,
+    policy_engine: Option<Arc<PolicyEngine>>,
+    tenant_config: TenantConfig,
+    login_lockout: LoginLockout,
+    trusted_proxies: TrustedProxies,
+    public_paths: Vec<String>,
+    optional_auth_paths: Vec<String>,
+    health_checks: HealthChecks,
+    audit_queue: Option<AuditQueue>,
+    invalidation_sender: broadcast::Sender<InvalidationEvent>,
+    extensions: Extensions,
 }
 
 impl AppState {
+    /// Pools aux réglages par défaut, démarrage en mode dégradé (voir `AppStateBuilder`).
     pub async fn new(redis_url: String, pg_url: String) -> Self {
-        // --- Initialisation Redis ---
-        let redis_cfg = Config::from_url(redis_url);
-        let redis_pool = redis_cfg.create_pool(Some(Runtime::Tokio1));
-
-        // --- Initialisation PostgreSQL ---
-        let db_pool = PgPoolOptions::new()
-            .max_connections(5)
-            .acquire_timeout(std::time::Duration::from_secs(3))
-            .connect(&pg_url)
-            .await;
-
-        eprintln!("redis status: {:?}", redis_pool.is_ok());
-        eprintln!("pg status: {:?}", db_pool.is_ok());
-
-        Self {
-            redis_pool: match redis_pool {
-                Ok(pool) => Some(pool),
-                Err(_) => None,
-            },
-            db_pool: match db_pool {
-                Ok(pool) => Some(pool),
-                Err(_) => None,
-            },
-        }
-    }
-
-    pub async fn get_redis_conn(&self) -> Option<deadpool_redis::Connection> {
-        match &self.redis_pool {
-            Some(pool) => pool.get().await.ok(),
-            None => None,
-        }
-    }
-
-    pub async fn get_db_conn(&self) -> Option<sqlx::pool::PoolConnection<sqlx::Postgres>> {
-        match &self.db_pool {
-            Some(pool) => pool.acquire().await.ok(),
-            None => None,
-        }
+        AppStateBuilder::new()
+            .with_redis_url(redis_url)
+            .with_postgres_url(pg_url)
+            .build()
+            .await
+    }
+
+    pub fn builder() -> AppStateBuilder {
+        AppStateBuilder::new()
+    }
+
+    /// Pool PostgreSQL, `None` tant que la base n'a pas pu être jointe.
+    pub fn db_pool(&self) -> Option<&PgPool> {
+        self.db_pool.get()
+    }
+
+    /// Pool Redis, `None` si aucune URL n'a été fournie ou si elle est invalide.
+    pub fn redis_pool(&self) -> Option<&Pool> {
+        self.redis_pool.as_ref()
+    }
+
+    /// Installe un pool déjà construit, à la place de celui de l'`AppStateBuilder`.
+    pub fn with_redis_pool(mut self, pool: Pool) -> Self {
+        self.redis_pool = Some(pool);
+        self
+    }
+
+    /// Installe un pool déjà construit, à la place de celui de l'`AppStateBuilder`.
+    pub fn with_db_pool(mut self, pool: PgPool) -> Self {
+        self.db_pool = Arc::new(OnceLock::from(pool));
+        self
+    }
+
+    /// Remplace le cache des décisions d'accès (taille, TTL, niveau Redis).
+    pub fn with_access_cache(mut self, access_cache: AccessCache) -> Self {
+        self.access_cache = access_cache;
+        self
+    }
+
+    pub fn access_cache(&self) -> &AccessCache {
+        &self.access_cache
+    }
+
+    /// Remplace le cache des informations utilisateur (`is_admin`).
+    pub fn with_user_cache(mut self, user_cache: UserCache) -> Self {
+        self.user_cache = user_cache;
+        self
+    }
+
+    pub fn user_cache(&self) -> &UserCache {
+        &self.user_cache
+    }
+
+    /// Délègue au moteur ReBAC les vérifications d'accès sur les ressources et
+    /// actions déclarées dans son schéma, à la place de la fonction SQL `check_access`.
+    pub fn with_rebac_engine(mut self, engine: RebacEngine) -> Self {
+        self.rebac_engine = Some(Arc::new(engine));
+        self
+    }
+
+    pub fn rebac_engine(&self) -> Option<&Arc<RebacEngine>> {
+        self.rebac_engine.as_ref()
+    }
+
+    /// Ajoute les politiques ABAC évaluées par `access_guard_middleware`
+    /// une fois l'accès accordé par la base ou le moteur ReBAC.
+    pub fn with_policy_engine(mut self, engine: PolicyEngine) -> Self {
+        self.policy_engine = Some(Arc::new(engine));
+        self
+    }
+
+    pub fn policy_engine(&self) -> Option<&Arc<PolicyEngine>> {
+        self.policy_engine.as_ref()
+    }
+
+    /// Sources de la commune courante et rôle super-administrateur, lus par
+    /// `access_guard_middleware` et l'extracteur `CurrentTenant`.
+    pub fn with_tenant_config(mut self, tenant_config: TenantConfig) -> Self {
+        self.tenant_config = tenant_config;
+        self
+    }
+
+    pub fn tenant_config(&self) -> &TenantConfig {
+        &self.tenant_config
+    }
+
+    /// Politique anti force brute appliquée par `guard_login`.
+    pub fn with_login_lockout(mut self, login_lockout: LoginLockout) -> Self {
+        self.login_lockout = login_lockout;
+        self
+    }
+
+    pub fn login_lockout(&self) -> &LoginLockout {
+        &self.login_lockout
+    }
+
+    /// Proxies dont les en-têtes `Forwarded` / `X-Forwarded-*` sont crus pour l'adresse
+    /// du client (ABAC, limitation de débit, audit) et l'hôte (commune par sous-domaine).
+    pub fn with_trusted_proxies(mut self, trusted_proxies: TrustedProxies) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+
+    pub fn trusted_proxies(&self) -> &TrustedProxies {
+        &self.trusted_proxies
     }
+
+    /// Laisse passer sans JWT, dans `JwtMiddleware`, la route `path_prefix` et ses
+    /// sous-routes (ex: `/health` couvre `/health/live` mais pas `/healthcare`), en plus
+    /// de `/`, `/swagger-ui`, `/api-docs` et `/auth`.
+    pub fn with_public_path(mut self, path_prefix: &str) -> Self {
+        self.public_paths
+            .push(path_prefix.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn is_public_path(&self, path: &str) -> bool {
+        self.public_paths
+            .iter()
+            .any(|prefix| is_under_path(prefix, path))
+    }
+
+    /// Authentification facultative dans `JwtMiddleware` pour la route `path_prefix` et
+    /// ses sous-routes : un token valide attache l'`AuthenticatedUser`, une requête sans
+    /// token passe en anonyme (`MaybeAuthenticated`), un token invalide reste refusé.
+    pub fn with_optional_auth_path(mut self, path_prefix: &str) -> Self {
+        self.optional_auth_paths
+            .push(path_prefix.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn is_optional_auth_path(&self, path: &str) -> bool {
+        self.optional_auth_paths
+            .iter()
+            .any(|prefix| is_under_path(prefix, path))
+    }
+
+    /// Remplace les dépendances vérifiées par `/health/ready`.
+    pub fn with_health_checks(mut self, health_checks: HealthChecks) -> Self {
+        self.health_checks = health_checks;
+        self
+    }
+
+    pub fn health_checks(&self) -> &HealthChecks {
+        &self.health_checks
+    }
+
+    /// Destination des événements d'audit émis par `JwtMiddleware`, `AdminMiddleware`,
+    /// `access_guard_middleware` et `guard_login` (ex: `PgAuditSink`). Les middlewares
+    /// d'authentification passent par une file bornée (voir `queue_audit_event`).
+    pub fn with_audit_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit_queue = Some(AuditQueue::new(Arc::new(sink)));
+        self
+    }
+
+    pub fn audit_sink(&self) -> Option<&Arc<dyn AuditSink>> {
+        self.audit_queue.as_ref().map(AuditQueue::sink)
+    }
+
+    pub(crate) fn audit_queue(&self) -> Option<&AuditQueue> {
+        self.audit_queue.as_ref()
+    }
+
+    /// Ajoute un état propre à l'API, retrouvé ensuite par son type avec `extension`
+    /// (une valeur du même type est remplacée).
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.get()
+    }
+
+    pub fn extensions(&self) -> &Extensions {
+        &self.extensions
+    }
+
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.extensions
+    }
+
+    /// Reçoit les invalidations appliquées par `spawn_invalidation_listener`, pour que
+    /// les caches propres à chaque API suivent les mêmes évictions.
+    pub fn subscribe_invalidations(&self) -> broadcast::Receiver<InvalidationEvent> {
+        self.invalidation_sender.subscribe()
+    }
+
+    pub(crate) fn invalidation_sender(&self) -> &broadcast::Sender<InvalidationEvent> {
+        &self.invalidation_sender
+    }
+
+    pub async fn get_redis_conn(&self) -> Result<deadpool_redis::Connection, AppStateError> {
+        let pool = self
+            .redis_pool()
+            .ok_or(AppStateError::PoolMissing { pool: "redis" })?;
+        pool.get().await.map_err(AppStateError::from_deadpool)
+    }
+
+    pub async fn get_db_conn(
+        &self,
+    ) -> Result<sqlx::pool::PoolConnection<sqlx::Postgres>, AppStateError> {
+        let pool = self
+            .db_pool()
+            .ok_or(AppStateError::PoolMissing { pool: "postgres" })?;
+        pool.acquire().await.map_err(AppStateError::from_sqlx)
+    }
+
+    /// Connexion Redis, l'erreur étant convertie en `DatabaseError` pour remonter
+    /// telle quelle depuis un handler (`?`).
+    pub async fn redis(&self) -> Result<deadpool_redis::Connection, DatabaseError> {
+        Ok(self.get_redis_conn().await?)
+    }
+
+    /// Connexion PostgreSQL, l'erreur étant convertie en `DatabaseError` pour remonter
+    /// telle quelle depuis un handler (`?`).
+    pub async fn db(&self) -> Result<sqlx::pool::PoolConnection<sqlx::Postgres>, DatabaseError> {
+        Ok(self.get_db_conn().await?)
+    }
+}
+
+/// `path` vaut `prefix` ou en est une sous-route (`prefix/...`).
+fn is_under_path(prefix: &str, path: &str) -> bool {
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
 }
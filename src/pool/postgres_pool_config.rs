@@ -0,0 +1,119 @@
+use crate::env_manager::{get_env_var, parse_env_var, InvalidEnvVar};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Réglages du pool Postgres de l'`AppState`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostgresPoolConfig {
+    pub min_connections: u32,
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+    /// Durée après laquelle une connexion inutilisée est fermée (`None` : jamais)
+    pub idle_timeout: Option<Duration>,
+    /// Durée de vie maximale d'une connexion (`None` : illimitée)
+    pub max_lifetime: Option<Duration>,
+    /// `statement_timeout` de chaque connexion (`None` : réglage du serveur)
+    pub statement_timeout: Option<Duration>,
+    /// `application_name` visible dans `pg_stat_activity`
+    pub application_name: Option<String>,
+}
+
+impl Default for PostgresPoolConfig {
+    fn default() -> Self {
+        Self {
+            min_connections: 0,
+            max_connections: 5,
+            acquire_timeout: Duration::from_secs(3),
+            idle_timeout: Some(Duration::from_secs(10 * 60)),
+            max_lifetime: Some(Duration::from_secs(30 * 60)),
+            statement_timeout: None,
+            application_name: None,
+        }
+    }
+}
+
+impl PostgresPoolConfig {
+    /// Lit `PG_POOL_MIN_CONNECTIONS`, `PG_POOL_MAX_CONNECTIONS`, `PG_POOL_ACQUIRE_TIMEOUT_MS`,
+    /// `PG_POOL_IDLE_TIMEOUT_MS`, `PG_POOL_MAX_LIFETIME_MS`, `PG_STATEMENT_TIMEOUT_MS` et
+    /// `PG_APPLICATION_NAME`. Une durée à 0 désactive le délai correspondant.
+    pub fn from_env() -> Result<Self, InvalidEnvVar> {
+        let defaults = Self::default();
+        let millis =
+            |name: &str| parse_env_var::<u64>(name).map(|ms| ms.map(Duration::from_millis));
+        let optional = |name: &str, default: Option<Duration>| {
+            millis(name).map(|value| match value {
+                Some(duration) if duration.is_zero() => None,
+                Some(duration) => Some(duration),
+                None => default,
+            })
+        };
+        Ok(Self {
+            min_connections: parse_env_var("PG_POOL_MIN_CONNECTIONS")?
+                .unwrap_or(defaults.min_connections),
+            max_connections: parse_env_var("PG_POOL_MAX_CONNECTIONS")?
+                .unwrap_or(defaults.max_connections),
+            acquire_timeout: millis("PG_POOL_ACQUIRE_TIMEOUT_MS")?
+                .unwrap_or(defaults.acquire_timeout),
+            idle_timeout: optional("PG_POOL_IDLE_TIMEOUT_MS", defaults.idle_timeout)?,
+            max_lifetime: optional("PG_POOL_MAX_LIFETIME_MS", defaults.max_lifetime)?,
+            statement_timeout: optional("PG_STATEMENT_TIMEOUT_MS", defaults.statement_timeout)?,
+            application_name: get_env_var("PG_APPLICATION_NAME").or(defaults.application_name),
+        })
+    }
+
+    pub fn with_connections(mut self, min_connections: u32, max_connections: u32) -> Self {
+        self.min_connections = min_connections;
+        self.max_connections = max_connections;
+        self
+    }
+
+    pub fn with_acquire_timeout(mut self, acquire_timeout: Duration) -> Self {
+        self.acquire_timeout = acquire_timeout;
+        self
+    }
+
+    pub fn with_idle_timeout(mut self, idle_timeout: Option<Duration>) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    pub fn with_max_lifetime(mut self, max_lifetime: Option<Duration>) -> Self {
+        self.max_lifetime = max_lifetime;
+        self
+    }
+
+    pub fn with_statement_timeout(mut self, statement_timeout: Duration) -> Self {
+        self.statement_timeout = Some(statement_timeout);
+        self
+    }
+
+    pub fn with_application_name(mut self, application_name: &str) -> Self {
+        self.application_name = Some(application_name.to_string());
+        self
+    }
+
+    /// Options de connexion de `pg_url` complétées de `application_name` et `statement_timeout`.
+    pub(crate) fn connect_options(&self, pg_url: &str) -> Result<PgConnectOptions, sqlx::Error> {
+        let mut options = PgConnectOptions::from_str(pg_url)?;
+        if let Some(application_name) = &self.application_name {
+            options = options.application_name(application_name);
+        }
+        if let Some(statement_timeout) = self.statement_timeout {
+            options = options.options([(
+                "statement_timeout",
+                statement_timeout.as_millis().to_string(),
+            )]);
+        }
+        Ok(options)
+    }
+
+    pub(crate) fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .min_connections(self.min_connections)
+            .max_connections(self.max_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+    }
+}
@@ -1,4 +1,6 @@
 pub mod simple_key;
 
+#[cfg(feature = "axum")]
 mod handle_get;
+#[cfg(feature = "axum")]
 pub use handle_get::handle_get;
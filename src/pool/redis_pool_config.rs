@@ -0,0 +1,61 @@
+use crate::env_manager::{parse_env_var, InvalidEnvVar};
+use deadpool_redis::{PoolConfig, Timeouts};
+use std::time::Duration;
+
+/// Réglages du pool Redis de l'`AppState`. Un champ à `None` garde la valeur par
+/// défaut de deadpool (taille proportionnelle au nombre de CPU, délais illimités).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RedisPoolConfig {
+    pub max_size: Option<usize>,
+    /// Attente d'une connexion libre quand le pool est plein
+    pub wait_timeout: Option<Duration>,
+    /// Ouverture d'une nouvelle connexion
+    pub create_timeout: Option<Duration>,
+    /// Vérification d'une connexion rendue au pool
+    pub recycle_timeout: Option<Duration>,
+}
+
+impl RedisPoolConfig {
+    /// Lit `REDIS_POOL_MAX_SIZE`, `REDIS_POOL_WAIT_TIMEOUT_MS`, `REDIS_POOL_CREATE_TIMEOUT_MS`
+    /// et `REDIS_POOL_RECYCLE_TIMEOUT_MS`.
+    pub fn from_env() -> Result<Self, InvalidEnvVar> {
+        let millis =
+            |name: &str| parse_env_var::<u64>(name).map(|ms| ms.map(Duration::from_millis));
+        Ok(Self {
+            max_size: parse_env_var("REDIS_POOL_MAX_SIZE")?,
+            wait_timeout: millis("REDIS_POOL_WAIT_TIMEOUT_MS")?,
+            create_timeout: millis("REDIS_POOL_CREATE_TIMEOUT_MS")?,
+            recycle_timeout: millis("REDIS_POOL_RECYCLE_TIMEOUT_MS")?,
+        })
+    }
+
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn with_timeouts(
+        mut self,
+        wait_timeout: Duration,
+        create_timeout: Duration,
+        recycle_timeout: Duration,
+    ) -> Self {
+        self.wait_timeout = Some(wait_timeout);
+        self.create_timeout = Some(create_timeout);
+        self.recycle_timeout = Some(recycle_timeout);
+        self
+    }
+
+    pub(crate) fn to_pool_config(&self) -> PoolConfig {
+        let mut pool_config = PoolConfig::default();
+        if let Some(max_size) = self.max_size {
+            pool_config.max_size = max_size;
+        }
+        pool_config.timeouts = Timeouts {
+            wait: self.wait_timeout,
+            create: self.create_timeout,
+            recycle: self.recycle_timeout,
+        };
+        pool_config
+    }
+}
@@ -0,0 +1,47 @@
+use crate::pool::PostgresPoolConfig;
+use sqlx::postgres::PgConnectOptions;
+use sqlx::PgPool;
+use std::sync::{OnceLock, Weak};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Relance la connexion PostgreSQL avec un délai croissant jusqu'à ce qu'elle aboutisse,
+/// puis installe le pool dans `slot`. La tâche s'arrête si l'`AppState` est libéré.
+pub(crate) fn spawn_postgres_reconnect(
+    slot: Weak<OnceLock<PgPool>>,
+    config: PostgresPoolConfig,
+    connect_options: PgConnectOptions,
+    (min_retry_delay, max_retry_delay): (Duration, Duration),
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut retry_delay = min_retry_delay;
+        loop {
+            tokio::time::sleep(retry_delay).await;
+            if slot.strong_count() == 0 {
+                return;
+            }
+            match config
+                .pool_options()
+                .connect_with(connect_options.clone())
+                .await
+            {
+                Ok(pool) => {
+                    if let Some(slot) = slot.upgrade() {
+                        if slot.set(pool).is_ok() {
+                            tracing::info!("PostgreSQL connection established");
+                        }
+                    }
+                    return;
+                }
+                Err(e) => {
+                    retry_delay = (retry_delay * 2).min(max_retry_delay);
+                    tracing::warn!(
+                        error = %e,
+                        retry_in_ms = retry_delay.as_millis() as u64,
+                        "PostgreSQL reconnection failed"
+                    );
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,13 @@
+use deadpool_redis::{CreatePoolError, PoolError};
+use thiserror::Error;
+
+/// Dépendance indisponible au démarrage en mode fail-fast (`AppStateBuilder::try_build`).
+#[derive(Debug, Error)]
+pub enum StartupError {
+    #[error("PostgreSQL unavailable: {0}")]
+    Postgres(#[from] sqlx::Error),
+    #[error("Redis pool configuration error: {0}")]
+    RedisConfig(#[from] CreatePoolError),
+    #[error("Redis unavailable: {0}")]
+    Redis(#[from] PoolError),
+}
@@ -0,0 +1,40 @@
+use crate::rate_limit::{Quota, RateLimitDecision};
+use std::time::Duration;
+
+/**
+ * GCRA (Generic Cell Rate Algorithm) : l'état se résume au « theoretical arrival
+ * time » (`tat`) du prochain créneau libre. Même calcul que `GCRA_SCRIPT` côté Redis.
+ *
+ * Renvoie la décision et le nouveau `tat` à enregistrer si la requête est acceptée.
+ */
+pub(crate) fn gcra(
+    now_ms: u64,
+    tat_ms: Option<u64>,
+    quota: &Quota,
+) -> (RateLimitDecision, Option<u64>) {
+    let interval = quota.emission_interval_ms();
+    let tolerance = quota.burst_tolerance_ms();
+    let tat = tat_ms.unwrap_or(now_ms).max(now_ms);
+    let new_tat = tat + interval;
+    let allow_at = new_tat.saturating_sub(tolerance);
+
+    if now_ms < allow_at {
+        let decision = RateLimitDecision {
+            allowed: false,
+            limit: quota.get_limit(),
+            remaining: 0,
+            reset_after: Duration::from_millis(tat - now_ms),
+            retry_after: Some(Duration::from_millis(allow_at - now_ms)),
+        };
+        return (decision, None);
+    }
+
+    let decision = RateLimitDecision {
+        allowed: true,
+        limit: quota.get_limit(),
+        remaining: ((tolerance - (new_tat - now_ms)) / interval) as u32,
+        reset_after: Duration::from_millis(new_tat - now_ms),
+        retry_after: None,
+    };
+    (decision, Some(new_tat))
+}
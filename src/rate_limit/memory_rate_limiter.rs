@@ -0,0 +1,56 @@
+use crate::rate_limit::gcra::gcra;
+use crate::rate_limit::{Quota, RateLimitDecision};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Au-delà de ce nombre de clés, les compteurs expirés sont purgés.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Limiteur local au processus, utilisé quand Redis est absent ou en erreur.
+/// Les quotas ne sont alors pas partagés entre les instances d'une API.
+pub struct MemoryRateLimiter {
+    origin: Instant,
+    buckets: Mutex<HashMap<String, u64>>,
+}
+
+impl Default for MemoryRateLimiter {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl MemoryRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check(&self, key: &str, quota: &Quota) -> RateLimitDecision {
+        let now_ms = self.origin.elapsed().as_millis() as u64;
+        let mut buckets = match self.buckets.lock() {
+            Ok(buckets) => buckets,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, tat| *tat > now_ms);
+        }
+
+        let (decision, new_tat) = gcra(now_ms, buckets.get(key).copied(), quota);
+        if let Some(new_tat) = new_tat {
+            buckets.insert(key.to_string(), new_tat);
+        }
+        decision
+    }
+
+    pub fn len(&self) -> usize {
+        self.buckets.lock().map(|b| b.len()).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
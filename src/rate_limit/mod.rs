@@ -0,0 +1,24 @@
+mod quota;
+pub use quota::Quota;
+
+mod rate_limit_decision;
+pub use rate_limit_decision::RateLimitDecision;
+
+mod gcra;
+
+mod memory_rate_limiter;
+pub use memory_rate_limiter::MemoryRateLimiter;
+
+mod redis_rate_limiter;
+pub use redis_rate_limiter::GCRA_SCRIPT;
+
+mod rate_limit_key;
+pub use rate_limit_key::RateLimitKey;
+
+mod rate_limiter;
+pub use rate_limiter::RateLimiter;
+
+#[cfg(feature = "actix")]
+mod rate_limit_middleware;
+#[cfg(feature = "actix")]
+pub use rate_limit_middleware::RateLimitMiddleware;
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+/// Nombre de requêtes autorisées par période, rafale comprise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quota {
+    limit: u32,
+    period: Duration,
+}
+
+impl Quota {
+    /// `limit` est ramené à 1 au minimum.
+    pub fn new(limit: u32, period: Duration) -> Self {
+        Self {
+            limit: limit.max(1),
+            period,
+        }
+    }
+
+    pub fn per_second(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(60))
+    }
+
+    pub fn per_hour(limit: u32) -> Self {
+        Self::new(limit, Duration::from_secs(3600))
+    }
+
+    pub fn get_limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn get_period(&self) -> Duration {
+        self.period
+    }
+
+    /// Intervalle entre deux requêtes en régime établi, en millisecondes (1 au minimum).
+    pub fn emission_interval_ms(&self) -> u64 {
+        (self.period.as_millis() as u64 / u64::from(self.limit)).max(1)
+    }
+
+    /// Tolérance de rafale : `limit` requêtes consécutives sont acceptées.
+    pub fn burst_tolerance_ms(&self) -> u64 {
+        self.emission_interval_ms() * u64::from(self.limit)
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+/// Verdict du limiteur pour une requête, avec de quoi remplir les en-têtes
+/// `RateLimit-Limit`, `RateLimit-Remaining`, `RateLimit-Reset` et `Retry-After`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Délai avant que le quota soit de nouveau complet
+    pub reset_after: Duration,
+    /// Délai avant la prochaine requête acceptée, si celle-ci est refusée
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitDecision {
+    /// Décision sans limite, utilisée quand aucun quota ne s'applique.
+    pub fn unlimited() -> Self {
+        Self {
+            allowed: true,
+            limit: u32::MAX,
+            remaining: u32::MAX,
+            reset_after: Duration::ZERO,
+            retry_after: None,
+        }
+    }
+
+    pub fn is_allowed(&self) -> bool {
+        self.allowed
+    }
+}
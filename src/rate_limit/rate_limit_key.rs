@@ -0,0 +1,28 @@
+use sha2::{Digest, Sha256};
+
+/// Identité à laquelle le quota est appliqué.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitKey {
+    /// Adresse IP de la connexion ; `Forwarded` / `X-Forwarded-For` ne sont pris en
+    /// compte que derrière un proxy déclaré dans `TrustedProxies`
+    #[default]
+    Ip,
+    /// `AuthenticatedUser` placé par `JwtMiddleware`, sinon l'IP
+    User,
+    /// Valeur de l'en-tête donné (ex: `X-Api-Key`) si elle est acceptée par
+    /// `RateLimiter::with_api_key_validator`, sinon l'IP
+    ApiKey(String),
+}
+
+impl RateLimitKey {
+    /// Identifiant du compteur. La clé d'API est hachée pour ne jamais apparaître dans Redis.
+    pub fn subject(&self, ip: Option<&str>, user_id: Option<u64>, api_key: Option<&str>) -> String {
+        match (self, user_id, api_key) {
+            (RateLimitKey::User, Some(id), _) => format!("user:{}", id),
+            (RateLimitKey::ApiKey(_), _, Some(key)) => {
+                format!("key:{}", hex::encode(Sha256::digest(key.as_bytes())))
+            }
+            _ => format!("ip:{}", ip.unwrap_or("unknown")),
+        }
+    }
+}
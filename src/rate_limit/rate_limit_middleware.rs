@@ -0,0 +1,144 @@
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER},
+    Error, HttpMessage, HttpResponse,
+};
+use futures_util::future::LocalBoxFuture;
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::Instrument;
+
+use crate::pool::AppState;
+use crate::rate_limit::{RateLimitDecision, RateLimitKey, RateLimiter};
+use crate::security::{client_ip, record_check_latency, request_span, AuthenticatedUser};
+
+fn seconds_ceil(duration: std::time::Duration) -> u64 {
+    duration.as_millis().div_ceil(1000) as u64
+}
+
+fn insert_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    if decision.limit == u32::MAX {
+        return;
+    }
+    let values = [
+        ("ratelimit-limit", decision.limit as u64),
+        ("ratelimit-remaining", decision.remaining as u64),
+        ("ratelimit-reset", seconds_ceil(decision.reset_after)),
+    ];
+    for (name, value) in values {
+        headers.insert(HeaderName::from_static(name), HeaderValue::from(value));
+    }
+    if let Some(retry_after) = decision.retry_after {
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from(seconds_ceil(retry_after).max(1)),
+        );
+    }
+}
+
+/**
+ * Middleware limitant le débit des requêtes selon un `RateLimiter`.
+ * Pour limiter par utilisateur, il doit s'exécuter après `JwtMiddleware`
+ * (c'est-à-dire être déclaré avant lui avec `wrap`).
+ * Les requêtes refusées reçoivent un 429 avec `Retry-After`.
+ */
+#[derive(Clone)]
+pub struct RateLimitMiddleware {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self {
+            limiter: Arc::new(limiter),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimitMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddlewareService {
+            service: Rc::new(service),
+            limiter: self.limiter.clone(),
+        }))
+    }
+}
+
+pub struct RateLimitMiddlewareService<S> {
+    service: Rc<S>,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+        let limiter = self.limiter.clone();
+        let span = request_span("rate_limit", req.request());
+
+        Box::pin(
+            async move {
+                let Some(app_state) = req.app_data::<actix_web::web::Data<AppState>>().cloned()
+                else {
+                    let res = HttpResponse::InternalServerError()
+                        .body("AppState missing")
+                        .map_into_right_body();
+                    return Ok(req.into_response(res));
+                };
+
+                let api_key = match limiter.get_key() {
+                    RateLimitKey::ApiKey(header) => req
+                        .headers()
+                        .get(header.as_str())
+                        .and_then(|value| value.to_str().ok())
+                        .filter(|value| limiter.is_valid_api_key(value))
+                        .map(str::to_string),
+                    _ => None,
+                };
+                let user_id = req.extensions().get::<AuthenticatedUser>().map(|u| u.id);
+                let ip = client_ip(req.request());
+                let subject = limiter
+                    .get_key()
+                    .subject(ip.as_deref(), user_id, api_key.as_deref());
+
+                let started = Instant::now();
+                let decision = limiter.check(&app_state, &subject, req.path()).await;
+                record_check_latency("rate_limit", started, decision.is_allowed());
+                if !decision.is_allowed() {
+                    let mut res = HttpResponse::TooManyRequests().body("Too many requests");
+                    insert_rate_limit_headers(res.headers_mut(), &decision);
+                    return Ok(req.into_response(res.map_into_right_body()));
+                }
+
+                let mut res = svc.call(req).await?;
+                insert_rate_limit_headers(res.headers_mut(), &decision);
+                Ok(res.map_into_left_body())
+            }
+            .instrument(span),
+        )
+    }
+}
@@ -0,0 +1,113 @@
+use crate::pool::AppState;
+use crate::rate_limit::redis_rate_limiter::check_redis;
+use crate::rate_limit::{MemoryRateLimiter, Quota, RateLimitDecision, RateLimitKey};
+use std::sync::Arc;
+
+type ApiKeyValidator = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/**
+ * Limiteur de débit : quota par défaut, quotas propres à certains préfixes de route
+ * (le plus long l'emporte) et identité du client (`RateLimitKey`).
+ *
+ * Les compteurs vivent dans le Redis de l'`AppState` ; s'il est absent ou en
+ * erreur, un limiteur en mémoire prend le relais.
+ */
+pub struct RateLimiter {
+    default_quota: Option<Quota>,
+    route_quotas: Vec<(String, Option<Quota>)>,
+    key: RateLimitKey,
+    api_key_validator: Option<ApiKeyValidator>,
+    redis_prefix: String,
+    memory: MemoryRateLimiter,
+}
+
+impl RateLimiter {
+    pub fn new(default_quota: Quota) -> Self {
+        Self {
+            default_quota: Some(default_quota),
+            route_quotas: Vec::new(),
+            key: RateLimitKey::default(),
+            api_key_validator: None,
+            redis_prefix: "rate_limit".to_string(),
+            memory: MemoryRateLimiter::new(),
+        }
+    }
+
+    /// Quota des routes commençant par `path_prefix` (ex: `/auth/login`).
+    pub fn with_route_quota(mut self, path_prefix: &str, quota: Quota) -> Self {
+        self.route_quotas
+            .push((path_prefix.to_string(), Some(quota)));
+        self
+    }
+
+    /// Exclut les routes commençant par `path_prefix` de toute limite.
+    pub fn without_limit_on(mut self, path_prefix: &str) -> Self {
+        self.route_quotas.push((path_prefix.to_string(), None));
+        self
+    }
+
+    pub fn with_key(mut self, key: RateLimitKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Reconnaît les clés d'API valides pour `RateLimitKey::ApiKey`. Sans validateur,
+    /// ou pour une clé refusée, le quota s'applique à l'IP : une valeur inventée à
+    /// chaque requête ne donne pas un nouveau quota.
+    pub fn with_api_key_validator(
+        mut self,
+        validator: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.api_key_validator = Some(Arc::new(validator));
+        self
+    }
+
+    pub fn is_valid_api_key(&self, api_key: &str) -> bool {
+        self.api_key_validator
+            .as_ref()
+            .is_some_and(|validator| validator(api_key))
+    }
+
+    pub fn with_redis_prefix(mut self, prefix: &str) -> Self {
+        self.redis_prefix = prefix.to_string();
+        self
+    }
+
+    pub fn get_key(&self) -> &RateLimitKey {
+        &self.key
+    }
+
+    /// Préfixe de route retenu (`""` pour le quota par défaut) et son quota.
+    pub fn quota_for(&self, path: &str) -> (&str, Option<&Quota>) {
+        self.route_quotas
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, quota)| (prefix.as_str(), quota.as_ref()))
+            .unwrap_or(("", self.default_quota.as_ref()))
+    }
+
+    /// Consomme une requête du quota de `subject` sur `path`.
+    pub async fn check(
+        &self,
+        app_state: &AppState,
+        subject: &str,
+        path: &str,
+    ) -> RateLimitDecision {
+        let (route, quota) = self.quota_for(path);
+        let Some(quota) = quota else {
+            return RateLimitDecision::unlimited();
+        };
+        let key = format!("{}:{}:{}", self.redis_prefix, route, subject);
+
+        if let Ok(mut conn) = app_state.get_redis_conn().await {
+            match check_redis(&mut conn, &key, quota).await {
+                Ok(decision) => return decision,
+                Err(e) => {
+                    tracing::warn!(error = %e, "rate limit Redis error, using in-memory limiter")
+                }
+            }
+        }
+        self.memory.check(&key, quota)
+    }
+}
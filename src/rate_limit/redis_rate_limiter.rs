@@ -0,0 +1,51 @@
+use crate::rate_limit::{Quota, RateLimitDecision};
+use crate::redis::instrument_redis;
+use deadpool_redis::redis::{RedisError, Script};
+use std::time::Duration;
+
+/// GCRA exécuté atomiquement dans Redis, sur l'horloge du serveur Redis pour que
+/// toutes les instances partagent la même référence de temps.
+///
+/// `KEYS[1]` : compteur ; `ARGV[1]` : intervalle (ms) ; `ARGV[2]` : tolérance (ms).
+/// Renvoie `{autorisé, restant, reset_ms, retry_ms}`.
+pub const GCRA_SCRIPT: &str = r#"
+local interval = tonumber(ARGV[1])
+local tolerance = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local tat = tonumber(redis.call('GET', KEYS[1]) or now)
+if tat < now then
+  tat = now
+end
+local new_tat = tat + interval
+local allow_at = new_tat - tolerance
+if now < allow_at then
+  return {0, 0, tat - now, allow_at - now}
+end
+redis.call('SET', KEYS[1], new_tat, 'PX', new_tat - now)
+return {1, math.floor((tolerance - (new_tat - now)) / interval), new_tat - now, 0}
+"#;
+
+pub(crate) async fn check_redis(
+    conn: &mut deadpool_redis::Connection,
+    key: &str,
+    quota: &Quota,
+) -> Result<RateLimitDecision, RedisError> {
+    let (allowed, remaining, reset_ms, retry_ms): (i64, i64, i64, i64) = instrument_redis(
+        "EVALSHA",
+        Script::new(GCRA_SCRIPT)
+            .key(key)
+            .arg(quota.emission_interval_ms())
+            .arg(quota.burst_tolerance_ms())
+            .invoke_async(conn),
+    )
+    .await?;
+
+    Ok(RateLimitDecision {
+        allowed: allowed == 1,
+        limit: quota.get_limit(),
+        remaining: remaining.max(0) as u32,
+        reset_after: Duration::from_millis(reset_ms.max(0) as u64),
+        retry_after: (allowed != 1).then(|| Duration::from_millis(retry_ms.max(0) as u64)),
+    })
+}
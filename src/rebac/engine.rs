@@ -0,0 +1,347 @@
+use crate::rebac::{
+    ExpandTree, ObjectRef, RebacError, RebacSchema, RelationRewrite, RelationTuple, SubjectRef,
+    TupleStore, TENANT_NAMESPACE, TENANT_RELATION,
+};
+use futures_util::future::BoxFuture;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_MAX_DEPTH: usize = 16;
+
+/// Nœuds `(objet, relation)` du chemin de résolution en cours, pour couper les cycles.
+type Path = Mutex<HashSet<(ObjectRef, String)>>;
+
+/// Moteur d'évaluation des relations (Zanzibar) : `check`, `expand` et `list_objects`
+/// sur les tuples d'un `TupleStore`, selon les règles d'un `RebacSchema`.
+///
+/// Enregistré dans `AppState`, il remplace la fonction SQL `check_access` pour les
+/// ressources et actions déclarées dans le schéma.
+#[derive(Clone)]
+pub struct RebacEngine {
+    schema: RebacSchema,
+    store: Arc<dyn TupleStore>,
+    max_depth: usize,
+}
+
+impl RebacEngine {
+    pub fn new(schema: RebacSchema, store: Arc<dyn TupleStore>) -> Self {
+        Self {
+            schema,
+            store,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Profondeur maximale de résolution (délégations imbriquées). Au-delà, `check`
+    /// refuse l'accès et `expand` renvoie `RebacError::MaxDepthExceeded`.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn get_schema(&self) -> &RebacSchema {
+        &self.schema
+    }
+
+    pub fn get_store(&self) -> &Arc<dyn TupleStore> {
+        &self.store
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Écrit un tuple après avoir vérifié que sa relation est déclarée.
+    ///
+    /// Avec `PgTupleStore`, le trigger de `RELATION_TUPLES_SQL` purge le cache des accès
+    /// via `spawn_invalidation_listener` ; avec un autre stockage, appeler
+    /// `invalidate_all_access` après `write` et `delete`.
+    pub async fn write(&self, tuple: &RelationTuple) -> Result<bool, RebacError> {
+        self.schema
+            .get_rewrite(tuple.get_object().get_namespace(), tuple.get_relation())?;
+        Ok(self.store.write(tuple).await?)
+    }
+
+    pub async fn delete(&self, tuple: &RelationTuple) -> Result<bool, RebacError> {
+        Ok(self.store.delete(tuple).await?)
+    }
+
+    /// L'utilisateur a-t-il `relation` sur `object` ?
+    pub async fn check(
+        &self,
+        object: &ObjectRef,
+        relation: &str,
+        user_id: u64,
+    ) -> Result<bool, RebacError> {
+        let path = Path::default();
+        self.check_relation(object.clone(), relation.to_string(), user_id, 0, &path)
+            .await
+    }
+
+    /// L'objet est-il rattaché à la commune par le tuple `objet#tenant@tenant:<id>` ?
+    /// Un objet sans ce tuple n'appartient à aucune commune.
+    pub async fn belongs_to_tenant(
+        &self,
+        object: &ObjectRef,
+        tenant_id: &str,
+    ) -> Result<bool, RebacError> {
+        let tenant = SubjectRef::object(ObjectRef::new(TENANT_NAMESPACE, tenant_id));
+        Ok(self
+            .store
+            .read_subjects(object, TENANT_RELATION)
+            .await?
+            .contains(&tenant))
+    }
+
+    /// Arbre des sujets détenant `relation` sur `object`.
+    pub async fn expand(
+        &self,
+        object: &ObjectRef,
+        relation: &str,
+    ) -> Result<ExpandTree, RebacError> {
+        let path = Path::default();
+        self.expand_relation(object.clone(), relation.to_string(), 0, &path)
+            .await
+    }
+
+    /// Identifiants des objets de `namespace` sur lesquels l'utilisateur a `relation`.
+    /// Les candidats sont les objets présents dans au moins un tuple du namespace.
+    pub async fn list_objects(
+        &self,
+        namespace: &str,
+        relation: &str,
+        user_id: u64,
+    ) -> Result<Vec<String>, RebacError> {
+        self.schema.get_rewrite(namespace, relation)?;
+        let mut allowed = Vec::new();
+        for object_id in self.store.list_object_ids(namespace).await? {
+            let object = ObjectRef::new(namespace, &object_id);
+            if self.check(&object, relation, user_id).await? {
+                allowed.push(object_id);
+            }
+        }
+        Ok(allowed)
+    }
+
+    fn check_relation<'a>(
+        &'a self,
+        object: ObjectRef,
+        relation: String,
+        user_id: u64,
+        depth: usize,
+        path: &'a Path,
+    ) -> BoxFuture<'a, Result<bool, RebacError>> {
+        Box::pin(async move {
+            let rewrite = self
+                .schema
+                .get_rewrite(object.get_namespace(), &relation)?
+                .clone();
+            // Un cycle ou une chaîne trop longue n'accorde rien : refus plutôt qu'erreur
+            if depth > self.max_depth {
+                tracing::warn!(
+                    max_depth = self.max_depth,
+                    namespace = object.get_namespace(),
+                    relation = %relation,
+                    "ReBAC max depth reached, access denied"
+                );
+                return Ok(false);
+            }
+            let node = (object, relation);
+            if !path.lock().unwrap().insert(node.clone()) {
+                return Ok(false);
+            }
+            let allowed = self
+                .check_rewrite(&node.0, &node.1, &rewrite, user_id, depth, path)
+                .await;
+            path.lock().unwrap().remove(&node);
+            allowed
+        })
+    }
+
+    fn check_rewrite<'a>(
+        &'a self,
+        object: &'a ObjectRef,
+        relation: &'a str,
+        rewrite: &'a RelationRewrite,
+        user_id: u64,
+        depth: usize,
+        path: &'a Path,
+    ) -> BoxFuture<'a, Result<bool, RebacError>> {
+        Box::pin(async move {
+            match rewrite {
+                RelationRewrite::This => {
+                    for subject in self.store.read_subjects(object, relation).await? {
+                        match subject {
+                            SubjectRef::User(id) if id == user_id => return Ok(true),
+                            SubjectRef::User(_) => {}
+                            SubjectRef::Set {
+                                object: set_object,
+                                relation: Some(set_relation),
+                            } => {
+                                if self
+                                    .check_relation(
+                                        set_object,
+                                        set_relation,
+                                        user_id,
+                                        depth + 1,
+                                        path,
+                                    )
+                                    .await?
+                                {
+                                    return Ok(true);
+                                }
+                            }
+                            // Un objet sans relation ne désigne aucun utilisateur.
+                            SubjectRef::Set { relation: None, .. } => {}
+                        }
+                    }
+                    Ok(false)
+                }
+                RelationRewrite::ComputedUserset(computed) => {
+                    self.check_relation(object.clone(), computed.clone(), user_id, depth + 1, path)
+                        .await
+                }
+                RelationRewrite::TupleToUserset {
+                    tupleset,
+                    computed_relation,
+                } => {
+                    for parent in self.parents(object, tupleset).await? {
+                        if self
+                            .check_relation(
+                                parent,
+                                computed_relation.clone(),
+                                user_id,
+                                depth + 1,
+                                path,
+                            )
+                            .await?
+                        {
+                            return Ok(true);
+                        }
+                    }
+                    Ok(false)
+                }
+                RelationRewrite::Union(rewrites) => {
+                    for rewrite in rewrites {
+                        if self
+                            .check_rewrite(object, relation, rewrite, user_id, depth, path)
+                            .await?
+                        {
+                            return Ok(true);
+                        }
+                    }
+                    Ok(false)
+                }
+            }
+        })
+    }
+
+    fn expand_relation<'a>(
+        &'a self,
+        object: ObjectRef,
+        relation: String,
+        depth: usize,
+        path: &'a Path,
+    ) -> BoxFuture<'a, Result<ExpandTree, RebacError>> {
+        Box::pin(async move {
+            if depth > self.max_depth {
+                return Err(RebacError::MaxDepthExceeded(self.max_depth));
+            }
+            let rewrite = self
+                .schema
+                .get_rewrite(object.get_namespace(), &relation)?
+                .clone();
+            let mut children = Vec::new();
+            // Un nœud déjà sur le chemin (cycle) est rendu sans enfants
+            let node = (object, relation);
+            if path.lock().unwrap().insert(node.clone()) {
+                let expanded = self
+                    .expand_rewrite(&node.0, &node.1, &rewrite, depth, path, &mut children)
+                    .await;
+                path.lock().unwrap().remove(&node);
+                expanded?;
+            }
+            let (object, relation) = node;
+            Ok(ExpandTree::Union {
+                object,
+                relation,
+                children,
+            })
+        })
+    }
+
+    fn expand_rewrite<'a>(
+        &'a self,
+        object: &'a ObjectRef,
+        relation: &'a str,
+        rewrite: &'a RelationRewrite,
+        depth: usize,
+        path: &'a Path,
+        children: &'a mut Vec<ExpandTree>,
+    ) -> BoxFuture<'a, Result<(), RebacError>> {
+        Box::pin(async move {
+            match rewrite {
+                RelationRewrite::This => {
+                    for subject in self.store.read_subjects(object, relation).await? {
+                        match subject {
+                            SubjectRef::User(id) => children.push(ExpandTree::User(id)),
+                            SubjectRef::Set {
+                                object: set_object,
+                                relation: Some(set_relation),
+                            } => children.push(
+                                self.expand_relation(set_object, set_relation, depth + 1, path)
+                                    .await?,
+                            ),
+                            SubjectRef::Set { relation: None, .. } => {}
+                        }
+                    }
+                }
+                RelationRewrite::ComputedUserset(computed) => children.push(
+                    self.expand_relation(object.clone(), computed.clone(), depth + 1, path)
+                        .await?,
+                ),
+                RelationRewrite::TupleToUserset {
+                    tupleset,
+                    computed_relation,
+                } => {
+                    for parent in self.parents(object, tupleset).await? {
+                        children.push(
+                            self.expand_relation(
+                                parent,
+                                computed_relation.clone(),
+                                depth + 1,
+                                path,
+                            )
+                            .await?,
+                        );
+                    }
+                }
+                RelationRewrite::Union(rewrites) => {
+                    for rewrite in rewrites {
+                        self.expand_rewrite(object, relation, rewrite, depth, path, children)
+                            .await?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Objets référencés par `object#tupleset` (ex: le service d'un agent, le dossier d'un document).
+    async fn parents(
+        &self,
+        object: &ObjectRef,
+        tupleset: &str,
+    ) -> Result<Vec<ObjectRef>, RebacError> {
+        Ok(self
+            .store
+            .read_subjects(object, tupleset)
+            .await?
+            .into_iter()
+            .filter_map(|subject| match subject {
+                SubjectRef::Set { object, .. } => Some(object),
+                SubjectRef::User(_) => None,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::database::errors::DatabaseError;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum RebacError {
+    /// Aucun `NamespaceConfig` n'est déclaré pour ce type d'objet.
+    #[error("Unknown namespace: {0}")]
+    UnknownNamespace(String),
+
+    /// La relation n'est pas déclarée dans le namespace.
+    #[error("Unknown relation {relation} in namespace {namespace}")]
+    UnknownRelation { namespace: String, relation: String },
+
+    /// Tuple mal formé (ex: `document:12#viewer@user:42` attendu).
+    #[error("Invalid relation tuple: {0}")]
+    InvalidTuple(String),
+
+    /// Le schéma JSON ne décrit pas des `NamespaceConfig` valides.
+    #[error("Invalid ReBAC schema: {0}")]
+    InvalidSchema(String),
+
+    /// La chaîne de relations dépasse la profondeur autorisée (cycle probable).
+    #[error("Maximum evaluation depth of {0} exceeded")]
+    MaxDepthExceeded(usize),
+
+    #[error("Tuple store error: {0}")]
+    Database(#[from] DatabaseError),
+}
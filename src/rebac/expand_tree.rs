@@ -0,0 +1,37 @@
+use crate::rebac::ObjectRef;
+use std::collections::BTreeSet;
+
+/// Arbre renvoyé par `RebacEngine::expand` : qui détient `object#relation`, et par quel chemin.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpandTree {
+    /// Utilisateur désigné directement par un tuple.
+    User(u64),
+    /// Union des sujets de `object#relation`, chaque enfant étant une source de droits.
+    Union {
+        object: ObjectRef,
+        relation: String,
+        children: Vec<ExpandTree>,
+    },
+}
+
+impl ExpandTree {
+    /// Tous les utilisateurs présents dans l'arbre.
+    pub fn users(&self) -> BTreeSet<u64> {
+        let mut users = BTreeSet::new();
+        self.collect_users(&mut users);
+        users
+    }
+
+    fn collect_users(&self, users: &mut BTreeSet<u64>) {
+        match self {
+            ExpandTree::User(id) => {
+                users.insert(*id);
+            }
+            ExpandTree::Union { children, .. } => {
+                for child in children {
+                    child.collect_users(users);
+                }
+            }
+        }
+    }
+}
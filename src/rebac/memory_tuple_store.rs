@@ -0,0 +1,71 @@
+use crate::database::errors::DatabaseError;
+use crate::rebac::{ObjectRef, RelationTuple, SubjectRef, TupleStore};
+use async_trait::async_trait;
+use std::collections::BTreeSet;
+use std::sync::RwLock;
+
+/// Stockage en mémoire, pour les tests et les schémas chargés au démarrage.
+#[derive(Default)]
+pub struct MemoryTupleStore {
+    tuples: RwLock<BTreeSet<RelationTuple>>,
+}
+
+impl MemoryTupleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tuples(tuples: impl IntoIterator<Item = RelationTuple>) -> Self {
+        Self {
+            tuples: RwLock::new(tuples.into_iter().collect()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tuples.read().map(|t| t.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock_error() -> DatabaseError {
+        DatabaseError::Internal("Relation tuple store lock poisoned".to_string())
+    }
+}
+
+#[async_trait]
+impl TupleStore for MemoryTupleStore {
+    async fn read_subjects(
+        &self,
+        object: &ObjectRef,
+        relation: &str,
+    ) -> Result<Vec<SubjectRef>, DatabaseError> {
+        let tuples = self.tuples.read().map_err(|_| Self::lock_error())?;
+        Ok(tuples
+            .iter()
+            .filter(|t| t.get_object() == object && t.get_relation() == relation)
+            .map(|t| t.get_subject().clone())
+            .collect())
+    }
+
+    async fn list_object_ids(&self, namespace: &str) -> Result<Vec<String>, DatabaseError> {
+        let tuples = self.tuples.read().map_err(|_| Self::lock_error())?;
+        let ids: BTreeSet<String> = tuples
+            .iter()
+            .filter(|t| t.get_object().get_namespace() == namespace)
+            .map(|t| t.get_object().get_object_id().to_string())
+            .collect();
+        Ok(ids.into_iter().collect())
+    }
+
+    async fn write(&self, tuple: &RelationTuple) -> Result<bool, DatabaseError> {
+        let mut tuples = self.tuples.write().map_err(|_| Self::lock_error())?;
+        Ok(tuples.insert(tuple.clone()))
+    }
+
+    async fn delete(&self, tuple: &RelationTuple) -> Result<bool, DatabaseError> {
+        let mut tuples = self.tuples.write().map_err(|_| Self::lock_error())?;
+        Ok(tuples.remove(tuple))
+    }
+}
@@ -0,0 +1,28 @@
+mod errors;
+pub use errors::RebacError;
+
+mod relation_tuple;
+pub use relation_tuple::{
+    ObjectRef, RelationTuple, SubjectRef, TENANT_NAMESPACE, TENANT_RELATION, USER_NAMESPACE,
+};
+
+mod relation_rewrite;
+pub use relation_rewrite::RelationRewrite;
+
+mod namespace_config;
+pub use namespace_config::{NamespaceConfig, RebacSchema};
+
+mod tuple_store;
+pub use tuple_store::TupleStore;
+
+mod memory_tuple_store;
+pub use memory_tuple_store::MemoryTupleStore;
+
+mod pg_tuple_store;
+pub use pg_tuple_store::{install_relation_tuples_table, PgTupleStore, RELATION_TUPLES_SQL};
+
+mod expand_tree;
+pub use expand_tree::ExpandTree;
+
+mod engine;
+pub use engine::RebacEngine;
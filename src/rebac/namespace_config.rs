@@ -0,0 +1,84 @@
+use crate::rebac::{RebacError, RelationRewrite};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Relations d'un type d'objet et la règle de calcul de chacune.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceConfig {
+    name: String,
+    relations: HashMap<String, RelationRewrite>,
+}
+
+impl NamespaceConfig {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            relations: HashMap::new(),
+        }
+    }
+
+    /// Relation dont les sujets sont uniquement ceux écrits directement.
+    pub fn with_relation(self, relation: &str) -> Self {
+        self.with_rewrite(relation, RelationRewrite::This)
+    }
+
+    pub fn with_rewrite(mut self, relation: &str, rewrite: RelationRewrite) -> Self {
+        self.relations.insert(relation.to_string(), rewrite);
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_rewrite(&self, relation: &str) -> Option<&RelationRewrite> {
+        self.relations.get(relation)
+    }
+}
+
+/// Ensemble des namespaces connus du moteur ReBAC.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RebacSchema {
+    namespaces: HashMap<String, NamespaceConfig>,
+}
+
+impl RebacSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_namespace(mut self, namespace: NamespaceConfig) -> Self {
+        self.namespaces.insert(namespace.name.clone(), namespace);
+        self
+    }
+
+    /// Charge un schéma JSON de la forme `{"document": {"name": "document", "relations": {...}}}`.
+    pub fn from_json(json: &str) -> Result<Self, RebacError> {
+        serde_json::from_str(json).map_err(|e| RebacError::InvalidSchema(e.to_string()))
+    }
+
+    pub fn get_namespace(&self, namespace: &str) -> Option<&NamespaceConfig> {
+        self.namespaces.get(namespace)
+    }
+
+    pub fn has_relation(&self, namespace: &str, relation: &str) -> bool {
+        self.get_namespace(namespace)
+            .and_then(|ns| ns.get_rewrite(relation))
+            .is_some()
+    }
+
+    pub fn get_rewrite(
+        &self,
+        namespace: &str,
+        relation: &str,
+    ) -> Result<&RelationRewrite, RebacError> {
+        self.get_namespace(namespace)
+            .ok_or_else(|| RebacError::UnknownNamespace(namespace.to_string()))?
+            .get_rewrite(relation)
+            .ok_or_else(|| RebacError::UnknownRelation {
+                namespace: namespace.to_string(),
+                relation: relation.to_string(),
+            })
+    }
+}
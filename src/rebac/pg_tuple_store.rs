@@ -0,0 +1,90 @@
+use crate::database::errors::DatabaseError;
+use crate::database::queries::{
+    delete_relation_tuple_query, list_relation_objects_query, read_relation_subjects_query,
+    write_relation_tuple_query,
+};
+use crate::database::query_views::{
+    DeleteRelationTupleQueryView, ListRelationObjectsQueryView, ReadRelationSubjectsQueryView,
+    WriteRelationTupleQueryView,
+};
+use crate::rebac::{ObjectRef, RelationTuple, SubjectRef, TupleStore};
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+/// Table des tuples de relation et trigger d'invalidation du cache des accès (canal
+/// `INVALIDATION_CHANNEL`). Idempotent : peut être rejoué par les migrations.
+pub const RELATION_TUPLES_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS relation_tuples (
+    namespace text NOT NULL,
+    object_id text NOT NULL,
+    relation text NOT NULL,
+    subject_namespace text NOT NULL,
+    subject_id text NOT NULL,
+    subject_relation text NOT NULL DEFAULT '',
+    created_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (namespace, object_id, relation, subject_namespace, subject_id, subject_relation)
+);
+
+CREATE INDEX IF NOT EXISTS relation_tuples_subject_idx
+    ON relation_tuples (subject_namespace, subject_id, subject_relation);
+
+CREATE OR REPLACE FUNCTION mairie360_notify_relation_tuples()
+RETURNS trigger AS $$
+BEGIN
+    -- Une relation peut être héritée par d'autres objets et namespaces : tout le cache est visé
+    PERFORM pg_notify('mairie360_cache_invalidation', '{"table":"relation_tuples"}');
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS mairie360_cache_invalidation ON relation_tuples;
+CREATE TRIGGER mairie360_cache_invalidation
+    AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON relation_tuples
+    FOR EACH STATEMENT EXECUTE FUNCTION mairie360_notify_relation_tuples();
+"#;
+
+/// Crée la table `relation_tuples`. À réserver aux environnements où les
+/// migrations ne la créent pas déjà (tests, outils d'administration).
+pub async fn install_relation_tuples_table(pool: &PgPool) -> Result<(), DatabaseError> {
+    sqlx::raw_sql(RELATION_TUPLES_SQL).execute(pool).await?;
+    Ok(())
+}
+
+/// Stockage des tuples dans la table Postgres `relation_tuples`.
+#[derive(Clone)]
+pub struct PgTupleStore {
+    pool: PgPool,
+}
+
+impl PgTupleStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TupleStore for PgTupleStore {
+    async fn read_subjects(
+        &self,
+        object: &ObjectRef,
+        relation: &str,
+    ) -> Result<Vec<SubjectRef>, DatabaseError> {
+        let view = ReadRelationSubjectsQueryView::new(object.clone(), relation);
+        read_relation_subjects_query(view, self.pool.clone()).await
+    }
+
+    async fn list_object_ids(&self, namespace: &str) -> Result<Vec<String>, DatabaseError> {
+        let view = ListRelationObjectsQueryView::new(namespace);
+        list_relation_objects_query(view, self.pool.clone()).await
+    }
+
+    async fn write(&self, tuple: &RelationTuple) -> Result<bool, DatabaseError> {
+        let view = WriteRelationTupleQueryView::new(tuple.clone());
+        write_relation_tuple_query(view, self.pool.clone()).await
+    }
+
+    async fn delete(&self, tuple: &RelationTuple) -> Result<bool, DatabaseError> {
+        let view = DeleteRelationTupleQueryView::new(tuple.clone());
+        delete_relation_tuple_query(view, self.pool.clone()).await
+    }
+}
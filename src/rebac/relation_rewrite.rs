@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Règle de calcul d'une relation, à la manière des `userset_rewrite` de Zanzibar.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationRewrite {
+    /// Les sujets écrits directement sur `objet#relation`.
+    This,
+    /// Les sujets d'une autre relation du même objet (ex: tout `editor` est `viewer`).
+    ComputedUserset(String),
+    /// Suit `tupleset` vers les objets parents, puis évalue `computed_relation` sur eux
+    /// (ex: les `viewer` du dossier parent sont `viewer` du document).
+    TupleToUserset {
+        tupleset: String,
+        computed_relation: String,
+    },
+    /// Accordé si l'une des règles l'accorde.
+    Union(Vec<RelationRewrite>),
+}
+
+impl RelationRewrite {
+    pub fn computed(relation: &str) -> Self {
+        RelationRewrite::ComputedUserset(relation.to_string())
+    }
+
+    pub fn tuple_to_userset(tupleset: &str, computed_relation: &str) -> Self {
+        RelationRewrite::TupleToUserset {
+            tupleset: tupleset.to_string(),
+            computed_relation: computed_relation.to_string(),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use crate::rebac::RebacError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Namespace réservé aux utilisateurs : `user:42` désigne l'utilisateur 42.
+pub const USER_NAMESPACE: &str = "user";
+
+/// Namespace réservé aux communes : `tenant:lyon` désigne la commune `lyon`.
+pub const TENANT_NAMESPACE: &str = "tenant";
+
+/// Relation rattachant un objet à sa commune : `document:12#tenant@tenant:lyon`.
+/// À déclarer (`with_relation("tenant")`) dans les namespaces multi-communes.
+pub const TENANT_RELATION: &str = "tenant";
+
+/// Un objet protégé, identifié par son type (namespace) et son identifiant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectRef {
+    namespace: String,
+    object_id: String,
+}
+
+impl ObjectRef {
+    pub fn new(namespace: &str, object_id: impl ToString) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            object_id: object_id.to_string(),
+        }
+    }
+    pub fn get_namespace(&self) -> &str {
+        &self.namespace
+    }
+    pub fn get_object_id(&self) -> &str {
+        &self.object_id
+    }
+}
+
+impl Display for ObjectRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.object_id)
+    }
+}
+
+impl FromStr for ObjectRef {
+    type Err = RebacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((namespace, object_id)) if !namespace.is_empty() && !object_id.is_empty() => {
+                Ok(Self::new(namespace, object_id))
+            }
+            _ => Err(RebacError::InvalidTuple(s.to_string())),
+        }
+    }
+}
+
+/// Le sujet d'un tuple : un utilisateur, ou un ensemble d'utilisateurs
+/// (`group:5#member`). Sans relation, le sujet désigne l'objet lui-même,
+/// ce qui sert aux relations de hiérarchie (`service:7#parent@department:2`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SubjectRef {
+    User(u64),
+    Set {
+        object: ObjectRef,
+        relation: Option<String>,
+    },
+}
+
+impl SubjectRef {
+    pub fn user(user_id: u64) -> Self {
+        SubjectRef::User(user_id)
+    }
+
+    pub fn object(object: ObjectRef) -> Self {
+        SubjectRef::Set {
+            object,
+            relation: None,
+        }
+    }
+
+    pub fn userset(object: ObjectRef, relation: &str) -> Self {
+        SubjectRef::Set {
+            object,
+            relation: Some(relation.to_string()),
+        }
+    }
+
+    /// Reconstruit un sujet à partir des colonnes stockées (relation vide = aucune).
+    pub fn from_parts(namespace: &str, id: &str, relation: &str) -> Result<Self, RebacError> {
+        if namespace == USER_NAMESPACE && relation.is_empty() {
+            return id
+                .parse()
+                .map(SubjectRef::User)
+                .map_err(|_| RebacError::InvalidTuple(format!("{}:{}", namespace, id)));
+        }
+        Ok(SubjectRef::Set {
+            object: ObjectRef::new(namespace, id),
+            relation: (!relation.is_empty()).then(|| relation.to_string()),
+        })
+    }
+
+    /// Colonnes `(subject_namespace, subject_id, subject_relation)` du tuple stocké.
+    pub fn to_parts(&self) -> (String, String, String) {
+        match self {
+            SubjectRef::User(id) => (USER_NAMESPACE.to_string(), id.to_string(), String::new()),
+            SubjectRef::Set { object, relation } => (
+                object.namespace.clone(),
+                object.object_id.clone(),
+                relation.clone().unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+impl Display for SubjectRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubjectRef::User(id) => write!(f, "{}:{}", USER_NAMESPACE, id),
+            SubjectRef::Set {
+                object,
+                relation: None,
+            } => write!(f, "{}", object),
+            SubjectRef::Set {
+                object,
+                relation: Some(relation),
+            } => write!(f, "{}#{}", object, relation),
+        }
+    }
+}
+
+impl FromStr for SubjectRef {
+    type Err = RebacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (object, relation) = match s.split_once('#') {
+            Some((object, relation)) => (object, relation),
+            None => (s, ""),
+        };
+        let object: ObjectRef = object.parse()?;
+        SubjectRef::from_parts(&object.namespace, &object.object_id, relation)
+    }
+}
+
+/// Tuple de relation au format Zanzibar : `objet#relation@sujet`,
+/// par exemple `document:12#viewer@group:5#member`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RelationTuple {
+    object: ObjectRef,
+    relation: String,
+    subject: SubjectRef,
+}
+
+impl RelationTuple {
+    pub fn new(object: ObjectRef, relation: &str, subject: SubjectRef) -> Self {
+        Self {
+            object,
+            relation: relation.to_string(),
+            subject,
+        }
+    }
+    pub fn get_object(&self) -> &ObjectRef {
+        &self.object
+    }
+    pub fn get_relation(&self) -> &str {
+        &self.relation
+    }
+    pub fn get_subject(&self) -> &SubjectRef {
+        &self.subject
+    }
+}
+
+impl Display for RelationTuple {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}@{}", self.object, self.relation, self.subject)
+    }
+}
+
+impl FromStr for RelationTuple {
+    type Err = RebacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RebacError::InvalidTuple(s.to_string());
+        let (object_relation, subject) = s.split_once('@').ok_or_else(invalid)?;
+        let (object, relation) = object_relation.split_once('#').ok_or_else(invalid)?;
+        if relation.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self::new(
+            object.parse().map_err(|_| invalid())?,
+            relation,
+            subject.parse().map_err(|_| invalid())?,
+        ))
+    }
+}
@@ -0,0 +1,23 @@
+use crate::database::errors::DatabaseError;
+use crate::rebac::{ObjectRef, RelationTuple, SubjectRef};
+use async_trait::async_trait;
+
+/// Stockage des tuples de relation utilisé par `RebacEngine`.
+#[async_trait]
+pub trait TupleStore: Send + Sync {
+    /// Sujets écrits directement sur `object#relation`.
+    async fn read_subjects(
+        &self,
+        object: &ObjectRef,
+        relation: &str,
+    ) -> Result<Vec<SubjectRef>, DatabaseError>;
+
+    /// Identifiants des objets du namespace apparaissant dans au moins un tuple.
+    async fn list_object_ids(&self, namespace: &str) -> Result<Vec<String>, DatabaseError>;
+
+    /// Écrit le tuple. Renvoie `false` s'il existait déjà.
+    async fn write(&self, tuple: &RelationTuple) -> Result<bool, DatabaseError>;
+
+    /// Supprime le tuple. Renvoie `false` s'il n'existait pas.
+    async fn delete(&self, tuple: &RelationTuple) -> Result<bool, DatabaseError>;
+}
@@ -0,0 +1,40 @@
+use redis::RedisError;
+use std::future::Future;
+use std::time::Instant;
+use tracing::Instrument;
+
+/// Exécute une commande Redis dans un span `redis.command` portant son nom, et trace
+/// sa latence. Ni les clés ni les valeurs ne sont journalisées.
+pub(crate) async fn instrument_redis<T, F>(
+    command: &'static str,
+    future: F,
+) -> Result<T, RedisError>
+where
+    F: Future<Output = Result<T, RedisError>>,
+{
+    let span = tracing::debug_span!(
+        "redis.command",
+        otel.kind = "client",
+        otel.status_code = tracing::field::Empty,
+        db.system.name = "redis",
+        db.operation.name = command,
+    );
+    async move {
+        let started = Instant::now();
+        let result = future.await;
+        let elapsed = started.elapsed();
+        let latency_ms = elapsed.as_secs_f64() * 1000.0;
+        match &result {
+            Ok(_) => tracing::debug!(latency_ms, "redis command completed"),
+            Err(e) => {
+                tracing::Span::current().record("otel.status_code", "ERROR");
+                tracing::warn!(latency_ms, error = %e, "redis command failed");
+            }
+        }
+        #[cfg(feature = "telemetry")]
+        crate::telemetry::record_redis_command(command, elapsed, result.is_ok());
+        result
+    }
+    .instrument(span)
+    .await
+}
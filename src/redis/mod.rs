@@ -1 +1,4 @@
+mod instrument_redis;
+pub(crate) use instrument_redis::instrument_redis;
+
 pub mod simple_key;
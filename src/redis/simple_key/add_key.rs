@@ -1,3 +1,4 @@
+use crate::redis::instrument_redis;
 use deadpool_redis::redis::AsyncCommands;
 use deadpool_redis::Connection;
 
@@ -7,7 +8,7 @@ pub async fn add_key(
     value: &str,
 ) -> Result<(), redis::RedisError> {
     // Note l'utilisation de .set_nx().await
-    match conn.set_nx::<&str, &str, bool>(key, value).await {
+    match instrument_redis("SETNX", conn.set_nx::<&str, &str, bool>(key, value)).await {
         Ok(true) => Ok(()),
         Ok(false) => Err(redis::RedisError::from((
             redis::ErrorKind::Io,
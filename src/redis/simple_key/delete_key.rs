@@ -1,8 +1,9 @@
+use crate::redis::instrument_redis;
 use deadpool_redis::redis::AsyncCommands;
 use deadpool_redis::Connection;
 
 pub async fn delete_key(conn: &mut Connection, key: &str) -> Result<(), redis::RedisError> {
-    match conn.del::<&str, i32>(key).await {
+    match instrument_redis("DEL", conn.del::<&str, i32>(key)).await {
         Ok(0) => Err(redis::RedisError::from((
             redis::ErrorKind::Io,
             "Key not found",
@@ -1,6 +1,7 @@
+use crate::redis::instrument_redis;
 use deadpool_redis::redis::AsyncCommands;
 use deadpool_redis::Connection;
 
 pub async fn key_exist(conn: &mut Connection, key: &str) -> Result<bool, redis::RedisError> {
-    conn.exists(key).await
+    instrument_redis("EXISTS", conn.exists(key)).await
 }
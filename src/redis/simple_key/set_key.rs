@@ -1,3 +1,4 @@
+use crate::redis::instrument_redis;
 use deadpool_redis::redis::AsyncCommands;
 use deadpool_redis::Connection;
 
@@ -6,7 +7,7 @@ pub async fn set_key(
     key: &str,
     value: &str,
 ) -> Result<(), redis::RedisError> {
-    let response: String = conn.set(key, value).await?;
+    let response: String = instrument_redis("SET", conn.set(key, value)).await?;
     if response == "OK" {
         Ok(())
     } else {
@@ -0,0 +1,303 @@
+use actix_web::{web::Query, Error, HttpMessage, HttpRequest};
+use std::collections::HashMap;
+
+use crate::abac::AbacRequest;
+use crate::audit::{emit_audit_event, request_event, AuditEventType};
+use crate::cache::{get_cached_access, store_cached_access, AccessCacheKey};
+use crate::database::instance_id::{InstanceId, InstanceIdKind};
+use crate::database::queries::has_access_query;
+use crate::database::query_views::HasAccessQueryView;
+use crate::rebac::{ObjectRef, RebacError};
+use crate::security::extractors::current_tenant;
+use crate::security::{client_ip, AccessCheckConfig, InstanceIdSource};
+use crate::{pool::AppState, security::AuthenticatedUser};
+
+fn parse_instance_id(kind: InstanceIdKind, raw: &str) -> Result<InstanceId, Error> {
+    kind.parse(raw)
+        .map_err(|_| actix_web::error::ErrorBadRequest("Invalid ID format in request"))
+}
+
+/// Vrai si le motif de la route (`/users/{user_id}`) déclare le segment `param_name`.
+/// Une route non résolue (middleware placé avant le routage) est acceptée.
+fn route_declares_param(req: &HttpRequest, param_name: &str) -> bool {
+    let Some(pattern) = req.match_pattern() else {
+        return true;
+    };
+    pattern.contains(&format!("{{{}}}", param_name))
+        || pattern.contains(&format!("{{{}:", param_name))
+}
+
+/// Lit l'identifiant d'instance à l'emplacement décrit par `config`.
+/// Un paramètre absent donne `None`, c'est-à-dire une vérification globale.
+fn extract_instance_id(
+    req: &HttpRequest,
+    body: Option<&[u8]>,
+    config: &AccessCheckConfig,
+) -> Result<Option<InstanceId>, Error> {
+    let Some(param_name) = config.id_param_pattern else {
+        return Ok(None);
+    };
+
+    match config.id_source {
+        InstanceIdSource::Path => {
+            // Un paramètre absent du motif donnerait silencieusement une vérification globale
+            if !route_declares_param(req, param_name) {
+                tracing::error!(
+                    resource = config.resource_name,
+                    id_param = param_name,
+                    route = req.match_pattern().unwrap_or_default(),
+                    "access requirement id_param not declared by the route"
+                );
+                return Err(actix_web::error::ErrorInternalServerError(
+                    "Access requirement does not match the route",
+                ));
+            }
+            req.match_info()
+                .get(param_name)
+                .map(|val| parse_instance_id(config.id_kind, val))
+                .transpose()
+        }
+        InstanceIdSource::Query => {
+            let params = Query::<HashMap<String, String>>::from_query(req.query_string())
+                .map_err(|_| actix_web::error::ErrorBadRequest("Invalid query string"))?;
+            params
+                .get(param_name)
+                .map(|val| parse_instance_id(config.id_kind, val))
+                .transpose()
+        }
+        InstanceIdSource::Json => {
+            let body = body.ok_or_else(|| {
+                actix_web::error::ErrorInternalServerError(
+                    "JSON instance ids are only supported by access_guard_middleware",
+                )
+            })?;
+            if body.is_empty() {
+                return Ok(None);
+            }
+            let json: serde_json::Value = serde_json::from_slice(body)
+                .map_err(|_| actix_web::error::ErrorBadRequest("Invalid JSON body"))?;
+            let value = if param_name.starts_with('/') {
+                json.pointer(param_name)
+            } else {
+                json.get(param_name)
+            };
+            match value {
+                None | Some(serde_json::Value::Null) => Ok(None),
+                Some(serde_json::Value::String(s)) => {
+                    parse_instance_id(config.id_kind, s).map(Some)
+                }
+                Some(serde_json::Value::Number(n)) => {
+                    parse_instance_id(config.id_kind, &n.to_string()).map(Some)
+                }
+                Some(_) => Err(actix_web::error::ErrorBadRequest(
+                    "Invalid ID format in request",
+                )),
+            }
+        }
+    }
+}
+
+/// Interroge le moteur ReBAC s'il connaît la ressource et l'action.
+/// Renvoie `None` pour laisser la décision à la fonction SQL `check_access`.
+/// Avec une commune, l'objet doit lui être rattaché (tuple `#tenant@tenant:<id>`).
+async fn check_rebac_access(
+    app_state: &AppState,
+    user: &AuthenticatedUser,
+    tenant_id: Option<&str>,
+    config: &AccessCheckConfig,
+    instance_id: Option<&InstanceId>,
+) -> Result<Option<i32>, Error> {
+    let (Some(engine), Some(instance_id)) = (app_state.rebac_engine(), instance_id) else {
+        return Ok(None);
+    };
+    if !engine
+        .get_schema()
+        .has_relation(config.resource_name, config.action)
+    {
+        return Ok(None);
+    }
+
+    let object = ObjectRef::new(config.resource_name, instance_id);
+    let rebac_error = |e: RebacError| {
+        tracing::error!(error = %e, "ReBAC check error");
+        actix_web::error::ErrorInternalServerError("Error during access check")
+    };
+    if let Some(tenant_id) = tenant_id {
+        // Hors de la commune, l'objet est traité comme sans droit
+        if !engine
+            .belongs_to_tenant(&object, tenant_id)
+            .await
+            .map_err(rebac_error)?
+        {
+            return Ok(Some(0));
+        }
+    }
+    let allowed = engine
+        .check(&object, config.action, user.id)
+        .await
+        .map_err(rebac_error)?;
+    Ok(Some(i32::from(allowed)))
+}
+
+/// Interroge le cache puis, en cas d'absence, le moteur ReBAC ou la fonction SQL `check_access`.
+async fn check_single_access(
+    app_state: &AppState,
+    user: &AuthenticatedUser,
+    tenant_id: Option<&str>,
+    config: &AccessCheckConfig,
+    instance_id: Option<InstanceId>,
+) -> Result<i32, Error> {
+    let cache_key = AccessCacheKey::new(
+        user.id,
+        config.resource_name,
+        config.action,
+        instance_id.clone(),
+    )
+    .with_tenant(tenant_id);
+
+    if let Some(status) = get_cached_access(app_state, &cache_key).await {
+        return Ok(status);
+    }
+
+    if let Some(status) =
+        check_rebac_access(app_state, user, tenant_id, config, instance_id.as_ref()).await?
+    {
+        store_cached_access(app_state, cache_key, status).await;
+        return Ok(status);
+    }
+
+    let db_pool = match app_state.db_pool().cloned() {
+        Some(pool) => pool,
+        None => {
+            return Err(actix_web::error::ErrorInternalServerError(
+                "Database pool missing",
+            ))
+        }
+    };
+
+    let view = match instance_id {
+        Some(id) => HasAccessQueryView::new(user.id, config.resource_name, config.action, id),
+        None => HasAccessQueryView::new_global(user.id, config.resource_name, config.action),
+    }
+    .with_tenant(tenant_id);
+    let status = has_access_query(view, db_pool).await.map_err(|e| {
+        tracing::error!(error = %e, "access check SQL error");
+        actix_web::error::ErrorInternalServerError("Database error during access check")
+    })?;
+
+    store_cached_access(app_state, cache_key, status).await;
+    Ok(status)
+}
+
+/// Applique les politiques ABAC de l'`AppState`, si elles existent, à une vérification
+/// déjà accordée. Le contexte porte l'heure, la méthode, le chemin et l'IP du client.
+async fn check_policies(
+    req: &HttpRequest,
+    app_state: &AppState,
+    user: &AuthenticatedUser,
+    config: &AccessCheckConfig,
+    instance_id: Option<&InstanceId>,
+) -> Result<(), Error> {
+    let Some(engine) = app_state.policy_engine() else {
+        return Ok(());
+    };
+
+    let mut request = AbacRequest::new(user.id, config.resource_name, config.action)
+        .with_subject_attribute("roles", user.roles.clone().into())
+        .with_subject_attribute("scopes", user.scopes.clone().into())
+        .with_context_attribute("method", req.method().as_str().into())
+        .with_context_attribute("path", req.path().into());
+    if let Some(ip) = client_ip(req) {
+        request = request.with_context_attribute("ip", ip.as_str().into());
+    }
+    if let Some(tenant) = &user.tenant {
+        request = request.with_subject_attribute("tenant", tenant.as_str().into());
+    }
+    if let Some(instance_id) = instance_id {
+        request = request.with_instance_id(instance_id);
+    }
+
+    let decision = engine.authorize(request, instance_id).await.map_err(|e| {
+        tracing::error!(error = %e, "policy evaluation error");
+        actix_web::error::ErrorInternalServerError("Error during access check")
+    })?;
+    if decision.is_allowed() {
+        Ok(())
+    } else {
+        Err(deny(req, app_state, user, config, "policy").await)
+    }
+}
+
+/// Trace le refus dans la piste d'audit et renvoie le 403 correspondant.
+async fn deny(
+    req: &HttpRequest,
+    app_state: &AppState,
+    user: &AuthenticatedUser,
+    config: &AccessCheckConfig,
+    reason: &str,
+) -> Error {
+    let event = request_event(AuditEventType::PermissionDenied, req)
+        .with_user_id(user.id)
+        .with_target(config.resource_name, config.action)
+        .with_reason(reason);
+    emit_audit_event(app_state, event).await;
+    actix_web::error::ErrorForbidden("Insufficient permissions")
+}
+
+/// Vérifie que l'utilisateur authentifié de la requête a le droit `config.action`
+/// sur `config.resource_name`, après avoir vérifié chacune des ressources parentes.
+/// Utilisé par `access_guard_middleware` et par l'extracteur `RequireAccess`.
+pub(crate) async fn enforce_access(
+    req: &HttpRequest,
+    body: Option<&[u8]>,
+    config: &AccessCheckConfig,
+) -> Result<AuthenticatedUser, Error> {
+    // 1. Récupérer l'utilisateur injecté par JwtMiddleware
+    let user = req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| actix_web::error::ErrorUnauthorized("User not authenticated"))?;
+
+    let app_state = req
+        .app_data::<actix_web::web::Data<AppState>>()
+        .ok_or_else(|| actix_web::error::ErrorInternalServerError("AppState missing"))?;
+
+    // 2. La commune demandée doit être celle de l'utilisateur ; les droits sont
+    // ensuite limités à la commune résolue, sauf pour un super-administrateur
+    let tenant = match current_tenant(req) {
+        Ok(tenant) => tenant,
+        Err(e) => {
+            let event = request_event(AuditEventType::PermissionDenied, req)
+                .with_user_id(user.id)
+                .with_target(config.resource_name, config.action)
+                .with_reason("tenant");
+            emit_audit_event(app_state, event).await;
+            return Err(e);
+        }
+    };
+    let tenant_id = if user.has_role(&app_state.tenant_config().super_admin_role) {
+        None
+    } else {
+        tenant.get_tenant_id().or(user.tenant.as_deref())
+    };
+
+    // 3. Parents d'abord, puis la ressource visée
+    for check in config.checks() {
+        let instance_id = extract_instance_id(req, body, check)?;
+        let access_status =
+            check_single_access(app_state, &user, tenant_id, check, instance_id.clone()).await?;
+
+        // 4. Verdict étendu selon le code retourné par la DB
+        match access_status {
+            // Accès accordé : restent les politiques ABAC éventuelles
+            1 => check_policies(req, app_state, &user, check, instance_id.as_ref()).await?,
+            // La ressource ou la table n'existe pas -> 404 Not Found propre
+            -1 => return Err(actix_web::error::ErrorNotFound("Resource not found")),
+            // Pas de droits (0) ou toute autre valeur -> 403 Forbidden standard
+            _ => return Err(deny(req, app_state, &user, check, "access_denied").await),
+        }
+    }
+
+    Ok(user)
+}
@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+use thiserror::Error;
+
+use crate::database::errors::DatabaseError;
+use crate::database::queries::does_permission_exist_query;
+use crate::database::query_views::DoesPermissionExistQueryView;
+use crate::pool::AppState;
+use crate::security::require_access::registered_requirements;
+use crate::security::{AccessCheckConfig, AccessRequirement};
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum AccessConfigError {
+    #[error("Database pool is not available to validate access requirements")]
+    MissingDatabase,
+
+    #[error("Unknown resource '{0}' in access requirement")]
+    UnknownResource(String),
+
+    #[error("Unknown action '{action}' on resource '{resource_name}' in access requirement")]
+    UnknownAction {
+        resource_name: String,
+        action: String,
+    },
+
+    #[error("Database error while validating access requirements: {0}")]
+    Database(#[from] DatabaseError),
+}
+
+/**
+ * Liste des exigences d'accès d'une API, à valider au démarrage pour qu'une faute
+ * de frappe dans un nom de ressource ou d'action empêche le lancement au lieu de
+ * produire des 403/404 en production.
+ *
+ * Les exigences déclarées avec `access_requirement!` sont toujours vérifiées ; `with`
+ * et `with_config` servent aux implémentations manuelles et aux `AccessCheckConfig`
+ * passées à `access_guard_middleware`.
+ */
+#[derive(Clone, Default)]
+pub struct AccessRequirements {
+    configs: Vec<AccessCheckConfig>,
+}
+
+impl AccessRequirements {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<R: AccessRequirement>(self) -> Self {
+        self.with_config(R::config())
+    }
+
+    pub fn with_config(mut self, config: AccessCheckConfig) -> Self {
+        self.configs.push(config);
+        self
+    }
+
+    pub fn get_configs(&self) -> &[AccessCheckConfig] {
+        &self.configs
+    }
+
+    /// Vérifie que chaque ressource et chaque action déclarée existe en base.
+    pub async fn validate(&self, state: &AppState) -> Result<(), AccessConfigError> {
+        let pool = state
+            .db_pool()
+            .cloned()
+            .ok_or(AccessConfigError::MissingDatabase)?;
+
+        let configs: Vec<AccessCheckConfig> = self
+            .configs
+            .iter()
+            .cloned()
+            .chain(registered_requirements())
+            .collect();
+        let mut checked = HashSet::new();
+        for config in configs.iter().flat_map(AccessCheckConfig::checks) {
+            if !checked.insert((config.resource_name, config.action)) {
+                continue;
+            }
+            let view = DoesPermissionExistQueryView::new(config.resource_name, config.action);
+            match does_permission_exist_query(view, pool.clone()).await? {
+                1 => {}
+                -1 => {
+                    return Err(AccessConfigError::UnknownResource(
+                        config.resource_name.to_string(),
+                    ))
+                }
+                _ => {
+                    return Err(AccessConfigError::UnknownAction {
+                        resource_name: config.resource_name.to_string(),
+                        action: config.action.to_string(),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+}
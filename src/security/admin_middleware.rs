@@ -6,13 +6,14 @@ use actix_web::{
 use futures_util::future::LocalBoxFuture;
 use std::future::{ready, Ready};
 use std::rc::Rc;
-
-use crate::security::AuthenticatedUser;
-use crate::{database::queries::is_admin_query, pool::AppState};
-use crate::{
-    database::query_views::IsAdminQueryView,
-    jwt_manager::{check_jwt_validity, get_jwt_from_request, get_user_id_from_jwt, JWTCheckError},
-};
+use std::time::Instant;
+use tracing::Instrument;
+
+use crate::audit::{queue_audit_event, request_event, AuditEventType};
+use crate::jwt_manager::{check_jwt_claims, get_jwt_from_request, JWTCheckError};
+use crate::pool::AppState;
+use crate::security::extractors::check_is_admin;
+use crate::security::{record_check_latency, request_span, AuthenticatedUser};
 use lazy_static::lazy_static;
 use regex::Regex;
 
@@ -75,9 +76,10 @@ where
 
         // On clone le pool pour la closure async move
         let pool = match app_state {
-            Some(state) => state.db_pool.clone(),
+            Some(state) => state.db_pool().cloned(),
             None => None,
         };
+        let app_state = app_state.cloned();
 
         let path = req.path();
         lazy_static! {
@@ -93,52 +95,84 @@ where
             });
         }
 
-        Box::pin(async move {
-            let pool = match pool {
-                Some(p) => p,
-                None => {
-                    // Erreur si le pool n'a pas été injecté dans l'App
-                    let res = HttpResponse::InternalServerError()
-                        .body("DB Pool missing")
-                        .map_into_right_body();
-                    return Ok(req.into_response(res));
-                }
-            };
-
-            let jwt_option = get_jwt_from_request(req.request());
-
-            let jwt = match jwt_option {
-                Some(token) => token,
-                None => {
-                    let response = HttpResponse::Unauthorized()
-                        .body("Unauthorized: No JWT token provided.")
-                        .map_into_right_body();
-                    return Ok(req.into_response(response));
-                }
-            };
-
-            match check_jwt_validity(&jwt, pool.clone()).await {
-                Ok(_) => {
-                    let view: IsAdminQueryView = IsAdminQueryView::new(
-                        get_user_id_from_jwt(&jwt).unwrap().parse().unwrap_or(0),
-                    );
-                    if is_admin_query(view, pool).await.unwrap() {
-                        req.extensions_mut().insert(AuthenticatedUser {
-                            id: get_user_id_from_jwt(&jwt).unwrap().parse().unwrap_or(0),
-                        });
-
-                        let res = svc.call(req).await?;
-                        Ok(res.map_into_left_body())
-                    } else {
-                        let response = HttpResponse::Forbidden()
-                            .body("Forbidden: User is not an admin.")
+        let span = request_span("admin", req.request());
+        Box::pin(
+            async move {
+                let started = Instant::now();
+                let (pool, app_state) = match (pool, app_state) {
+                    (Some(p), Some(state)) => (p, state),
+                    _ => {
+                        // Erreur si le pool n'a pas été injecté dans l'App
+                        let res = HttpResponse::InternalServerError()
+                            .body("DB Pool missing")
                             .map_into_right_body();
-                        Ok(req.into_response(response))
+                        return Ok(req.into_response(res));
                     }
-                }
-                Err(error) => {
-                    let response =
-                        match error {
+                };
+
+                let jwt_option = get_jwt_from_request(req.request());
+
+                let jwt = match jwt_option {
+                    Some(token) => token,
+                    None => {
+                        let event =
+                            request_event(AuditEventType::AuthenticationFailure, req.request())
+                                .with_reason("NoTokenProvided");
+                        queue_audit_event(&app_state, event);
+                        let response = HttpResponse::Unauthorized()
+                            .body("Unauthorized: No JWT token provided.")
+                            .map_into_right_body();
+                        return Ok(req.into_response(response));
+                    }
+                };
+
+                let user = check_jwt_claims(&jwt, pool.clone())
+                    .await
+                    .and_then(|claims| {
+                        AuthenticatedUser::from_claims(&claims).ok_or(JWTCheckError::InvalidToken)
+                    });
+
+                match user {
+                    Ok(user) => {
+                        tracing::Span::current().record("user_id", user.id);
+                        let is_admin = match check_is_admin(&app_state, user.id).await {
+                            Ok(is_admin) => is_admin,
+                            Err(e) => {
+                                tracing::error!(error = %e, "is_admin query error");
+                                let response = HttpResponse::InternalServerError()
+                                    .body("Internal server error: Database error.")
+                                    .map_into_right_body();
+                                return Ok(req.into_response(response));
+                            }
+                        };
+                        record_check_latency("admin", started, is_admin);
+                        let event_type = if is_admin {
+                            AuditEventType::AdminAccess
+                        } else {
+                            AuditEventType::AdminAccessDenied
+                        };
+                        let event = request_event(event_type, req.request()).with_user_id(user.id);
+                        queue_audit_event(&app_state, event);
+
+                        if is_admin {
+                            req.extensions_mut().insert(user);
+
+                            let res = svc.call(req).await?;
+                            Ok(res.map_into_left_body())
+                        } else {
+                            let response = HttpResponse::Forbidden()
+                                .body("Forbidden: User is not an admin.")
+                                .map_into_right_body();
+                            Ok(req.into_response(response))
+                        }
+                    }
+                    Err(error) => {
+                        record_check_latency("admin", started, false);
+                        let event =
+                            request_event(AuditEventType::AuthenticationFailure, req.request())
+                                .with_reason(&format!("{:?}", error));
+                        queue_audit_event(&app_state, event);
+                        let response = match error {
                             JWTCheckError::DatabaseError => HttpResponse::InternalServerError()
                                 .body("Internal server error: Database not initialized."),
                             JWTCheckError::NoTokenProvided => HttpResponse::Unauthorized()
@@ -151,9 +185,11 @@ where
                                 HttpResponse::NotFound().body("User not found.")
                             }
                         };
-                    Ok(req.into_response(response.map_into_right_body()))
+                        Ok(req.into_response(response.map_into_right_body()))
+                    }
                 }
             }
-        })
+            .instrument(span),
+        )
     }
 }
@@ -6,18 +6,22 @@ use actix_web::{
 use futures_util::future::LocalBoxFuture;
 use std::future::{ready, Ready};
 use std::rc::Rc;
+use std::time::Instant;
+use tracing::Instrument;
 
-use crate::jwt_manager::{
-    check_jwt_validity, get_jwt_from_request, get_user_id_from_jwt, JWTCheckError,
-};
+use crate::audit::{queue_audit_event, request_event, AuditEventType};
+use crate::jwt_manager::{check_jwt_claims, get_jwt_from_request, JWTCheckError};
 use crate::pool::AppState;
 
-use crate::security::AuthenticatedUser;
+use crate::metrics::record_auth_outcome;
+use crate::security::{record_check_latency, request_span, AuthenticatedUser};
 
 /**
  * Middleware to check the validity of JWT tokens in incoming requests.
  * If the token is valid, the request is passed to the next service in the chain.
  * If the token is invalid or missing, an appropriate HTTP response is returned.
+ * Routes declared with `AppState::with_public_path` (ex: `/health`, `/metrics`) are not checked.
+ * Routes declared with `AppState::with_optional_auth_path` let requests without a token through.
  */
 pub struct JwtMiddleware;
 
@@ -73,15 +77,19 @@ where
 
         // On clone le pool pour la closure async move
         let pool = match app_state {
-            Some(state) => state.db_pool.clone(),
+            Some(state) => state.db_pool().cloned(),
             None => None,
         };
+        let app_state = app_state.cloned();
 
         let path = req.path();
         if path == "/"
             || path.starts_with("/swagger-ui")
             || path.starts_with("/api-docs")
             || path.contains("/auth")
+            || app_state
+                .as_ref()
+                .is_some_and(|state| state.is_public_path(path))
         {
             return Box::pin(async move {
                 let res = svc.call(req).await?;
@@ -89,44 +97,76 @@ where
             });
         }
 
-        Box::pin(async move {
-            let pool = match pool {
-                Some(p) => p,
-                None => {
-                    // Erreur si le pool n'a pas été injecté dans l'App
-                    let res = HttpResponse::InternalServerError()
-                        .body("DB Pool missing")
-                        .map_into_right_body();
-                    return Ok(req.into_response(res));
-                }
-            };
+        let optional_auth = app_state
+            .as_ref()
+            .is_some_and(|state| state.is_optional_auth_path(path));
 
-            let jwt_option = get_jwt_from_request(req.request());
+        let span = request_span("jwt", req.request());
+        Box::pin(
+            async move {
+                let started = Instant::now();
+                let jwt_option = get_jwt_from_request(req.request());
 
-            let jwt = match jwt_option {
-                Some(token) => token,
-                None => {
-                    let response = HttpResponse::Unauthorized()
-                        .body("Unauthorized: No JWT token provided.")
-                        .map_into_right_body();
-                    return Ok(req.into_response(response));
+                // Authentification facultative : sans token, la requête passe en anonyme
+                if optional_auth && jwt_option.is_none() {
+                    let res = svc.call(req).await?;
+                    return Ok(res.map_into_left_body());
                 }
-            };
 
-            match check_jwt_validity(&jwt, pool).await {
-                Ok(_) => {
-                    // ON AJOUTE L'UTILISATEUR DANS LES EXTENSIONS
-                    // Supposons que claims.sub contient l'ID
-                    req.extensions_mut().insert(AuthenticatedUser {
-                        id: get_user_id_from_jwt(&jwt).unwrap().parse().unwrap_or(0),
-                    });
-
-                    let res = svc.call(req).await?;
-                    Ok(res.map_into_left_body())
+                let pool = match pool {
+                    Some(p) => p,
+                    None => {
+                        // Erreur si le pool n'a pas été injecté dans l'App
+                        let res = HttpResponse::InternalServerError()
+                            .body("DB Pool missing")
+                            .map_into_right_body();
+                        return Ok(req.into_response(res));
+                    }
+                };
+
+                let jwt = match jwt_option {
+                    Some(token) => token,
+                    None => {
+                        record_auth_outcome("NoTokenProvided");
+                        if let Some(state) = &app_state {
+                            let event =
+                                request_event(AuditEventType::AuthenticationFailure, req.request())
+                                    .with_reason("NoTokenProvided");
+                            queue_audit_event(state, event);
+                        }
+                        let response = HttpResponse::Unauthorized()
+                            .body("Unauthorized: No JWT token provided.")
+                            .map_into_right_body();
+                        return Ok(req.into_response(response));
+                    }
+                };
+
+                let user = check_jwt_claims(&jwt, pool).await.and_then(|claims| {
+                    AuthenticatedUser::from_claims(&claims).ok_or(JWTCheckError::InvalidToken)
+                });
+                record_check_latency("jwt", started, user.is_ok());
+                match &user {
+                    Ok(_) => record_auth_outcome("success"),
+                    Err(error) => record_auth_outcome(&format!("{:?}", error)),
                 }
-                Err(error) => {
-                    let response =
-                        match error {
+
+                match user {
+                    Ok(user) => {
+                        tracing::Span::current().record("user_id", user.id);
+                        // ON AJOUTE L'UTILISATEUR DANS LES EXTENSIONS
+                        req.extensions_mut().insert(user);
+
+                        let res = svc.call(req).await?;
+                        Ok(res.map_into_left_body())
+                    }
+                    Err(error) => {
+                        if let Some(state) = &app_state {
+                            let event =
+                                request_event(AuditEventType::AuthenticationFailure, req.request())
+                                    .with_reason(&format!("{:?}", error));
+                            queue_audit_event(state, event);
+                        }
+                        let response = match error {
                             JWTCheckError::DatabaseError => HttpResponse::InternalServerError()
                                 .body("Internal server error: Database not initialized."),
                             JWTCheckError::NoTokenProvided => HttpResponse::Unauthorized()
@@ -139,9 +179,11 @@ where
                                 HttpResponse::NotFound().body("User not found.")
                             }
                         };
-                    Ok(req.into_response(response.map_into_right_body()))
+                        Ok(req.into_response(response.map_into_right_body()))
+                    }
                 }
             }
-        })
+            .instrument(span),
+        )
     }
 }
@@ -1,27 +1,74 @@
-use actix_web::HttpMessage;
+use crate::jwt_manager::Claims;
 
-use actix_web::{dev::Payload, FromRequest, HttpRequest};
-use futures_util::future::{ready, Ready};
+/// Méthode d'authentification ayant produit le token (claim `amr`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum AuthMethod {
+    Password,
+    Sso,
+    ApiKey,
+    Other(String),
+    /// Token émis sans claim `amr`
+    #[default]
+    Unspecified,
+}
 
-#[derive(Copy, Clone)]
+impl From<&str> for AuthMethod {
+    fn from(value: &str) -> Self {
+        match value {
+            "password" | "pwd" => AuthMethod::Password,
+            "sso" => AuthMethod::Sso,
+            "api_key" => AuthMethod::ApiKey,
+            other => AuthMethod::Other(other.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct AuthenticatedUser {
     pub id: u64,
+    pub session_id: Option<String>,
+    pub token_id: Option<String>,
+    pub roles: Vec<String>,
+    pub scopes: Vec<String>,
+    pub tenant: Option<String>,
+    pub auth_method: AuthMethod,
 }
 
-impl FromRequest for AuthenticatedUser {
-    type Error = actix_web::Error;
-    type Future = Ready<Result<Self, Self::Error>>;
-
-    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
-        // Comme ton Middleware a DEJA validé le token et l'a mis dans les extensions :
-        if let Some(user) = req.extensions().get::<AuthenticatedUser>() {
-            return ready(Ok(AuthenticatedUser { id: user.id }));
+impl AuthenticatedUser {
+    /// Principal sans informations de session, de rôles ni de scopes.
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            ..Self::default()
         }
+    }
+
+    /// Construit le principal à partir des claims d'un token déjà validé.
+    /// Renvoie `None` si `sub` n'est pas un identifiant numérique, plutôt que l'id 0.
+    pub fn from_claims(claims: &Claims) -> Option<Self> {
+        Some(Self {
+            id: claims.parse_user_id()?,
+            session_id: claims.get_session_id().map(str::to_string),
+            token_id: claims.get_token_id().map(str::to_string),
+            roles: claims.get_roles().to_vec(),
+            scopes: claims.get_scopes().to_vec(),
+            tenant: claims.get_tenant().map(str::to_string),
+            auth_method: claims
+                .get_auth_method()
+                .map(AuthMethod::from)
+                .unwrap_or_default(),
+        })
+    }
+
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+
+    pub fn has_any_role(&self, roles: &[&str]) -> bool {
+        roles.iter().any(|role| self.has_role(role))
+    }
 
-        // Si on arrive ici, c'est que le middleware n'a pas fait son job
-        // ou que la route n'est pas protégée
-        ready(Err(actix_web::error::ErrorUnauthorized(
-            "User not authenticated",
-        )))
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
     }
 }
@@ -0,0 +1,36 @@
+use crate::pool::AppState;
+use actix_web::{http::header, web, HttpRequest};
+
+fn peer_is_trusted(req: &HttpRequest) -> bool {
+    let Some(peer) = req.peer_addr() else {
+        return false;
+    };
+    req.app_data::<web::Data<AppState>>()
+        .is_some_and(|state| state.trusted_proxies().is_trusted(&peer.ip()))
+}
+
+/// Adresse du client : celle de la connexion, ou celle annoncée par les en-têtes
+/// `Forwarded` / `X-Forwarded-For` quand la connexion vient d'un proxy de confiance.
+pub(crate) fn client_ip(req: &HttpRequest) -> Option<String> {
+    if peer_is_trusted(req) {
+        return req
+            .connection_info()
+            .realip_remote_addr()
+            .map(str::to_string);
+    }
+    req.peer_addr().map(|addr| addr.ip().to_string())
+}
+
+/// Hôte de la requête : URI absolue ou en-tête `Host`, ou hôte annoncé par
+/// `Forwarded` / `X-Forwarded-Host` quand la connexion vient d'un proxy de confiance.
+pub(crate) fn request_host(req: &HttpRequest) -> Option<String> {
+    if peer_is_trusted(req) {
+        return Some(req.connection_info().host().to_string());
+    }
+    req.uri().host().map(str::to_string).or_else(|| {
+        req.headers()
+            .get(header::HOST)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    })
+}
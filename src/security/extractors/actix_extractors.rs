@@ -0,0 +1,162 @@
+use actix_web::{dev::Payload, web::Data, FromRequest, HttpMessage, HttpRequest};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+
+use crate::correlation::{RequestId, REQUEST_ID_HEADER, TRACEPARENT_HEADER};
+use crate::database::rls_transaction::RlsTransaction;
+use crate::pool::AppState;
+use crate::security::extractors::check_is_admin;
+use crate::security::{
+    request_host, AdminUser, AuthenticatedUser, CurrentTenant, MaybeAuthenticated,
+    ScopeRequirement, WithScope,
+};
+use crate::tenant::{resolve_tenant, TenantError};
+
+pub(crate) fn tenant_error(error: TenantError) -> actix_web::Error {
+    match error {
+        TenantError::MissingTenant => actix_web::error::ErrorBadRequest("Missing tenant"),
+        TenantError::TenantMismatch { .. } => {
+            actix_web::error::ErrorForbidden("Tenant not accessible")
+        }
+    }
+}
+
+/// Résout la commune de la requête (claim, sous-domaine ou en-tête).
+pub(crate) fn current_tenant(req: &HttpRequest) -> Result<CurrentTenant, actix_web::Error> {
+    let app_state = req
+        .app_data::<Data<AppState>>()
+        .ok_or_else(|| actix_web::error::ErrorInternalServerError("AppState missing"))?;
+    let user = authenticated_user(req).ok();
+    let host = request_host(req);
+    resolve_tenant(
+        app_state.tenant_config(),
+        user.as_ref(),
+        host.as_deref(),
+        |name| {
+            req.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        },
+    )
+    .map(CurrentTenant)
+    .map_err(tenant_error)
+}
+
+fn authenticated_user(req: &HttpRequest) -> Result<AuthenticatedUser, actix_web::Error> {
+    // Comme ton Middleware a DEJA validé le token et l'a mis dans les extensions :
+    req.extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        // Si on arrive ici, c'est que le middleware n'a pas fait son job
+        // ou que la route n'est pas protégée
+        .ok_or_else(|| actix_web::error::ErrorUnauthorized("User not authenticated"))
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(authenticated_user(req))
+    }
+}
+
+impl FromRequest for MaybeAuthenticated {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(Ok(MaybeAuthenticated(authenticated_user(req).ok())))
+    }
+}
+
+impl FromRequest for AdminUser {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let user = authenticated_user(req);
+        let app_state = req.app_data::<Data<AppState>>().cloned();
+        Box::pin(async move {
+            let user = user?;
+            let app_state = app_state
+                .ok_or_else(|| actix_web::error::ErrorInternalServerError("AppState missing"))?;
+            let is_admin = check_is_admin(&app_state, user.id).await.map_err(|e| {
+                tracing::error!(error = %e, "is_admin query error");
+                actix_web::error::ErrorInternalServerError("Database error")
+            })?;
+            if is_admin {
+                Ok(AdminUser(user))
+            } else {
+                Err(actix_web::error::ErrorForbidden("User is not an admin"))
+            }
+        })
+    }
+}
+
+impl<S: ScopeRequirement> FromRequest for WithScope<S> {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(authenticated_user(req).and_then(|user| {
+            WithScope::new(user).ok_or_else(|| actix_web::error::ErrorForbidden("Missing scope"))
+        }))
+    }
+}
+
+impl FromRequest for CurrentTenant {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(current_tenant(req))
+    }
+}
+
+/// Ouvre la transaction RLS avec l'utilisateur et la commune de la requête.
+/// Un super-administrateur sans commune demandée a un `app.tenant_id` vide.
+impl FromRequest for RlsTransaction {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let context = authenticated_user(req).and_then(|user| {
+            let tenant = current_tenant(req)?;
+            Ok((user, tenant))
+        });
+        let db_pool = req
+            .app_data::<Data<AppState>>()
+            .and_then(|app_state| app_state.db_pool().cloned());
+        Box::pin(async move {
+            let (user, tenant) = context?;
+            let db_pool = db_pool.ok_or_else(|| {
+                actix_web::error::ErrorInternalServerError("Database pool missing")
+            })?;
+            RlsTransaction::begin(&db_pool, user.id, tenant.get_tenant_id())
+                .await
+                .map_err(|e| {
+                    tracing::error!(error = %e, "RLS transaction error");
+                    actix_web::error::ErrorInternalServerError("Database error")
+                })
+        })
+    }
+}
+
+/// Identifiant posé par `RequestIdMiddleware`, ou calculé depuis les en-têtes sans lui.
+impl FromRequest for RequestId {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let request_id = req.extensions().get::<RequestId>().cloned();
+        ready(Ok(request_id.unwrap_or_else(|| {
+            let header = |name| {
+                req.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            };
+            RequestId::from_headers(header(REQUEST_ID_HEADER), header(TRACEPARENT_HEADER))
+        })))
+    }
+}
@@ -0,0 +1,21 @@
+use crate::security::AuthenticatedUser;
+use std::ops::Deref;
+
+/// Utilisateur authentifié et administrateur (`is_admin`). L'extracteur répond
+/// 401 sans utilisateur et 403 s'il n'est pas administrateur.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdminUser(pub AuthenticatedUser);
+
+impl AdminUser {
+    pub fn into_inner(self) -> AuthenticatedUser {
+        self.0
+    }
+}
+
+impl Deref for AdminUser {
+    type Target = AuthenticatedUser;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
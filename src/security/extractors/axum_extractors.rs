@@ -0,0 +1,156 @@
+use axum::extract::{FromRef, FromRequestParts, OptionalFromRequestParts};
+use axum::http::{header::HOST, request::Parts, StatusCode};
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::correlation::{RequestId, REQUEST_ID_HEADER, TRACEPARENT_HEADER};
+use crate::database::rls_transaction::RlsTransaction;
+use crate::pool::AppState;
+use crate::security::extractors::check_is_admin;
+use crate::security::{
+    AdminUser, AuthenticatedUser, CurrentTenant, MaybeAuthenticated, ScopeRequirement, WithScope,
+};
+use crate::tenant::{resolve_tenant, TenantError};
+
+/// Rejet des extracteurs d'authentification : statut et message générique.
+type AuthRejection = (StatusCode, &'static str);
+
+/// Les extracteurs axum lisent l'`AuthenticatedUser` que la couche d'authentification
+/// du service a placé dans les extensions de la requête.
+fn authenticated_user(parts: &Parts) -> Result<AuthenticatedUser, AuthRejection> {
+    parts
+        .extensions
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or((StatusCode::UNAUTHORIZED, "User not authenticated"))
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedUser {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        authenticated_user(parts)
+    }
+}
+
+impl<S: Send + Sync> OptionalFromRequestParts<S> for AuthenticatedUser {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &S,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        Ok(authenticated_user(parts).ok())
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for MaybeAuthenticated {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(MaybeAuthenticated(authenticated_user(parts).ok()))
+    }
+}
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+    Arc<AppState>: FromRef<S>,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = authenticated_user(parts)?;
+        let app_state = Arc::<AppState>::from_ref(state);
+        match check_is_admin(&app_state, user.id).await {
+            Ok(true) => Ok(AdminUser(user)),
+            Ok(false) => Err((StatusCode::FORBIDDEN, "User is not an admin")),
+            Err(e) => {
+                tracing::error!(error = %e, "is_admin query error");
+                Err((StatusCode::INTERNAL_SERVER_ERROR, "Database error"))
+            }
+        }
+    }
+}
+
+impl<S: Send + Sync, R: ScopeRequirement> FromRequestParts<S> for WithScope<R> {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        WithScope::new(authenticated_user(parts)?).ok_or((StatusCode::FORBIDDEN, "Missing scope"))
+    }
+}
+
+impl<S> FromRequestParts<S> for CurrentTenant
+where
+    S: Send + Sync,
+    Arc<AppState>: FromRef<S>,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let app_state = Arc::<AppState>::from_ref(state);
+        let user = authenticated_user(parts).ok();
+        let host = parts
+            .headers
+            .get(HOST)
+            .and_then(|value| value.to_str().ok())
+            .or_else(|| parts.uri.host());
+        resolve_tenant(app_state.tenant_config(), user.as_ref(), host, |name| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        })
+        .map(CurrentTenant)
+        .map_err(|e| match e {
+            TenantError::MissingTenant => (StatusCode::BAD_REQUEST, "Missing tenant"),
+            TenantError::TenantMismatch { .. } => (StatusCode::FORBIDDEN, "Tenant not accessible"),
+        })
+    }
+}
+
+impl<S> FromRequestParts<S> for RlsTransaction
+where
+    S: Send + Sync,
+    Arc<AppState>: FromRef<S>,
+{
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = authenticated_user(parts)?;
+        let tenant = CurrentTenant::from_request_parts(parts, state).await?;
+        let app_state = Arc::<AppState>::from_ref(state);
+        let db_pool = app_state
+            .db_pool()
+            .ok_or((StatusCode::INTERNAL_SERVER_ERROR, "Database pool missing"))?;
+        RlsTransaction::begin(db_pool, user.id, tenant.get_tenant_id())
+            .await
+            .map_err(|e| {
+                tracing::error!(error = %e, "RLS transaction error");
+                (StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+            })
+    }
+}
+
+/// Identifiant posé par `request_id_middleware`, ou calculé depuis les en-têtes sans lui.
+impl<S: Send + Sync> FromRequestParts<S> for RequestId {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(request_id) = parts.extensions.get::<RequestId>() {
+            return Ok(request_id.clone());
+        }
+        let header = |name| {
+            parts
+                .headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+        };
+        Ok(RequestId::from_headers(
+            header(REQUEST_ID_HEADER),
+            header(TRACEPARENT_HEADER),
+        ))
+    }
+}
@@ -0,0 +1,23 @@
+use crate::database::errors::DatabaseError;
+use crate::database::queries::is_admin_query;
+use crate::database::query_views::IsAdminQueryView;
+use crate::pool::AppState;
+
+/// `is_admin` de l'utilisateur, via le `UserCache` de l'`AppState` puis la base.
+pub(crate) async fn check_is_admin(
+    app_state: &AppState,
+    user_id: u64,
+) -> Result<bool, DatabaseError> {
+    let user_cache = app_state.user_cache();
+    if let Some(is_admin) = user_cache.get_is_admin(user_id) {
+        return Ok(is_admin);
+    }
+
+    let pool = app_state
+        .db_pool()
+        .cloned()
+        .ok_or(DatabaseError::NotInitialized)?;
+    let is_admin = is_admin_query(IsAdminQueryView::new(user_id), pool).await?;
+    user_cache.insert_is_admin(user_id, is_admin);
+    Ok(is_admin)
+}
@@ -0,0 +1,20 @@
+use crate::tenant::TenantScope;
+
+/// Commune de la requête, résolue selon la `TenantConfig` de l'`AppState`.
+/// `TenantScope::All` pour un super-administrateur sans commune demandée.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurrentTenant(pub TenantScope);
+
+impl CurrentTenant {
+    pub fn scope(&self) -> &TenantScope {
+        &self.0
+    }
+
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        self.0.get_tenant_id()
+    }
+
+    pub fn into_inner(self) -> TenantScope {
+        self.0
+    }
+}
@@ -0,0 +1,21 @@
+use crate::security::AuthenticatedUser;
+
+/// Utilisateur authentifié s'il y en a un : n'échoue jamais, pour les handlers
+/// qui répondent différemment aux visiteurs anonymes. Derrière `JwtMiddleware`, la route
+/// doit être déclarée avec `AppState::with_optional_auth_path`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MaybeAuthenticated(pub Option<AuthenticatedUser>);
+
+impl MaybeAuthenticated {
+    pub fn user(&self) -> Option<&AuthenticatedUser> {
+        self.0.as_ref()
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn into_inner(self) -> Option<AuthenticatedUser> {
+        self.0
+    }
+}
@@ -0,0 +1,24 @@
+mod admin_user;
+pub use admin_user::AdminUser;
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+mod check_is_admin;
+#[cfg(any(feature = "actix", feature = "axum"))]
+pub(crate) use check_is_admin::check_is_admin;
+
+mod current_tenant;
+pub use current_tenant::CurrentTenant;
+
+mod maybe_authenticated;
+pub use maybe_authenticated::MaybeAuthenticated;
+
+mod with_scope;
+pub use with_scope::{ScopeRequirement, WithScope};
+
+#[cfg(feature = "actix")]
+mod actix_extractors;
+#[cfg(feature = "actix")]
+pub(crate) use actix_extractors::current_tenant;
+
+#[cfg(feature = "axum")]
+mod axum_extractors;
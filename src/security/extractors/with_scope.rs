@@ -0,0 +1,60 @@
+use crate::security::AuthenticatedUser;
+use std::marker::PhantomData;
+
+/**
+ * Scope attaché à un type, pour l'extracteur `WithScope`.
+ * Se déclare avec la macro `scope_requirement!`.
+ */
+pub trait ScopeRequirement: 'static {
+    const SCOPE: &'static str;
+}
+
+/**
+ * Déclare un type marqueur implémentant `ScopeRequirement`.
+ *
+ * ```
+ * use mairie360_api_lib::scope_requirement;
+ *
+ * scope_requirement!(DocumentsRead, "documents:read");
+ * ```
+ */
+#[macro_export]
+macro_rules! scope_requirement {
+    ($name:ident, $scope:literal) => {
+        pub struct $name;
+
+        impl $crate::security::ScopeRequirement for $name {
+            const SCOPE: &'static str = $scope;
+        }
+    };
+}
+
+/**
+ * Utilisateur authentifié dont le token porte le scope `S::SCOPE`.
+ * L'extracteur répond 401 sans utilisateur et 403 sans le scope :
+ *
+ * `async fn export(user: WithScope<DocumentsRead>) -> HttpResponse`
+ */
+pub struct WithScope<S: ScopeRequirement> {
+    user: AuthenticatedUser,
+    _scope: PhantomData<S>,
+}
+
+impl<S: ScopeRequirement> WithScope<S> {
+    /// `None` si l'utilisateur n'a pas le scope.
+    #[cfg(any(feature = "actix", feature = "axum"))]
+    pub(crate) fn new(user: AuthenticatedUser) -> Option<Self> {
+        user.has_scope(S::SCOPE).then_some(Self {
+            user,
+            _scope: PhantomData,
+        })
+    }
+
+    pub fn user(&self) -> &AuthenticatedUser {
+        &self.user
+    }
+
+    pub fn into_inner(self) -> AuthenticatedUser {
+        self.user
+    }
+}
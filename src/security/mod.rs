@@ -1,8 +1,36 @@
+#[cfg(feature = "actix")]
+mod access_check;
+#[cfg(feature = "actix")]
+mod access_requirements;
+#[cfg(feature = "actix")]
+pub use access_requirements::{AccessConfigError, AccessRequirements};
+#[cfg(feature = "actix")]
 mod admin_middleware;
+#[cfg(feature = "actix")]
 pub use admin_middleware::AdminMiddleware;
+#[cfg(feature = "actix")]
 mod auth_middleware;
+#[cfg(feature = "actix")]
 pub use auth_middleware::JwtMiddleware;
 mod auth_user;
-pub use auth_user::AuthenticatedUser;
+pub use auth_user::{AuthMethod, AuthenticatedUser};
+#[cfg(feature = "actix")]
+mod client_addr;
+#[cfg(feature = "actix")]
+pub(crate) use client_addr::{client_ip, request_host};
+mod extractors;
+pub use extractors::{AdminUser, CurrentTenant, MaybeAuthenticated, ScopeRequirement, WithScope};
+#[cfg(feature = "actix")]
+mod request_span;
+#[cfg(feature = "actix")]
+pub(crate) use request_span::{record_check_latency, request_span};
+#[cfg(feature = "actix")]
+mod require_access;
+#[cfg(feature = "actix")]
+pub use require_access::{AccessRequirement, RegisteredRequirement, RequireAccess};
+#[cfg(feature = "actix")]
 mod right_middleware;
-pub use right_middleware::{access_guard_middleware, AccessCheckConfig};
+#[cfg(feature = "actix")]
+pub use right_middleware::{access_guard_middleware, AccessCheckConfig, InstanceIdSource};
+mod trusted_proxies;
+pub use trusted_proxies::TrustedProxies;
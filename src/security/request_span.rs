@@ -0,0 +1,37 @@
+use crate::correlation::RequestId;
+use actix_web::{HttpMessage, HttpRequest};
+use std::time::Instant;
+use tracing::Span;
+
+/// Span des middlewares de sécurité. `user_id` est renseigné une fois l'utilisateur
+/// authentifié et `decision` une fois la vérification faite ; les en-têtes
+/// (dont `Authorization`) ne sont jamais journalisés.
+pub(crate) fn request_span(middleware: &'static str, req: &HttpRequest) -> Span {
+    // Identifiant validé par `RequestIdMiddleware`, jamais l'en-tête brut
+    let request_id = req
+        .extensions()
+        .get::<RequestId>()
+        .map(RequestId::to_string)
+        .unwrap_or_default();
+    tracing::info_span!(
+        "http.security",
+        middleware,
+        method = %req.method(),
+        path = req.path(),
+        request_id = request_id.as_str(),
+        user_id = tracing::field::Empty,
+        decision = tracing::field::Empty,
+    )
+}
+
+/// Trace la décision et la durée de la vérification, hors exécution du handler.
+/// À appeler dans le span créé par `request_span`.
+pub(crate) fn record_check_latency(middleware: &'static str, started: Instant, allowed: bool) {
+    let elapsed = started.elapsed();
+    let latency_ms = elapsed.as_secs_f64() * 1000.0;
+    Span::current().record("decision", if allowed { "allow" } else { "deny" });
+    tracing::debug!(latency_ms, allowed, "security check completed");
+    crate::metrics::record_access_check(middleware, elapsed, allowed);
+    #[cfg(feature = "telemetry")]
+    crate::telemetry::record_security_decision(middleware, elapsed, allowed);
+}
@@ -0,0 +1,122 @@
+use actix_web::{dev::Payload, FromRequest, HttpRequest};
+use futures_util::future::LocalBoxFuture;
+use std::marker::PhantomData;
+
+use crate::security::access_check::enforce_access;
+use crate::security::{AccessCheckConfig, AuthenticatedUser};
+
+/**
+ * Exigence d'accès attachée à un type, pour l'extracteur `RequireAccess`.
+ * Se déclare avec la macro `access_requirement!`.
+ */
+pub trait AccessRequirement: 'static {
+    const RESOURCE_NAME: &'static str;
+    const ACTION: &'static str;
+    const ID_PARAM: Option<&'static str>;
+
+    /// Configuration complète de la vérification. À redéfinir pour un ID non entier,
+    /// lu dans la query string, ou pour ajouter des vérifications parentes. Les ID lus
+    /// dans le corps JSON ne sont pas supportés ici : le corps appartient au handler.
+    fn config() -> AccessCheckConfig {
+        AccessCheckConfig {
+            resource_name: Self::RESOURCE_NAME,
+            action: Self::ACTION,
+            id_param_pattern: Self::ID_PARAM,
+            ..AccessCheckConfig::default()
+        }
+    }
+}
+
+/// Exigence déclarée avec `access_requirement!`, recensée à l'édition de liens pour
+/// que `AccessRequirements::validate` la vérifie sans qu'on ait à la lister.
+#[doc(hidden)]
+pub struct RegisteredRequirement(pub fn() -> AccessCheckConfig);
+
+inventory::collect!(RegisteredRequirement);
+
+/// Configurations de toutes les exigences déclarées avec `access_requirement!`.
+pub(crate) fn registered_requirements() -> impl Iterator<Item = AccessCheckConfig> {
+    inventory::iter::<RegisteredRequirement>
+        .into_iter()
+        .map(|requirement| (requirement.0)())
+}
+
+/**
+ * Déclare un type marqueur implémentant `AccessRequirement`, automatiquement
+ * vérifié par `AccessRequirements::validate`.
+ *
+ * ```
+ * use mairie360_api_lib::access_requirement;
+ *
+ * access_requirement!(ReadUser, "users", "read", "user_id");
+ * access_requirement!(ListUsers, "users", "read");
+ * ```
+ */
+#[macro_export]
+macro_rules! access_requirement {
+    ($name:ident, $resource:literal, $action:literal) => {
+        pub struct $name;
+
+        impl $crate::security::AccessRequirement for $name {
+            const RESOURCE_NAME: &'static str = $resource;
+            const ACTION: &'static str = $action;
+            const ID_PARAM: Option<&'static str> = None;
+        }
+
+        $crate::inventory::submit! {
+            $crate::security::RegisteredRequirement(
+                <$name as $crate::security::AccessRequirement>::config,
+            )
+        }
+    };
+    ($name:ident, $resource:literal, $action:literal, $id_param:literal) => {
+        pub struct $name;
+
+        impl $crate::security::AccessRequirement for $name {
+            const RESOURCE_NAME: &'static str = $resource;
+            const ACTION: &'static str = $action;
+            const ID_PARAM: Option<&'static str> = Some($id_param);
+        }
+
+        $crate::inventory::submit! {
+            $crate::security::RegisteredRequirement(
+                <$name as $crate::security::AccessRequirement>::config,
+            )
+        }
+    };
+}
+
+/**
+ * Extracteur vérifiant l'exigence `R` avant d'exécuter le handler, sans scope ni
+ * `AccessCheckConfig` dans `app_data` :
+ *
+ * `async fn get_user(access: RequireAccess<ReadUser>) -> HttpResponse`
+ *
+ * Nécessite que `JwtMiddleware` ait authentifié la requête.
+ */
+pub struct RequireAccess<R: AccessRequirement> {
+    user: AuthenticatedUser,
+    _requirement: PhantomData<R>,
+}
+
+impl<R: AccessRequirement> RequireAccess<R> {
+    pub fn user(&self) -> &AuthenticatedUser {
+        &self.user
+    }
+}
+
+impl<R: AccessRequirement> FromRequest for RequireAccess<R> {
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        Box::pin(async move {
+            let user = enforce_access(&req, None, &R::config()).await?;
+            Ok(RequireAccess {
+                user,
+                _requirement: PhantomData,
+            })
+        })
+    }
+}
@@ -1,92 +1,127 @@
+use actix_web::Error;
 use actix_web::{
     body::BoxBody,
-    dev::{ServiceRequest, ServiceResponse},
+    dev::{Payload, ServiceRequest, ServiceResponse},
     middleware::Next,
+    web::Bytes,
+    FromRequest, HttpMessage,
 };
-use actix_web::{Error, HttpMessage};
 
-use crate::{pool::AppState, security::AuthenticatedUser};
+use crate::database::instance_id::InstanceIdKind;
+use crate::security::access_check::enforce_access;
+use crate::security::{record_check_latency, request_span, AuthenticatedUser};
+use std::time::Instant;
+use tracing::Instrument;
 
-#[derive(Clone)]
+/// Emplacement de l'identifiant d'instance dans la requête.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InstanceIdSource {
+    /// Paramètre de chemin (`/users/{user_id}`)
+    #[default]
+    Path,
+    /// Paramètre de query string (`?user_id=3`)
+    Query,
+    /// Champ du corps JSON : clé de premier niveau (`user_id`) ou JSON pointer (`/owner/id`)
+    Json,
+}
+
+#[derive(Clone, Default)]
 pub struct AccessCheckConfig {
     /// Le nom de la ressource (ex: "users", "sessions")
     pub resource_name: &'static str,
     /// L'action requise (ex: "read", "write")
     pub action: &'static str,
-    /// Le nom du paramètre dans l'URL (ex: "user_id", "id")
+    /// Le nom du paramètre portant l'ID (ex: "user_id", "id")
     /// Si None, on considère que c'est une vérification globale (instance = NULL)
     pub id_param_pattern: Option<&'static str>,
+    /// Le type attendu pour l'ID (entier par défaut)
+    pub id_kind: InstanceIdKind,
+    /// Où lire l'ID (chemin par défaut)
+    pub id_source: InstanceIdSource,
+    /// Vérifications préalables sur les ressources parentes, dans l'ordre
+    /// (ex: le groupe `{gid}` avant le document `{did}` de `/groups/{gid}/documents/{did}`)
+    pub parents: Vec<AccessCheckConfig>,
 }
 
-pub async fn access_guard_middleware(
-    req: ServiceRequest,
-    next: Next<BoxBody>,
-) -> Result<ServiceResponse<BoxBody>, Error> {
-    // 1. Récupérer la config de la route
-    let config = req.app_data::<AccessCheckConfig>().ok_or_else(|| {
-        actix_web::error::ErrorInternalServerError("AccessConfig missing on route")
-    })?;
+impl AccessCheckConfig {
+    pub fn new(resource_name: &'static str, action: &'static str) -> Self {
+        Self {
+            resource_name,
+            action,
+            ..Self::default()
+        }
+    }
 
-    // 2. Récupérer l'utilisateur injecté par JwtMiddleware
-    let user = req
-        .extensions()
-        .get::<AuthenticatedUser>()
-        .copied()
-        .ok_or_else(|| actix_web::error::ErrorUnauthorized("User not authenticated"))?;
+    pub fn with_id_param(mut self, id_param: &'static str) -> Self {
+        self.id_param_pattern = Some(id_param);
+        self
+    }
 
-    // 3. Extraire l'ID de l'instance dans l'URL (si défini)
-    let mut instance_id: Option<i32> = None;
-    if let Some(param_name) = config.id_param_pattern {
-        if let Some(val) = req.match_info().get(param_name) {
-            instance_id = val.parse::<i32>().ok();
-            if instance_id.is_none() {
-                return Err(actix_web::error::ErrorBadRequest(
-                    "Invalid ID format in URL",
-                ));
-            }
-        }
+    pub fn with_id_kind(mut self, id_kind: InstanceIdKind) -> Self {
+        self.id_kind = id_kind;
+        self
     }
 
-    // 4. Appel à la base de données
-    let app_state = req
-        .app_data::<actix_web::web::Data<AppState>>()
-        .ok_or_else(|| actix_web::error::ErrorInternalServerError("AppState missing"))?;
+    pub fn with_id_source(mut self, id_source: InstanceIdSource) -> Self {
+        self.id_source = id_source;
+        self
+    }
 
-    let db_pool = match app_state.db_pool.clone() {
-        Some(pool) => pool,
-        None => {
-            return Err(actix_web::error::ErrorInternalServerError(
-                "Database pool missing",
-            ))
-        }
-    };
+    pub fn with_parent(mut self, parent: AccessCheckConfig) -> Self {
+        self.parents.push(parent);
+        self
+    }
 
-    // Mise à jour ici : On récupère un i32 au lieu d'un bool
-    let access_status = sqlx::query_scalar::<_, i32>("SELECT check_access($1, $2, $3, $4)")
-        .bind(user.id as i32)
-        .bind(config.resource_name)
-        .bind(config.action)
-        .bind(instance_id)
-        .fetch_one(&db_pool)
-        .await
-        .map_err(|e| {
-            eprintln!("Access check SQL error: {:?}", e);
-            actix_web::error::ErrorInternalServerError("Database error during access check")
+    /// Toutes les vérifications à effectuer, parents (récursivement) d'abord.
+    pub fn checks(&self) -> Vec<&AccessCheckConfig> {
+        let mut checks: Vec<&AccessCheckConfig> = self
+            .parents
+            .iter()
+            .flat_map(AccessCheckConfig::checks)
+            .collect();
+        checks.push(self);
+        checks
+    }
+
+    /// Vrai si cette vérification ou une vérification parente lit le corps JSON.
+    pub fn needs_body(&self) -> bool {
+        (self.id_param_pattern.is_some() && self.id_source == InstanceIdSource::Json)
+            || self.parents.iter().any(AccessCheckConfig::needs_body)
+    }
+}
+
+pub async fn access_guard_middleware(
+    req: ServiceRequest,
+    next: Next<BoxBody>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    // 1. Récupérer la config de la route
+    let config = req
+        .app_data::<AccessCheckConfig>()
+        .cloned()
+        .ok_or_else(|| {
+            actix_web::error::ErrorInternalServerError("AccessConfig missing on route")
         })?;
 
-    // 5. Verdict étendu selon le code retourné par la DB
-    match access_status {
-        1 => {
-            // Accès accordé, on passe au handler ou middleware suivant
-            next.call(req).await
-        }
-        -1 => {
-            // La ressource ou la table n'existe pas -> 404 Not Found propre
-            Err(actix_web::error::ErrorNotFound("Resource not found"))
-        }
-        _ => {
-            // Pas de droits (0) ou toute autre valeur -> 403 Forbidden standard
-            Err(actix_web::error::ErrorForbidden("Insufficient permissions"))
-        }
+    // 2. Lire le corps si un ID y est attendu, puis le remettre en place pour le handler
+    let (req, body) = if config.needs_body() {
+        let (http_req, mut payload) = req.into_parts();
+        let body = Bytes::from_request(&http_req, &mut payload).await?;
+        let req = ServiceRequest::from_parts(http_req, Payload::from(body.clone()));
+        (req, Some(body))
+    } else {
+        (req, None)
+    };
+
+    // 3. Vérification partagée avec l'extracteur `RequireAccess`
+    let span = request_span("access_guard", req.request());
+    if let Some(user) = req.extensions().get::<AuthenticatedUser>() {
+        span.record("user_id", user.id);
     }
+    let started = Instant::now();
+    let result = enforce_access(req.request(), body.as_deref(), &config)
+        .instrument(span.clone())
+        .await;
+    span.in_scope(|| record_check_latency("access_guard", started, result.is_ok()));
+    result?;
+    next.call(req).await
 }
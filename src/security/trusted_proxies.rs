@@ -0,0 +1,25 @@
+use std::net::IpAddr;
+
+/// Reverse proxies dont les en-têtes `Forwarded`, `X-Forwarded-For` et
+/// `X-Forwarded-Host` sont crus. Vide par défaut : l'adresse du client est
+/// celle de la connexion TCP et l'hôte celui de la requête.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrustedProxies {
+    addrs: Vec<IpAddr>,
+}
+
+impl TrustedProxies {
+    pub fn new(addrs: impl IntoIterator<Item = IpAddr>) -> Self {
+        Self {
+            addrs: addrs.into_iter().collect(),
+        }
+    }
+
+    pub fn is_trusted(&self, addr: &IpAddr) -> bool {
+        self.addrs.contains(addr)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addrs.is_empty()
+    }
+}
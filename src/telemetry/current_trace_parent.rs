@@ -0,0 +1,18 @@
+use crate::correlation::TraceParent;
+use opentelemetry::trace::TraceContextExt;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// `traceparent` du span courant exporté, pour que le service appelé s'y rattache.
+pub(crate) fn current_trace_parent() -> Option<TraceParent> {
+    let context = Span::current().context();
+    let span = context.span();
+    let span_context = span.span_context();
+    span_context.is_valid().then(|| {
+        TraceParent::from_span(
+            span_context.trace_id().to_string(),
+            span_context.span_id().to_string(),
+            span_context.is_sampled(),
+        )
+    })
+}
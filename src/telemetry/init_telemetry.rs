@@ -0,0 +1,47 @@
+use crate::telemetry::instruments::install_instruments;
+use crate::telemetry::{TelemetryConfig, TelemetryError, TelemetryGuard};
+use opentelemetry::global;
+use opentelemetry_otlp::{MetricExporter, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+
+/// Configure l'export OTLP/HTTP des traces et des métriques et installe les fournisseurs
+/// globaux. Les spans `tracing` ne sont exportés qu'une fois `tracing_layer` ajoutée
+/// au subscriber du service.
+pub fn init_telemetry(config: &TelemetryConfig) -> Result<TelemetryGuard, TelemetryError> {
+    let resource = Resource::builder()
+        .with_service_name(config.service_name.clone())
+        .build();
+
+    let span_exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(config.signal_endpoint("traces"))
+        .with_timeout(config.export_timeout)
+        .build()?;
+    let tracer_provider = SdkTracerProvider::builder()
+        .with_batch_exporter(span_exporter)
+        .with_resource(resource.clone())
+        .build();
+
+    let metric_exporter = MetricExporter::builder()
+        .with_http()
+        .with_endpoint(config.signal_endpoint("metrics"))
+        .with_timeout(config.export_timeout)
+        .build()?;
+    let reader = PeriodicReader::builder(metric_exporter)
+        .with_interval(config.metrics_interval)
+        .build();
+    let meter_provider = SdkMeterProvider::builder()
+        .with_reader(reader)
+        .with_resource(resource)
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(tracer_provider.clone());
+    global::set_meter_provider(meter_provider.clone());
+    install_instruments(&global::meter("mairie360_api_lib"));
+
+    Ok(TelemetryGuard::new(tracer_provider, meter_provider))
+}
@@ -0,0 +1,87 @@
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::KeyValue;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Instruments créés à l'initialisation : avant `init_telemetry`, rien n'est enregistré.
+#[cfg_attr(not(feature = "actix"), allow(dead_code))]
+pub(crate) struct Instruments {
+    db_duration: Histogram<f64>,
+    redis_duration: Histogram<f64>,
+    security_duration: Histogram<f64>,
+    security_decisions: Counter<u64>,
+}
+
+static INSTRUMENTS: OnceLock<Instruments> = OnceLock::new();
+
+fn outcome(ok: bool) -> KeyValue {
+    KeyValue::new("outcome", if ok { "success" } else { "error" })
+}
+
+pub(crate) fn install_instruments(meter: &Meter) {
+    let seconds = |name: &'static str, description: &'static str| {
+        meter
+            .f64_histogram(name)
+            .with_unit("s")
+            .with_description(description)
+            .build()
+    };
+    let _ = INSTRUMENTS.set(Instruments {
+        db_duration: seconds(
+            "db.client.operation.duration",
+            "Durée des requêtes exécutées via les query views",
+        ),
+        redis_duration: seconds(
+            "redis.client.operation.duration",
+            "Durée des commandes Redis",
+        ),
+        security_duration: seconds(
+            "security.check.duration",
+            "Durée des vérifications des middlewares de sécurité",
+        ),
+        security_decisions: meter
+            .u64_counter("security.decisions")
+            .with_description("Décisions des middlewares de sécurité")
+            .build(),
+    });
+}
+
+pub(crate) fn record_query(query_view: &'static str, elapsed: Duration, ok: bool) {
+    if let Some(instruments) = INSTRUMENTS.get() {
+        instruments.db_duration.record(
+            elapsed.as_secs_f64(),
+            &[
+                KeyValue::new("db.system.name", "postgresql"),
+                KeyValue::new("query_view", query_view),
+                outcome(ok),
+            ],
+        );
+    }
+}
+
+pub(crate) fn record_redis_command(command: &'static str, elapsed: Duration, ok: bool) {
+    if let Some(instruments) = INSTRUMENTS.get() {
+        instruments.redis_duration.record(
+            elapsed.as_secs_f64(),
+            &[
+                KeyValue::new("db.system.name", "redis"),
+                KeyValue::new("db.operation.name", command),
+                outcome(ok),
+            ],
+        );
+    }
+}
+
+#[cfg(feature = "actix")]
+pub(crate) fn record_security_decision(middleware: &'static str, elapsed: Duration, allowed: bool) {
+    if let Some(instruments) = INSTRUMENTS.get() {
+        let attributes = [
+            KeyValue::new("middleware", middleware),
+            KeyValue::new("decision", if allowed { "allow" } else { "deny" }),
+        ];
+        instruments
+            .security_duration
+            .record(elapsed.as_secs_f64(), &attributes);
+        instruments.security_decisions.add(1, &attributes);
+    }
+}
@@ -0,0 +1,24 @@
+mod telemetry_config;
+pub use telemetry_config::TelemetryConfig;
+
+mod telemetry_error;
+pub use telemetry_error::TelemetryError;
+
+mod telemetry_guard;
+pub use telemetry_guard::TelemetryGuard;
+
+mod instruments;
+#[cfg(feature = "actix")]
+pub(crate) use instruments::record_security_decision;
+pub(crate) use instruments::{record_query, record_redis_command};
+
+mod current_trace_parent;
+pub(crate) use current_trace_parent::current_trace_parent;
+
+#[cfg(any(feature = "actix", feature = "axum"))]
+mod remote_parent;
+#[cfg(any(feature = "actix", feature = "axum"))]
+pub(crate) use remote_parent::set_remote_parent;
+
+mod init_telemetry;
+pub use init_telemetry::init_telemetry;
@@ -0,0 +1,26 @@
+use crate::correlation::TraceParent;
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+use opentelemetry::Context;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Rattache le span de la requête à la trace de l'appelant (`traceparent` reçu),
+/// pour que les spans exportés forment une seule trace entre les APIs.
+pub(crate) fn set_remote_parent(span: &Span, trace_parent: &TraceParent) {
+    if !trace_parent.is_remote() {
+        return;
+    }
+    let (Ok(trace_id), Ok(span_id)) = (
+        TraceId::from_hex(trace_parent.get_trace_id()),
+        SpanId::from_hex(trace_parent.get_parent_id()),
+    ) else {
+        return;
+    };
+    let flags = if trace_parent.is_sampled() {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+    let span_context = SpanContext::new(trace_id, span_id, flags, true, TraceState::default());
+    let _ = span.set_parent(Context::new().with_remote_span_context(span_context));
+}
@@ -0,0 +1,63 @@
+use crate::env_manager::get_env_var;
+use std::time::Duration;
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:4318";
+
+/// Configuration de l'export OTLP/HTTP (protobuf) des traces et des métriques.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TelemetryConfig {
+    pub service_name: String,
+    /// URL de base du collecteur ; `/v1/traces` et `/v1/metrics` y sont ajoutés
+    pub endpoint: String,
+    pub export_timeout: Duration,
+    /// Période d'export des métriques
+    pub metrics_interval: Duration,
+}
+
+impl TelemetryConfig {
+    pub fn new(service_name: impl Into<String>) -> Self {
+        Self {
+            service_name: service_name.into(),
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            export_timeout: Duration::from_secs(10),
+            metrics_interval: Duration::from_secs(60),
+        }
+    }
+
+    /// Lit `OTEL_SERVICE_NAME`, `OTEL_EXPORTER_OTLP_ENDPOINT`, `OTEL_EXPORTER_OTLP_TIMEOUT`
+    /// et `OTEL_METRIC_EXPORT_INTERVAL` (en millisecondes), avec `service_name` par défaut.
+    pub fn from_env(service_name: impl Into<String>) -> Self {
+        let millis = |name: &str| {
+            get_env_var(name)
+                .and_then(|value| value.parse::<u64>().ok())
+                .map(Duration::from_millis)
+        };
+        let config = Self::new(get_env_var("OTEL_SERVICE_NAME").unwrap_or(service_name.into()));
+        Self {
+            endpoint: get_env_var("OTEL_EXPORTER_OTLP_ENDPOINT").unwrap_or(config.endpoint),
+            export_timeout: millis("OTEL_EXPORTER_OTLP_TIMEOUT").unwrap_or(config.export_timeout),
+            metrics_interval: millis("OTEL_METRIC_EXPORT_INTERVAL")
+                .unwrap_or(config.metrics_interval),
+            service_name: config.service_name,
+        }
+    }
+
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    pub fn with_export_timeout(mut self, export_timeout: Duration) -> Self {
+        self.export_timeout = export_timeout;
+        self
+    }
+
+    pub fn with_metrics_interval(mut self, metrics_interval: Duration) -> Self {
+        self.metrics_interval = metrics_interval;
+        self
+    }
+
+    pub(crate) fn signal_endpoint(&self, signal: &str) -> String {
+        format!("{}/v1/{}", self.endpoint.trim_end_matches('/'), signal)
+    }
+}
@@ -0,0 +1,11 @@
+use opentelemetry_otlp::ExporterBuildError;
+use opentelemetry_sdk::error::OTelSdkError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum TelemetryError {
+    #[error("OTLP exporter configuration error: {0}")]
+    Exporter(#[from] ExporterBuildError),
+    #[error("Telemetry export error: {0}")]
+    Export(#[from] OTelSdkError),
+}
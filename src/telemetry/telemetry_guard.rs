@@ -0,0 +1,47 @@
+use crate::telemetry::TelemetryError;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Fournisseurs OTLP installés par `init_telemetry`. À garder jusqu'à l'arrêt du
+/// service, puis appeler `shutdown` pour exporter les derniers spans et métriques.
+pub struct TelemetryGuard {
+    tracer_provider: SdkTracerProvider,
+    meter_provider: SdkMeterProvider,
+}
+
+impl TelemetryGuard {
+    pub(crate) fn new(
+        tracer_provider: SdkTracerProvider,
+        meter_provider: SdkMeterProvider,
+    ) -> Self {
+        Self {
+            tracer_provider,
+            meter_provider,
+        }
+    }
+
+    /// Couche `tracing` exportant les spans, à composer avec celles du service :
+    /// `tracing_subscriber::registry().with(fmt::layer()).with(guard.tracing_layer()).init()`.
+    pub fn tracing_layer<S>(&self) -> OpenTelemetryLayer<S, Tracer>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer().with_tracer(self.tracer_provider.tracer("mairie360_api_lib"))
+    }
+
+    pub fn force_flush(&self) -> Result<(), TelemetryError> {
+        self.tracer_provider.force_flush()?;
+        self.meter_provider.force_flush()?;
+        Ok(())
+    }
+
+    pub fn shutdown(&self) -> Result<(), TelemetryError> {
+        self.tracer_provider.shutdown()?;
+        self.meter_provider.shutdown()?;
+        Ok(())
+    }
+}
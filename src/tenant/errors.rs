@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum TenantError {
+    /// Aucune source configurée n'a fourni de commune alors qu'elle est obligatoire,
+    /// ou une commune est demandée par un utilisateur qui n'est rattaché à aucune.
+    #[error("No tenant could be resolved for this request")]
+    MissingTenant,
+
+    /// La commune demandée (sous-domaine, en-tête) n'est pas celle du token.
+    #[error("Tenant {requested} is not accessible to a user of tenant {user_tenant}")]
+    TenantMismatch {
+        requested: String,
+        user_tenant: String,
+    },
+}
@@ -0,0 +1,14 @@
+mod errors;
+pub use errors::TenantError;
+
+mod tenant_scope;
+pub use tenant_scope::TenantScope;
+
+mod tenant_config;
+pub use tenant_config::{TenantConfig, TenantSource};
+
+mod resolve_tenant;
+pub use resolve_tenant::resolve_tenant;
+
+mod tenant_columns;
+pub use tenant_columns::{install_tenant_columns, TENANT_COLUMNS_SQL};
@@ -0,0 +1,49 @@
+use crate::security::AuthenticatedUser;
+use crate::tenant::{TenantConfig, TenantError, TenantScope, TenantSource};
+
+fn subdomain<'a>(host: &'a str, base_domain: &str) -> Option<&'a str> {
+    // On ignore le port éventuel (`lyon.mairie360.fr:8080`)
+    let host = host.split(':').next()?;
+    let prefix = host.strip_suffix(base_domain)?.strip_suffix('.')?;
+    prefix.rsplit('.').next().filter(|label| !label.is_empty())
+}
+
+/**
+ * Détermine la commune de la requête à partir des sources de `config`.
+ *
+ * Un utilisateur rattaché à une commune ne peut pas en cibler une autre, sauf s'il a
+ * le rôle super-administrateur ; sans commune demandée, ce dernier agit sur toutes.
+ * Sans claim `tenant` (ou sans utilisateur), seul un super-administrateur peut
+ * cibler une commune par le sous-domaine ou l'en-tête.
+ * `header` renvoie la valeur d'un en-tête de la requête.
+ */
+pub fn resolve_tenant(
+    config: &TenantConfig,
+    user: Option<&AuthenticatedUser>,
+    host: Option<&str>,
+    header: impl Fn(&str) -> Option<String>,
+) -> Result<TenantScope, TenantError> {
+    let requested = config.sources.iter().find_map(|source| match source {
+        TenantSource::Claim => user.and_then(|u| u.tenant.clone()),
+        TenantSource::Subdomain { base_domain } => host
+            .and_then(|h| subdomain(h, base_domain))
+            .map(str::to_string),
+        TenantSource::Header(name) => header(name).filter(|value| !value.is_empty()),
+    });
+
+    let is_super_admin = user.is_some_and(|u| u.has_role(&config.super_admin_role));
+    let user_tenant = user.and_then(|u| u.tenant.as_deref());
+
+    match (requested, user_tenant) {
+        (Some(requested), Some(user_tenant)) if requested != user_tenant && !is_super_admin => {
+            Err(TenantError::TenantMismatch {
+                requested,
+                user_tenant: user_tenant.to_string(),
+            })
+        }
+        (Some(_), None) if !is_super_admin => Err(TenantError::MissingTenant),
+        (Some(requested), _) => Ok(TenantScope::Tenant(requested)),
+        (None, _) if is_super_admin || !config.required => Ok(TenantScope::All),
+        (None, _) => Err(TenantError::MissingTenant),
+    }
+}
@@ -0,0 +1,24 @@
+use crate::database::errors::DatabaseError;
+use sqlx::PgPool;
+
+/// Colonne `tenant_id` des utilisateurs et table `tenant_resources` (commune de chaque
+/// instance de ressource), lues par les requêtes filtrées par commune.
+/// Idempotent : peut être rejoué par les migrations.
+pub const TENANT_COLUMNS_SQL: &str = r#"
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tenant_id text;
+CREATE INDEX IF NOT EXISTS users_tenant_id_idx ON users (tenant_id);
+
+CREATE TABLE IF NOT EXISTS tenant_resources (
+    resource_name text NOT NULL,
+    instance_id text NOT NULL,
+    tenant_id text NOT NULL,
+    PRIMARY KEY (resource_name, instance_id)
+);
+"#;
+
+/// Ajoute la colonne `tenant_id` et la table `tenant_resources`. À réserver aux environnements où les
+/// migrations ne la créent pas déjà (tests, outils d'administration).
+pub async fn install_tenant_columns(pool: &PgPool) -> Result<(), DatabaseError> {
+    sqlx::raw_sql(TENANT_COLUMNS_SQL).execute(pool).await?;
+    Ok(())
+}
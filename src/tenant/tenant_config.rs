@@ -0,0 +1,49 @@
+/// Emplacement de la commune dans la requête.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TenantSource {
+    /// Claim `tenant` du JWT
+    Claim,
+    /// Premier label de l'hôte sous `base_domain` (`lyon.mairie360.fr` -> `lyon`)
+    Subdomain { base_domain: String },
+    /// En-tête HTTP (ex: `X-Tenant-Id`)
+    Header(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TenantConfig {
+    /// Sources consultées dans l'ordre ; la première qui répond l'emporte.
+    pub sources: Vec<TenantSource>,
+    /// Rôle (claim `roles`) autorisé à agir sur toutes les communes.
+    pub super_admin_role: String,
+    /// Refuse les requêtes sans commune (400). Désactivé pour les déploiements mono-commune.
+    pub required: bool,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            sources: vec![TenantSource::Claim],
+            super_admin_role: "super_admin".to_string(),
+            required: false,
+        }
+    }
+}
+
+impl TenantConfig {
+    pub fn new(sources: Vec<TenantSource>) -> Self {
+        Self {
+            sources,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_super_admin_role(mut self, role: &str) -> Self {
+        self.super_admin_role = role.to_string();
+        self
+    }
+
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt::Display;
+
+/// Périmètre d'une requête : une commune, ou toutes pour un super-administrateur
+/// (ou un déploiement mono-commune).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TenantScope {
+    Tenant(String),
+    All,
+}
+
+impl TenantScope {
+    /// Commune à appliquer en filtre, `None` pour ne pas filtrer.
+    pub fn get_tenant_id(&self) -> Option<&str> {
+        match self {
+            TenantScope::Tenant(id) => Some(id),
+            TenantScope::All => None,
+        }
+    }
+
+    pub fn is_cross_tenant(&self) -> bool {
+        matches!(self, TenantScope::All)
+    }
+}
+
+impl Display for TenantScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TenantScope::Tenant(id) => write!(f, "{}", id),
+            TenantScope::All => write!(f, "*"),
+        }
+    }
+}
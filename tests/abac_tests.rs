@@ -0,0 +1,187 @@
+use async_trait::async_trait;
+use chrono::{FixedOffset, TimeZone};
+use mairie360_api_lib::abac::{
+    AbacRequest, AttributeResolver, PolicyDecision, PolicyEngine, PolicyError, PolicySet,
+};
+use mairie360_api_lib::database::errors::DatabaseError;
+use mairie360_api_lib::database::instance_id::InstanceId;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+
+/**
+ * Politiques municipales de référence : les agents ne modifient les demandes
+ * qu'aux heures d'ouverture, et uniquement pour leur propre commune.
+ */
+const MUNICIPAL_POLICIES: &str = r#"
+default_effect = "allow"
+
+[[policies]]
+id = "office-hours"
+description = "Modification des demandes du lundi au vendredi, de 8h à 18h"
+effect = "deny"
+resources = ["requests"]
+actions = ["write"]
+condition = { not = { all = [
+    { compare = { attr = "context.hour", op = "gte", value = 8 } },
+    { compare = { attr = "context.hour", op = "lt", value = 18 } },
+    { compare = { attr = "context.weekday", op = "in", value = [1, 2, 3, 4, 5] } },
+] } }
+
+[[policies]]
+id = "own-commune"
+effect = "deny"
+resources = ["requests"]
+condition = { compare = { attr = "subject.commune", op = "ne", value = { attr = "resource.commune" } } }
+"#;
+
+fn at(day: u32, hour: u32) -> chrono::DateTime<FixedOffset> {
+    // Octobre 2026 : le 19 est un lundi, le 24 un samedi.
+    FixedOffset::east_opt(7200)
+        .unwrap()
+        .with_ymd_and_hms(2026, 10, day, hour, 30, 0)
+        .unwrap()
+}
+
+fn request(action: &str, day: u32, hour: u32) -> AbacRequest {
+    AbacRequest::new(1, "requests", action)
+        .with_time(at(day, hour))
+        .with_subject_attribute("commune", json!("lyon"))
+        .with_resource_attribute("commune", json!("lyon"))
+}
+
+#[cfg(test)]
+mod policy_tests {
+    use super::*;
+
+    fn engine() -> PolicyEngine {
+        PolicyEngine::new(PolicySet::from_toml(MUNICIPAL_POLICIES).unwrap())
+    }
+
+    #[test]
+    fn test_office_hours() {
+        let engine = engine();
+        assert!(engine.evaluate(&request("write", 19, 10)).is_allowed());
+        assert_eq!(
+            engine.evaluate(&request("write", 19, 19)),
+            PolicyDecision::Deny {
+                policy_id: Some("office-hours".to_string())
+            }
+        );
+        assert!(!engine.evaluate(&request("write", 24, 10)).is_allowed());
+        // La lecture n'est pas concernée par les horaires
+        assert!(engine.evaluate(&request("read", 24, 22)).is_allowed());
+    }
+
+    #[test]
+    fn test_own_commune() {
+        let engine = engine();
+        let other =
+            request("read", 19, 10).with_resource_attribute("commune", json!("villeurbanne"));
+        assert_eq!(
+            engine.evaluate(&other),
+            PolicyDecision::Deny {
+                policy_id: Some("own-commune".to_string())
+            }
+        );
+        // Un attribut manquant ne satisfait aucune comparaison
+        let unknown = AbacRequest::new(1, "requests", "read").with_time(at(19, 10));
+        assert!(engine.evaluate(&unknown).is_allowed());
+    }
+
+    #[test]
+    fn test_default_deny_with_allow_policy() {
+        let policies = PolicySet::from_json(
+            r#"{"default_effect": "deny", "policies": [
+                {"id": "owner", "effect": "allow", "resources": ["documents"],
+                 "condition": {"compare": {"attr": "subject.id", "op": "eq", "value": {"attr": "resource.owner_id"}}}}
+            ]}"#,
+        )
+        .unwrap();
+        let engine = PolicyEngine::new(policies);
+        let owned =
+            AbacRequest::new(4, "documents", "read").with_resource_attribute("owner_id", json!(4));
+        assert!(engine.evaluate(&owned).is_allowed());
+        let foreign =
+            AbacRequest::new(5, "documents", "read").with_resource_attribute("owner_id", json!(4));
+        assert_eq!(
+            engine.evaluate(&foreign),
+            PolicyDecision::Deny { policy_id: None }
+        );
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        assert!(matches!(
+            PolicySet::from_toml("[[policies]]\nid = \"x\"\neffect = \"maybe\""),
+            Err(PolicyError::Parse(_))
+        ));
+        assert!(matches!(
+            PolicySet::from_file("Cargo.lock"),
+            Err(PolicyError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_file() {
+        let path = std::env::temp_dir().join("mairie360_abac_policies.toml");
+        std::fs::write(&path, MUNICIPAL_POLICIES).unwrap();
+        let policies = PolicySet::from_file(&path).unwrap();
+        assert_eq!(policies.policies.len(), 2);
+        std::fs::remove_file(path).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod resolver_tests {
+    use super::*;
+
+    struct CommuneResolver;
+
+    #[async_trait]
+    impl AttributeResolver for CommuneResolver {
+        async fn subject_attributes(
+            &self,
+            user_id: u64,
+        ) -> Result<Map<String, Value>, DatabaseError> {
+            let mut attributes = Map::new();
+            attributes.insert(
+                "commune".to_string(),
+                json!(if user_id == 1 { "lyon" } else { "bron" }),
+            );
+            Ok(attributes)
+        }
+
+        async fn resource_attributes(
+            &self,
+            _resource_name: &str,
+            instance_id: Option<&InstanceId>,
+        ) -> Result<Map<String, Value>, DatabaseError> {
+            let mut attributes = Map::new();
+            if instance_id == Some(&InstanceId::Int(42)) {
+                attributes.insert("commune".to_string(), json!("lyon"));
+            }
+            Ok(attributes)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorize_resolves_attributes() {
+        let engine = PolicyEngine::new(PolicySet::from_toml(MUNICIPAL_POLICIES).unwrap())
+            .with_resolver(Arc::new(CommuneResolver));
+        let instance = InstanceId::Int(42);
+
+        let lyon_agent = AbacRequest::new(1, "requests", "read").with_instance_id(&instance);
+        assert!(engine
+            .authorize(lyon_agent, Some(&instance))
+            .await
+            .unwrap()
+            .is_allowed());
+
+        let bron_agent = AbacRequest::new(2, "requests", "read").with_instance_id(&instance);
+        assert!(!engine
+            .authorize(bron_agent, Some(&instance))
+            .await
+            .unwrap()
+            .is_allowed());
+    }
+}
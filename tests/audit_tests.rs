@@ -0,0 +1,336 @@
+use mairie360_api_lib::audit::{AuditEvent, AuditEventType, MemoryAuditSink};
+use mairie360_api_lib::pool::AppState;
+use std::time::Duration;
+
+async fn state_with_sink(sink: &MemoryAuditSink) -> AppState {
+    AppState::new("".to_string(), "".to_string())
+        .await
+        .with_audit_sink(sink.clone())
+}
+
+/// Attend que la tâche de fond ait écrit `count` événements (file d'audit asynchrone).
+async fn wait_for_events(sink: &MemoryAuditSink, count: usize) -> Vec<AuditEvent> {
+    for _ in 0..100 {
+        if sink.events().len() >= count {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    sink.events()
+}
+
+/**
+ * Construction et représentation canonique des événements.
+ */
+#[cfg(test)]
+mod audit_event_tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_is_stable() {
+        let occurred_at = "2026-03-01T08:30:00.123456789Z".parse().unwrap();
+        let event = AuditEvent::new(AuditEventType::PermissionDenied)
+            .with_occurred_at(occurred_at)
+            .with_user_id(4)
+            .with_request("GET", "/documents/12")
+            .with_target("document", "read");
+
+        assert_eq!(
+            event.canonical(),
+            r#"["permission_denied","2026-03-01T08:30:00.123456Z",4,null,"GET","/documents/12","document","read",null]"#
+        );
+        assert_eq!(event.clone().canonical(), event.canonical());
+    }
+
+    #[test]
+    fn test_event_type_round_trip() {
+        for event_type in [
+            AuditEventType::AuthenticationFailure,
+            AuditEventType::AdminAccess,
+            AuditEventType::AdminAccessDenied,
+            AuditEventType::PermissionDenied,
+            AuditEventType::LoginLocked,
+            AuditEventType::Other("export_requested".to_string()),
+        ] {
+            assert_eq!(AuditEventType::from(event_type.as_str()), event_type);
+        }
+    }
+}
+
+/**
+ * Événements émis automatiquement, collectés par un `MemoryAuditSink`.
+ */
+#[cfg(test)]
+mod audit_hooks_tests {
+    use super::*;
+    use actix_web::middleware::from_fn;
+    use actix_web::{http::StatusCode, test, web, App, HttpMessage, HttpResponse};
+    use mairie360_api_lib::database::instance_id::InstanceIdKind;
+    use mairie360_api_lib::database::queries::QueryError;
+    use mairie360_api_lib::lockout::{guard_login, LockoutPolicy, LoginLockout};
+    use mairie360_api_lib::rebac::{MemoryTupleStore, NamespaceConfig, RebacEngine, RebacSchema};
+    use mairie360_api_lib::security::{
+        access_guard_middleware, AccessCheckConfig, AuthenticatedUser,
+    };
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_access_guard_denial_is_audited() {
+        let sink = MemoryAuditSink::new();
+        let schema = RebacSchema::new()
+            .with_namespace(NamespaceConfig::new("document").with_relation("read"));
+        let store = MemoryTupleStore::with_tuples(["document:plan#read@user:7".parse().unwrap()]);
+        let app_state = state_with_sink(&sink)
+            .await
+            .with_rebac_engine(RebacEngine::new(schema, Arc::new(store)));
+
+        let app = test::init_service(
+            App::new().app_data(web::Data::new(app_state)).service(
+                web::resource("/documents/{slug}")
+                    .app_data(
+                        AccessCheckConfig::new("document", "read")
+                            .with_id_param("slug")
+                            .with_id_kind(InstanceIdKind::Str),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::get().to(HttpResponse::Ok)),
+            ),
+        )
+        .await;
+
+        for user_id in [7, 8] {
+            let req = test::TestRequest::get()
+                .uri("/documents/plan")
+                .peer_addr("10.0.0.1:1234".parse().unwrap())
+                .insert_header(("X-Forwarded-For", "203.0.113.9"))
+                .to_request();
+            req.extensions_mut().insert(AuthenticatedUser::new(user_id));
+            let status = match test::try_call_service(&app, req).await {
+                Ok(res) => res.status(),
+                Err(err) => err.as_response_error().status_code(),
+            };
+            let expected = if user_id == 7 {
+                StatusCode::OK
+            } else {
+                StatusCode::FORBIDDEN
+            };
+            assert_eq!(status, expected);
+        }
+
+        let events = sink.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, AuditEventType::PermissionDenied);
+        assert_eq!(events[0].user_id, Some(8));
+        assert_eq!(events[0].path.as_deref(), Some("/documents/plan"));
+        assert_eq!(events[0].ip.as_deref(), Some("10.0.0.1"));
+        assert_eq!(events[0].resource.as_deref(), Some("document"));
+        assert_eq!(events[0].reason.as_deref(), Some("access_denied"));
+    }
+
+    #[tokio::test]
+    async fn test_login_lockout_is_audited() {
+        let sink = MemoryAuditSink::new();
+        let state = state_with_sink(&sink)
+            .await
+            .with_login_lockout(LoginLockout::new(
+                LockoutPolicy::default().with_free_attempts(0),
+            ));
+
+        for _ in 0..2 {
+            let _ = guard_login(&state, "bob@example.com", Some("10.0.0.2"), async {
+                Err::<(), _>(QueryError::InvalidPassword("bob@example.com".to_string()).into())
+            })
+            .await;
+        }
+
+        let events = sink.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, AuditEventType::LoginLocked);
+        assert_eq!(events[0].ip.as_deref(), Some("10.0.0.2"));
+    }
+}
+
+/**
+ * File bornée des événements émis sans authentification.
+ */
+#[cfg(test)]
+mod audit_queue_tests {
+    use super::*;
+    use async_trait::async_trait;
+    use mairie360_api_lib::audit::{queue_audit_event, AuditSink, AUDIT_QUEUE_CAPACITY};
+    use mairie360_api_lib::database::errors::DatabaseError;
+    use std::sync::Arc;
+    use tokio::sync::Semaphore;
+
+    /// Sink dont chaque écriture attend un jeton, pour simuler une base saturée.
+    struct GatedSink {
+        gate: Arc<Semaphore>,
+        inner: MemoryAuditSink,
+    }
+
+    #[async_trait]
+    impl AuditSink for GatedSink {
+        async fn record(&self, event: &AuditEvent) -> Result<(), DatabaseError> {
+            self.gate.acquire().await.unwrap().forget();
+            self.inner.record(event).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_queued_events_are_written_in_background() {
+        let sink = MemoryAuditSink::new();
+        let state = state_with_sink(&sink).await;
+
+        queue_audit_event(
+            &state,
+            AuditEvent::new(AuditEventType::AuthenticationFailure),
+        );
+        queue_audit_event(&state, AuditEvent::new(AuditEventType::AdminAccessDenied));
+
+        let events = wait_for_events(&sink, 2).await;
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, AuditEventType::AuthenticationFailure);
+        assert_eq!(events[1].event_type, AuditEventType::AdminAccessDenied);
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_drops_events_without_blocking() {
+        let gate = Arc::new(Semaphore::new(0));
+        let sink = MemoryAuditSink::new();
+        let state = AppState::new("".to_string(), "".to_string())
+            .await
+            .with_audit_sink(GatedSink {
+                gate: gate.clone(),
+                inner: sink.clone(),
+            });
+
+        // Le premier événement est pris par la tâche de fond, bloquée sur le sink.
+        queue_audit_event(
+            &state,
+            AuditEvent::new(AuditEventType::AuthenticationFailure),
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        for _ in 0..AUDIT_QUEUE_CAPACITY + 10 {
+            queue_audit_event(
+                &state,
+                AuditEvent::new(AuditEventType::AuthenticationFailure),
+            );
+        }
+
+        gate.add_permits(AUDIT_QUEUE_CAPACITY + 11);
+        let events = wait_for_events(&sink, AUDIT_QUEUE_CAPACITY + 1).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(events.len(), AUDIT_QUEUE_CAPACITY + 1);
+        assert_eq!(sink.events().len(), AUDIT_QUEUE_CAPACITY + 1);
+    }
+}
+
+/**
+ * Écriture chaînée dans la table `audit_log`.
+ */
+#[cfg(test)]
+mod pg_audit_sink_tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test, web, App, HttpResponse};
+    use mairie360_api_lib::audit::{
+        install_audit_log_table, verify_audit_chain, AuditSink, PgAuditSink,
+    };
+    use mairie360_api_lib::security::JwtMiddleware;
+    use mairie360_api_lib::test_setup::queries_setup::get_shared_db;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_chain_and_append_only() {
+        let (_container, url) = get_shared_db().await;
+        let state = AppState::new("".to_string(), url.to_string()).await;
+        let pool = state.db_pool().cloned().unwrap();
+        install_audit_log_table(&pool).await.unwrap();
+
+        let sink = PgAuditSink::new(pool.clone());
+        for user_id in 1..=3 {
+            let event = AuditEvent::new(AuditEventType::AdminAccess).with_user_id(user_id);
+            sink.record(&event).await.unwrap();
+        }
+        assert_eq!(verify_audit_chain(&pool).await.unwrap(), None);
+
+        let update = sqlx::query("UPDATE audit_log SET user_id = 9")
+            .execute(&pool)
+            .await;
+        assert!(update.is_err());
+        let delete = sqlx::query("DELETE FROM audit_log").execute(&pool).await;
+        assert!(delete.is_err());
+
+        // Une ligne insérée hors chaîne est détectée
+        let forged: i64 = sqlx::query_scalar(
+            "INSERT INTO audit_log (occurred_at, event_type, user_id, hash)
+                VALUES (now(), 'admin_access', 9, 'forged') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(verify_audit_chain(&pool).await.unwrap(), Some(forged));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_chain_is_verified_across_pages() {
+        let (_container, url) = get_shared_db().await;
+        let state = AppState::new("".to_string(), url.to_string()).await;
+        let pool = state.db_pool().cloned().unwrap();
+        // Table neuve : les triggers interdisent de vider celle des autres tests
+        sqlx::query("DROP TABLE IF EXISTS audit_log")
+            .execute(&pool)
+            .await
+            .unwrap();
+        install_audit_log_table(&pool).await.unwrap();
+
+        // Plus d'une page de vérification (1000 lignes) : la chaîne continue d'une page à l'autre
+        let sink = PgAuditSink::new(pool.clone());
+        for user_id in 0..1005 {
+            let event = AuditEvent::new(AuditEventType::AdminAccess).with_user_id(user_id);
+            sink.record(&event).await.unwrap();
+        }
+        assert_eq!(verify_audit_chain(&pool).await.unwrap(), None);
+
+        let forged: i64 = sqlx::query_scalar(
+            "INSERT INTO audit_log (occurred_at, event_type, user_id, hash)
+                VALUES (now(), 'admin_access', 9, 'forged') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(verify_audit_chain(&pool).await.unwrap(), Some(forged));
+
+        sqlx::query("DROP TABLE audit_log")
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_jwt_middleware_audits_missing_token() {
+        let (_container, url) = get_shared_db().await;
+        let sink = MemoryAuditSink::new();
+        let state = AppState::new("".to_string(), url.to_string())
+            .await
+            .with_audit_sink(sink.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .wrap(JwtMiddleware)
+                .route("/protected", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let req = test::TestRequest::get().uri("/protected").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let events = wait_for_events(&sink, 1).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, AuditEventType::AuthenticationFailure);
+        assert_eq!(events[0].reason.as_deref(), Some("NoTokenProvided"));
+    }
+}
@@ -0,0 +1,342 @@
+use mairie360_api_lib::cache::{AccessCache, AccessCacheKey, TtlLruCache};
+use mairie360_api_lib::database::instance_id::InstanceId;
+use std::time::Duration;
+
+/**
+ * Tests for the in-process caches used by the access guard.
+ * They cover TTL expiry, LRU eviction and the local invalidation helpers.
+ */
+#[cfg(test)]
+mod ttl_lru_cache_tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_get() {
+        let cache: TtlLruCache<&str, i32> = TtlLruCache::new(10, Duration::from_secs(60));
+        cache.insert("key", 1);
+        assert_eq!(cache.get(&"key"), Some(1));
+        assert_eq!(cache.get(&"missing"), None);
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let cache: TtlLruCache<&str, i32> = TtlLruCache::new(10, Duration::from_millis(20));
+        cache.insert("key", 1);
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(cache.get(&"key"), None, "expired entry should be a miss");
+        assert!(cache.is_empty(), "expired entry should be dropped on read");
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let cache: TtlLruCache<&str, i32> = TtlLruCache::new(2, Duration::from_secs(60));
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.get(&"a");
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"c"), Some(3));
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let cache: TtlLruCache<&str, i32> = TtlLruCache::new(0, Duration::from_secs(60));
+        cache.insert("key", 1);
+        assert_eq!(cache.get(&"key"), None);
+    }
+}
+
+#[cfg(test)]
+mod access_cache_tests {
+    use super::*;
+
+    fn filled_cache() -> AccessCache {
+        let cache = AccessCache::new(100, Duration::from_secs(60));
+        cache.insert_local(
+            AccessCacheKey::new(1, "document", "read", Some(InstanceId::Int(10))),
+            1,
+        );
+        cache.insert_local(AccessCacheKey::new(1, "groups", "read", None), 0);
+        cache.insert_local(
+            AccessCacheKey::new(2, "document", "read", Some(InstanceId::Int(10))),
+            0,
+        );
+        cache.insert_local(
+            AccessCacheKey::new(2, "document", "write", Some(InstanceId::Int(11))),
+            1,
+        );
+        cache
+    }
+
+    #[test]
+    fn test_invalidate_user() {
+        let cache = filled_cache();
+        assert_eq!(cache.invalidate_user_local(1), 2);
+        assert_eq!(cache.local_len(), 2);
+        assert_eq!(
+            cache.get_local(&AccessCacheKey::new(
+                2,
+                "document",
+                "read",
+                Some(InstanceId::Int(10))
+            )),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_invalidate_resource() {
+        let cache = filled_cache();
+        assert_eq!(cache.invalidate_resource_local("document"), 3);
+        assert_eq!(
+            cache.get_local(&AccessCacheKey::new(1, "groups", "read", None)),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_invalidate_instance() {
+        let cache = filled_cache();
+        assert_eq!(
+            cache.invalidate_instance_local("document", &InstanceId::Int(10)),
+            2
+        );
+        assert_eq!(cache.local_len(), 2);
+    }
+
+    #[test]
+    fn test_redis_key_format() {
+        let cache = AccessCache::default().with_redis_prefix("test");
+        assert_eq!(
+            cache.redis_key(&AccessCacheKey::new(3, "users", "read", None)),
+            "test:3:-:users:read:global"
+        );
+        assert_eq!(
+            cache.redis_key(&AccessCacheKey::new(
+                3,
+                "users",
+                "read",
+                Some(InstanceId::Int(7))
+            )),
+            "test:3:-:users:read:7"
+        );
+        assert_eq!(
+            cache.redis_key(
+                &AccessCacheKey::new(3, "users", "read", Some(InstanceId::Int(7)))
+                    .with_tenant(Some("lyon"))
+            ),
+            "test:3:lyon:users:read:7"
+        );
+    }
+
+    #[test]
+    fn test_decisions_are_cached_per_tenant() {
+        let cache = AccessCache::default();
+        let key = |tenant| {
+            AccessCacheKey::new(1, "document", "read", Some(InstanceId::Int(10)))
+                .with_tenant(tenant)
+        };
+        cache.insert_local(key(Some("lyon")), 1);
+        assert_eq!(cache.get_local(&key(Some("lyon"))), Some(1));
+        assert_eq!(cache.get_local(&key(Some("paris"))), None);
+        assert_eq!(cache.get_local(&key(None)), None);
+
+        // L'invalidation d'une instance couvre toutes les communes
+        cache.insert_local(key(Some("paris")), 0);
+        assert_eq!(
+            cache.invalidate_instance_local("document", &InstanceId::Int(10)),
+            2
+        );
+    }
+
+    #[test]
+    fn test_disabled_cache_never_hits() {
+        let cache = AccessCache::disabled().with_redis_tier(true);
+        cache.insert_local(AccessCacheKey::new(1, "users", "read", None), 1);
+        assert_eq!(
+            cache.get_local(&AccessCacheKey::new(1, "users", "read", None)),
+            None
+        );
+        assert!(!cache.is_redis_tier_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_invalidation_reports_unavailable_redis_tier() {
+        use mairie360_api_lib::cache::{invalidate_user_access, InvalidationError};
+        use mairie360_api_lib::pool::{AppState, AppStateError};
+
+        let state = AppState::new("".to_string(), "".to_string())
+            .await
+            .with_access_cache(filled_cache().with_redis_tier(true));
+        let err = invalidate_user_access(&state, 1).await.unwrap_err();
+        assert!(matches!(
+            err,
+            InvalidationError::Unavailable(AppStateError::PoolMissing { pool: "redis" })
+        ));
+        // Le niveau local est purgé malgré l'erreur
+        assert_eq!(state.access_cache().local_len(), 2);
+
+        // Sans niveau Redis, rien à purger côté partagé
+        let state = AppState::new("".to_string(), "".to_string())
+            .await
+            .with_access_cache(filled_cache());
+        assert!(invalidate_user_access(&state, 1).await.is_ok());
+    }
+}
+
+/**
+ * Purge du niveau Redis : seules les clés dont le segment visé correspond sont supprimées.
+ */
+#[cfg(test)]
+mod redis_invalidation_tests {
+    use super::*;
+    use mairie360_api_lib::cache::{
+        invalidate_instance_access, invalidate_resource_access, store_cached_access,
+    };
+    use mairie360_api_lib::pool::AppState;
+    use mairie360_api_lib::test_setup::redis_setup::start_redis_container;
+
+    async fn redis_keys(state: &AppState) -> Vec<String> {
+        let mut conn = state.get_redis_conn().await.unwrap();
+        let mut keys: Vec<String> = deadpool_redis::redis::cmd("KEYS")
+            .arg("*")
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+        keys.sort();
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_resource_invalidation_matches_resource_segment_only() {
+        let (_container, config) = start_redis_container().await;
+        let state = AppState::new(config.url.clone(), "".to_string())
+            .await
+            .with_access_cache(
+                AccessCache::new(100, Duration::from_secs(60)).with_redis_tier(true),
+            );
+        let instance = || Some(InstanceId::Int(10));
+
+        let keys = [
+            AccessCacheKey::new(1, "document", "read", instance()),
+            // Commune, action ou instance portant le nom de la ressource : conservées
+            AccessCacheKey::new(1, "groups", "read", instance()).with_tenant(Some("document")),
+            AccessCacheKey::new(1, "groups", "document", instance()),
+            AccessCacheKey::new(1, "groups", "read", Some(InstanceId::from("document:10"))),
+        ];
+        for key in keys.iter().cloned() {
+            store_cached_access(&state, key, 1).await;
+        }
+
+        invalidate_instance_access(&state, "groups", &InstanceId::Int(10))
+            .await
+            .unwrap();
+        let cache = state.access_cache();
+        let expected: Vec<String> = [&keys[0], &keys[3]]
+            .iter()
+            .map(|key| cache.redis_key(key))
+            .collect();
+        assert_eq!(redis_keys(&state).await, expected);
+
+        invalidate_resource_access(&state, "document")
+            .await
+            .unwrap();
+        assert_eq!(redis_keys(&state).await, vec![cache.redis_key(&keys[3])]);
+    }
+}
+
+#[cfg(test)]
+mod invalidation_event_tests {
+    use mairie360_api_lib::cache::InvalidationEvent;
+
+    #[test]
+    fn test_user_roles_payload() {
+        let event = InvalidationEvent::from_payload(r#"{"table":"user_roles","user_id":4}"#);
+        assert_eq!(event, InvalidationEvent::User(4));
+    }
+
+    #[test]
+    fn test_relation_tuples_payload_clears_everything() {
+        let event = InvalidationEvent::from_payload(r#"{"table":"relation_tuples"}"#);
+        assert_eq!(event, InvalidationEvent::All);
+    }
+
+    #[test]
+    fn test_group_acl_payload_targets_instance() {
+        let event = InvalidationEvent::from_payload(
+            r#"{"table":"access_control","user_id":null,"resource_name":"groups","instance_id":50}"#,
+        );
+        assert_eq!(
+            event,
+            InvalidationEvent::Instance {
+                resource_name: "groups".to_string(),
+                instance_id: 50
+            }
+        );
+    }
+
+    #[test]
+    fn test_unreadable_payload_clears_everything() {
+        assert_eq!(
+            InvalidationEvent::from_payload("not json"),
+            InvalidationEvent::All
+        );
+        assert_eq!(
+            InvalidationEvent::from_payload(r#"{"table":"access_control"}"#),
+            InvalidationEvent::All
+        );
+    }
+}
+
+#[cfg(test)]
+mod invalidation_listener_tests {
+    use super::*;
+    use mairie360_api_lib::cache::{
+        install_invalidation_triggers, spawn_invalidation_listener, InvalidationEvent,
+    };
+    use mairie360_api_lib::pool::AppState;
+    use mairie360_api_lib::test_setup::queries_setup::{get_shared_db, BOB_ID};
+    use serial_test::serial;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_role_change_evicts_user_entries() {
+        let (_container, url) = get_shared_db().await;
+        let state = Arc::new(AppState::new("".to_string(), url.to_string()).await);
+        let pool = state.db_pool().cloned().unwrap();
+        install_invalidation_triggers(&pool).await.unwrap();
+
+        let bob_id = *BOB_ID.get().unwrap() as u64;
+        let key = AccessCacheKey::new(bob_id, "document", "read", Some(InstanceId::Int(1)));
+        state.access_cache().insert_local(key.clone(), 0);
+
+        let mut events = state.subscribe_invalidations();
+        let listener = spawn_invalidation_listener(state.clone());
+        // Laisse le listener exécuter son LISTEN avant de modifier la table.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        sqlx::query(
+            "INSERT INTO user_roles (user_id, role_id) VALUES ($1, 1) ON CONFLICT DO NOTHING",
+        )
+        .bind(bob_id as i32)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("no invalidation received")
+            .unwrap();
+        assert_eq!(event, InvalidationEvent::User(bob_id));
+        assert_eq!(state.access_cache().get_local(&key), None);
+
+        sqlx::query("DELETE FROM user_roles WHERE user_id = $1")
+            .bind(bob_id as i32)
+            .execute(&pool)
+            .await
+            .unwrap();
+        listener.abort();
+    }
+}
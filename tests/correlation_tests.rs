@@ -0,0 +1,206 @@
+use mairie360_api_lib::correlation::{
+    current_request_id, outgoing_headers, with_request_id, RequestId, TraceParent,
+};
+use mairie360_api_lib::database::errors::DatabaseError;
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+/**
+ * Choix de l'identifiant de corrélation et propagation vers les appels sortants.
+ */
+#[cfg(test)]
+mod request_id_tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_valid_request_id() {
+        let request_id = RequestId::from_headers(Some("front-42:abc"), Some(TRACEPARENT));
+        assert_eq!(request_id.as_str(), "front-42:abc");
+        assert_eq!(
+            request_id.get_trace_parent().get_trace_id(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_trace_id_then_generated() {
+        let request_id = RequestId::from_headers(None, Some(TRACEPARENT));
+        assert_eq!(request_id.as_str(), "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        // Un identifiant pouvant polluer les logs est remplacé
+        let too_long = "a".repeat(129);
+        for invalid in ["", "bad\nid", "<script>", too_long.as_str()] {
+            let request_id = RequestId::from_headers(Some(invalid), None);
+            assert_ne!(request_id.as_str(), invalid);
+            assert!(uuid::Uuid::parse_str(request_id.as_str()).is_ok());
+        }
+        assert_ne!(RequestId::generate(), RequestId::generate());
+    }
+
+    #[test]
+    fn test_traceparent_parsing() {
+        let trace_parent = TraceParent::parse(TRACEPARENT).unwrap();
+        assert_eq!(trace_parent.get_parent_id(), "00f067aa0ba902b7");
+        assert!(trace_parent.is_sampled());
+        assert_eq!(trace_parent.to_string(), TRACEPARENT);
+
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(TraceParent::parse(invalid).is_none(), "{}", invalid);
+        }
+        // Une version future peut ajouter des champs
+        assert!(TraceParent::parse(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra"
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn test_propagation_headers_continue_the_trace() {
+        let request_id = RequestId::from_headers(Some("req-1"), Some(TRACEPARENT));
+        let headers = request_id.propagation_headers();
+        assert_eq!(headers[0], ("x-request-id", "req-1".to_string()));
+
+        let (name, value) = &headers[1];
+        assert_eq!(*name, "traceparent");
+        let child = TraceParent::parse(value).unwrap();
+        assert_eq!(child.get_trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_ne!(child.get_parent_id(), "00f067aa0ba902b7");
+    }
+
+    #[tokio::test]
+    async fn test_outgoing_headers_follow_the_current_request() {
+        assert!(current_request_id().is_none());
+        assert!(outgoing_headers().is_empty());
+
+        let request_id = RequestId::from_headers(Some("req-2"), None);
+        let headers = with_request_id(request_id, async {
+            tokio::task::yield_now().await;
+            outgoing_headers()
+        })
+        .await;
+        assert_eq!(headers[0].1, "req-2");
+        assert!(current_request_id().is_none());
+    }
+}
+
+/**
+ * Middleware actix : en-tête de réponse, extracteur et corps d'erreur.
+ */
+#[cfg(test)]
+mod actix_request_id_tests {
+    use super::*;
+    use actix_web::{test, web, App, HttpResponse};
+    use mairie360_api_lib::correlation::RequestIdMiddleware;
+
+    async fn echo(request_id: RequestId) -> HttpResponse {
+        let outgoing = outgoing_headers();
+        assert_eq!(outgoing[0].1, request_id.as_str());
+        HttpResponse::Ok().body(request_id.to_string())
+    }
+
+    async fn failing() -> Result<HttpResponse, DatabaseError> {
+        Err(DatabaseError::Timeout)
+    }
+
+    #[tokio::test]
+    async fn test_request_id_is_propagated() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RequestIdMiddleware::new())
+                .route("/echo", web::get().to(echo))
+                .route("/fail", web::get().to(failing)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/echo")
+            .insert_header(("X-Request-Id", "abc-123"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.headers().get("x-request-id").unwrap(), "abc-123");
+        assert_eq!(test::read_body(resp).await, "abc-123");
+
+        let req = test::TestRequest::get()
+            .uri("/echo")
+            .insert_header(("traceparent", TRACEPARENT))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get("x-request-id").unwrap(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+
+        let req = test::TestRequest::get().uri("/fail").to_request();
+        let resp = test::call_service(&app, req).await;
+        let generated = resp.headers().get("x-request-id").unwrap().clone();
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "timeout");
+        assert_eq!(body["request_id"], generated.to_str().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_header_on_inner_middleware_error() {
+        use actix_web::{http::StatusCode, middleware::from_fn};
+        use mairie360_api_lib::security::access_guard_middleware;
+
+        // Sans `AccessCheckConfig`, le middleware interne renvoie une erreur
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(access_guard_middleware))
+                .wrap(RequestIdMiddleware::new())
+                .route("/echo", web::get().to(echo)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/echo")
+            .insert_header(("X-Request-Id", "inner-err"))
+            .to_request();
+        let err = test::try_call_service(&app, req).await.unwrap_err();
+        let resp = err.error_response();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(resp.headers().get("x-request-id").unwrap(), "inner-err");
+    }
+}
+
+/**
+ * Middleware axum : mêmes garanties que la version actix.
+ */
+#[cfg(test)]
+mod axum_request_id_tests {
+    use super::*;
+    use axum::{body::Body, http::Request, middleware, routing::get, Router};
+    use mairie360_api_lib::correlation::request_id_middleware;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_request_id_in_header_and_error_body() {
+        let app = Router::new()
+            .route(
+                "/fail",
+                get(|| async { Err::<(), _>(DatabaseError::ConnectionClosed) }),
+            )
+            .layer(middleware::from_fn(request_id_middleware));
+
+        let req = Request::get("/fail")
+            .header("x-request-id", "axum-1")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.headers().get("x-request-id").unwrap(), "axum-1");
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "service_unavailable");
+        assert_eq!(body["request_id"], "axum-1");
+    }
+}
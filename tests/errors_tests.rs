@@ -0,0 +1,91 @@
+use mairie360_api_lib::database::errors::DatabaseError;
+use mairie360_api_lib::database::http_errors::ErrorBody;
+use mairie360_api_lib::database::queries::QueryError;
+
+/**
+ * Tests for the HTTP mapping of database errors.
+ * They check the status codes chosen for each variant and that the raw
+ * driver message never ends up in the body sent to the client.
+ */
+#[cfg(test)]
+mod error_body_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_results_is_not_found() {
+        let body = ErrorBody::from(&DatabaseError::Query(QueryError::NoResults));
+        assert_eq!(body.get_status(), 404);
+        assert_eq!(body.get_error(), "not_found");
+    }
+
+    #[test]
+    fn test_constraint_violation_is_conflict() {
+        let body = ErrorBody::from(&QueryError::ConstraintViolation(
+            "duplicate key value violates unique constraint \"users_email_key\"".to_string(),
+        ));
+        assert_eq!(body.get_status(), 409);
+        assert!(!body.get_message().contains("users_email_key"));
+    }
+
+    #[test]
+    fn test_timeout_is_service_unavailable() {
+        let body = ErrorBody::from(&DatabaseError::Timeout);
+        assert_eq!(body.get_status(), 503);
+    }
+
+    #[test]
+    fn test_credentials_errors_are_indistinguishable() {
+        let unknown = ErrorBody::from(&QueryError::EmailNotFound("a@b.c".to_string()));
+        let wrong = ErrorBody::from(&QueryError::InvalidPassword("a@b.c".to_string()));
+        assert_eq!(unknown, wrong);
+    }
+}
+
+#[cfg(test)]
+mod actix_response_tests {
+    use super::*;
+    use actix_web::{body::MessageBody, http::StatusCode, ResponseError};
+
+    #[test]
+    fn test_actix_response_hides_driver_message() {
+        let err = DatabaseError::Query(QueryError::ExecutionFailed(
+            "relation \"secret_table\" does not exist".to_string(),
+        ));
+        let response = err.error_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = response.into_body().try_into_bytes().unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!body.contains("secret_table"), "body leaked: {}", body);
+        assert!(body.contains("internal_error"));
+    }
+
+    #[test]
+    fn test_actix_status_code() {
+        assert_eq!(
+            QueryError::NoResults.status_code(),
+            StatusCode::NOT_FOUND,
+            "NoResults should map to 404"
+        );
+        assert_eq!(
+            DatabaseError::Timeout.status_code(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
+
+#[cfg(test)]
+mod axum_response_tests {
+    use super::*;
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    #[test]
+    fn test_axum_response_status() {
+        let response =
+            DatabaseError::Query(QueryError::ConstraintViolation("x".to_string())).into_response();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = DatabaseError::ConnectionClosed.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
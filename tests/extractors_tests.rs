@@ -0,0 +1,339 @@
+use mairie360_api_lib::pool::AppState;
+use mairie360_api_lib::scope_requirement;
+use mairie360_api_lib::security::{
+    AdminUser, AuthenticatedUser, CurrentTenant, MaybeAuthenticated, ScopeRequirement,
+    TrustedProxies, WithScope,
+};
+use mairie360_api_lib::tenant::{TenantConfig, TenantScope, TenantSource};
+use std::sync::Arc;
+
+scope_requirement!(DocumentsRead, "documents:read");
+
+fn user_with_scope(id: u64, scope: &str) -> AuthenticatedUser {
+    AuthenticatedUser {
+        scopes: vec![scope.to_string()],
+        ..AuthenticatedUser::new(id)
+    }
+}
+
+// Le cache utilisateur pré-rempli évite d'interroger la base pour `is_admin`.
+async fn state_with_admin(admin_id: u64) -> AppState {
+    let state = AppState::new("".to_string(), "".to_string()).await;
+    state.user_cache().insert_is_admin(admin_id, true);
+    state.user_cache().insert_is_admin(admin_id + 1, false);
+    state
+}
+
+fn user_of(id: u64, tenant: &str) -> AuthenticatedUser {
+    AuthenticatedUser {
+        tenant: Some(tenant.to_string()),
+        ..AuthenticatedUser::new(id)
+    }
+}
+
+async fn state_with_subdomains() -> AppState {
+    AppState::new("".to_string(), "".to_string())
+        .await
+        .with_tenant_config(TenantConfig::new(vec![
+            TenantSource::Subdomain {
+                base_domain: "mairie360.fr".to_string(),
+            },
+            TenantSource::Claim,
+        ]))
+}
+
+/**
+ * Extracteurs actix : chaque handler renvoie ce qu'il a reçu.
+ */
+#[cfg(test)]
+mod actix_extractors {
+    use super::*;
+    use actix_web::{http::StatusCode, test, web, App, HttpMessage, HttpResponse};
+
+    async fn maybe(user: MaybeAuthenticated) -> HttpResponse {
+        match user.user() {
+            Some(user) => HttpResponse::Ok().body(format!("user {}", user.id)),
+            None => HttpResponse::Ok().body("anonymous"),
+        }
+    }
+
+    async fn optional(user: Option<AuthenticatedUser>) -> HttpResponse {
+        HttpResponse::Ok().body(format!("{:?}", user.map(|u| u.id)))
+    }
+
+    async fn admin(user: AdminUser) -> HttpResponse {
+        HttpResponse::Ok().body(format!("admin {}", user.id))
+    }
+
+    async fn scoped(user: WithScope<DocumentsRead>) -> HttpResponse {
+        HttpResponse::Ok().body(format!("scoped {}", user.user().id))
+    }
+
+    async fn tenant(tenant: CurrentTenant) -> HttpResponse {
+        HttpResponse::Ok().body(tenant.scope().to_string())
+    }
+
+    async fn call(path: &str, user: Option<AuthenticatedUser>) -> (StatusCode, String) {
+        let app_state = web::Data::new(state_with_admin(7).await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .route("/maybe", web::get().to(maybe))
+                .route("/optional", web::get().to(optional))
+                .route("/admin", web::get().to(admin))
+                .route("/scoped", web::get().to(scoped)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri(path).to_request();
+        if let Some(user) = user {
+            req.extensions_mut().insert(user);
+        }
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status();
+        let body = test::read_body(resp).await;
+        (status, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_maybe_authenticated() {
+        assert_eq!(
+            call("/maybe", None).await,
+            (StatusCode::OK, "anonymous".to_string())
+        );
+        assert_eq!(
+            call("/maybe", Some(AuthenticatedUser::new(3))).await,
+            (StatusCode::OK, "user 3".to_string())
+        );
+        assert_eq!(
+            call("/optional", None).await,
+            (StatusCode::OK, "None".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_maybe_authenticated_behind_jwt_middleware() {
+        use mairie360_api_lib::security::JwtMiddleware;
+
+        let app_state = AppState::new("".to_string(), "".to_string())
+            .await
+            .with_optional_auth_path("/maybe");
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(app_state))
+                .wrap(JwtMiddleware)
+                .route("/maybe", web::get().to(maybe)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/maybe").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, "anonymous");
+
+        // Un token présenté est vérifié : sans base, la requête n'aboutit pas
+        let req = test::TestRequest::get()
+            .uri("/maybe")
+            .insert_header(("Authorization", "Bearer not-a-jwt"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_ne!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_admin_user() {
+        assert_eq!(call("/admin", None).await.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            call("/admin", Some(AuthenticatedUser::new(8))).await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call("/admin", Some(AuthenticatedUser::new(7))).await,
+            (StatusCode::OK, "admin 7".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_scope() {
+        assert_eq!(call("/scoped", None).await.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            call("/scoped", Some(user_with_scope(3, "documents:write")))
+                .await
+                .0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            call("/scoped", Some(user_with_scope(3, DocumentsRead::SCOPE))).await,
+            (StatusCode::OK, "scoped 3".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_current_tenant() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(state_with_subdomains().await))
+                .route("/tenant", web::get().to(tenant)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/tenant")
+            .insert_header(("Host", "lyon.mairie360.fr"))
+            .to_request();
+        req.extensions_mut().insert(user_of(3, "lyon"));
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, "lyon");
+
+        let req = test::TestRequest::get()
+            .uri("/tenant")
+            .insert_header(("Host", "paris.mairie360.fr"))
+            .to_request();
+        req.extensions_mut().insert(user_of(3, "lyon"));
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_current_tenant_forwarded_host_needs_trusted_proxy() {
+        let forwarded_request = || {
+            let req = test::TestRequest::get()
+                .uri("/tenant")
+                .peer_addr("10.0.0.1:1234".parse().unwrap())
+                .insert_header(("Host", "lyon.mairie360.fr"))
+                .insert_header(("X-Forwarded-Host", "paris.mairie360.fr"))
+                .to_request();
+            req.extensions_mut().insert(user_of(3, "lyon"));
+            req
+        };
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(state_with_subdomains().await))
+                .route("/tenant", web::get().to(tenant)),
+        )
+        .await;
+        let resp = test::call_service(&app, forwarded_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, "lyon");
+
+        let state = state_with_subdomains()
+            .await
+            .with_trusted_proxies(TrustedProxies::new(["10.0.0.1".parse().unwrap()]));
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(state))
+                .route("/tenant", web::get().to(tenant)),
+        )
+        .await;
+        let resp = test::call_service(&app, forwarded_request()).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
+
+/**
+ * Extracteurs axum, appelés directement sur les `Parts` d'une requête.
+ */
+#[cfg(test)]
+mod axum_extractors {
+    use super::*;
+    use axum::extract::FromRequestParts;
+    use axum::http::{request::Parts, Request, StatusCode};
+
+    fn parts(user: Option<AuthenticatedUser>) -> Parts {
+        let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+        if let Some(user) = user {
+            parts.extensions.insert(user);
+        }
+        parts
+    }
+
+    #[tokio::test]
+    async fn test_maybe_authenticated() {
+        let state = ();
+        let anonymous = MaybeAuthenticated::from_request_parts(&mut parts(None), &state)
+            .await
+            .unwrap();
+        assert!(!anonymous.is_authenticated());
+
+        let logged = MaybeAuthenticated::from_request_parts(
+            &mut parts(Some(AuthenticatedUser::new(3))),
+            &state,
+        )
+        .await
+        .unwrap();
+        assert_eq!(logged.user().map(|u| u.id), Some(3));
+
+        let optional = <Option<AuthenticatedUser>>::from_request_parts(&mut parts(None), &state)
+            .await
+            .unwrap();
+        assert_eq!(optional, None);
+    }
+
+    #[tokio::test]
+    async fn test_admin_user() {
+        let state = Arc::new(state_with_admin(7).await);
+
+        let rejection = AdminUser::from_request_parts(&mut parts(None), &state)
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.0, StatusCode::UNAUTHORIZED);
+
+        let rejection =
+            AdminUser::from_request_parts(&mut parts(Some(AuthenticatedUser::new(8))), &state)
+                .await
+                .unwrap_err();
+        assert_eq!(rejection.0, StatusCode::FORBIDDEN);
+
+        let admin =
+            AdminUser::from_request_parts(&mut parts(Some(AuthenticatedUser::new(7))), &state)
+                .await
+                .unwrap();
+        assert_eq!(admin.id, 7);
+    }
+
+    #[tokio::test]
+    async fn test_with_scope() {
+        let state = ();
+        let rejection = WithScope::<DocumentsRead>::from_request_parts(
+            &mut parts(Some(AuthenticatedUser::new(3))),
+            &state,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(rejection.0, StatusCode::FORBIDDEN);
+
+        let scoped = WithScope::<DocumentsRead>::from_request_parts(
+            &mut parts(Some(user_with_scope(3, "documents:read"))),
+            &state,
+        )
+        .await
+        .ok()
+        .unwrap();
+        assert_eq!(scoped.user().id, 3);
+    }
+
+    #[tokio::test]
+    async fn test_current_tenant() {
+        let state = Arc::new(state_with_subdomains().await);
+
+        let mut lyon = parts(Some(user_of(3, "lyon")));
+        lyon.headers
+            .insert("host", "lyon.mairie360.fr".parse().unwrap());
+        let tenant = CurrentTenant::from_request_parts(&mut lyon, &state)
+            .await
+            .unwrap();
+        assert_eq!(tenant.into_inner(), TenantScope::Tenant("lyon".to_string()));
+
+        let mut paris = parts(Some(user_of(3, "lyon")));
+        paris
+            .headers
+            .insert("host", "paris.mairie360.fr".parse().unwrap());
+        let rejection = CurrentTenant::from_request_parts(&mut paris, &state)
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.0, StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,205 @@
+use mairie360_api_lib::database::errors::DatabaseError;
+use mairie360_api_lib::database::queries::QueryError;
+use mairie360_api_lib::lockout::{
+    guard_login, unlock_account, unlock_ip, LockoutPolicy, LoginError, LoginLockout,
+};
+use mairie360_api_lib::pool::AppState;
+use std::time::Duration;
+
+const ACCOUNT: &str = "alice@example.com";
+
+fn wrong_password() -> Result<u64, DatabaseError> {
+    Err(QueryError::InvalidPassword(ACCOUNT.to_string()).into())
+}
+
+/// La tentative n'est pas bloquée : une connexion réussie passe.
+async fn is_allowed(state: &AppState, account: &str, ip: Option<&str>) -> bool {
+    guard_login(state, account, ip, async { Ok(0) })
+        .await
+        .is_ok()
+}
+
+// Sans Redis, les compteurs en mémoire de l'`AppState` sont utilisés.
+async fn state_with_policy(policy: LockoutPolicy) -> AppState {
+    AppState::new("".to_string(), "".to_string())
+        .await
+        .with_login_lockout(LoginLockout::new(policy))
+}
+
+/**
+ * Délais progressifs et verrouillage définis par la politique.
+ */
+#[cfg(test)]
+mod lockout_policy_tests {
+    use super::*;
+
+    #[test]
+    fn test_progressive_delays() {
+        let policy = LockoutPolicy::default()
+            .with_free_attempts(2)
+            .with_delays(Duration::from_secs(1), Duration::from_secs(5))
+            .with_lockout(8, Duration::from_secs(600));
+
+        let delays: Vec<Option<Duration>> = (1..=8).map(|n| policy.account_delay(n)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                None,
+                None,
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(5)),
+                Some(Duration::from_secs(5)),
+                Some(Duration::from_secs(600)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ip_is_only_locked_out() {
+        let policy = LockoutPolicy::default().with_ip_max_failures(3);
+        assert_eq!(policy.ip_delay(2), None);
+        assert_eq!(policy.ip_delay(3), Some(policy.lockout_duration));
+    }
+}
+
+/**
+ * `guard_login` autour d'une vérification d'identifiants simulée.
+ */
+#[cfg(test)]
+mod guard_login_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_account_is_locked_then_unlocked() {
+        let state = state_with_policy(
+            LockoutPolicy::default()
+                .with_free_attempts(2)
+                .with_delays(Duration::from_secs(60), Duration::from_secs(60)),
+        )
+        .await;
+
+        for _ in 0..3 {
+            let result = guard_login(&state, ACCOUNT, None, async { wrong_password() }).await;
+            assert!(matches!(result, Err(LoginError::Credentials(_))));
+        }
+
+        // Bloqué : même un bon mot de passe n'est pas vérifié
+        let result = guard_login::<u64, _>(&state, "Alice@Example.com ", None, async {
+            panic!("credentials must not be verified while locked")
+        })
+        .await;
+        let retry_after = result.unwrap_err().get_retry_after().unwrap();
+        assert!(retry_after > Duration::from_secs(55));
+
+        unlock_account(&state, ACCOUNT).await;
+        let result = guard_login(&state, ACCOUNT, None, async { Ok(7) }).await;
+        assert_eq!(result, Ok(7));
+    }
+
+    #[tokio::test]
+    async fn test_success_resets_failures() {
+        let state = state_with_policy(LockoutPolicy::default().with_free_attempts(2)).await;
+
+        for _ in 0..2 {
+            let _ = guard_login(&state, ACCOUNT, None, async { wrong_password() }).await;
+        }
+        assert_eq!(
+            guard_login(&state, ACCOUNT, None, async { Ok(1) }).await,
+            Ok(1)
+        );
+        for _ in 0..2 {
+            let _ = guard_login(&state, ACCOUNT, None, async { wrong_password() }).await;
+        }
+        assert!(is_allowed(&state, ACCOUNT, None).await);
+    }
+
+    #[tokio::test]
+    async fn test_ip_lockout_and_uncounted_errors() {
+        let state = state_with_policy(LockoutPolicy::default().with_ip_max_failures(3)).await;
+        let ip = Some("10.0.0.1");
+
+        // Une base indisponible ne compte pas comme un échec
+        for _ in 0..5 {
+            let _ = guard_login(&state, ACCOUNT, ip, async {
+                Err::<u64, _>(DatabaseError::Timeout)
+            })
+            .await;
+        }
+        assert!(is_allowed(&state, ACCOUNT, ip).await);
+
+        for account in ["a@example.com", "b@example.com", "c@example.com"] {
+            let _ = guard_login(&state, account, ip, async { wrong_password() }).await;
+        }
+        assert!(!is_allowed(&state, "d@example.com", ip).await);
+        assert!(is_allowed(&state, "d@example.com", None).await);
+
+        unlock_ip(&state, "10.0.0.1").await;
+        assert!(is_allowed(&state, "d@example.com", ip).await);
+    }
+
+    /// Tentatives lancées en même temps : combien de vérifications ont été exécutées.
+    async fn concurrent_verifications(state: &AppState, attempts: usize) -> usize {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let verified = AtomicUsize::new(0);
+        let attempt = || {
+            guard_login(state, ACCOUNT, None, async {
+                verified.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                wrong_password()
+            })
+        };
+        futures_util::future::join_all((0..attempts).map(|_| attempt())).await;
+        verified.load(Ordering::SeqCst)
+    }
+
+    fn lock_after_three() -> LockoutPolicy {
+        LockoutPolicy::default()
+            .with_free_attempts(2)
+            .with_delays(Duration::from_secs(60), Duration::from_secs(60))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_attempts_cannot_bypass_lockout() {
+        let state = state_with_policy(lock_after_three()).await;
+        assert_eq!(concurrent_verifications(&state, 10).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_attempts_cannot_bypass_redis_lockout() {
+        use mairie360_api_lib::test_setup::redis_setup::start_redis_container;
+
+        let (_container, config) = start_redis_container().await;
+        let state = AppState::new(config.url.clone(), "".to_string())
+            .await
+            .with_login_lockout(LoginLockout::new(lock_after_three()));
+        assert_eq!(concurrent_verifications(&state, 10).await, 3);
+
+        // Une erreur non comptée libère la tentative réservée
+        unlock_account(&state, ACCOUNT).await;
+        for _ in 0..5 {
+            let _ = guard_login(&state, ACCOUNT, None, async {
+                Err::<u64, _>(DatabaseError::Timeout)
+            })
+            .await;
+        }
+        assert!(is_allowed(&state, ACCOUNT, None).await);
+    }
+
+    #[tokio::test]
+    async fn test_locked_response() {
+        use actix_web::{http::StatusCode, ResponseError};
+
+        let error = LoginError::Locked {
+            retry_after: Duration::from_secs(42),
+        };
+        let response = error.error_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get("retry-after").unwrap(), "42");
+
+        let error = LoginError::Credentials(QueryError::EmailNotFound(ACCOUNT.to_string()).into());
+        assert_eq!(error.status_code(), StatusCode::UNAUTHORIZED);
+    }
+}
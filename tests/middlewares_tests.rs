@@ -19,7 +19,10 @@ mod auth_middleware {
 
     use super::*;
     use actix_web::{http::StatusCode, test, web, App, HttpResponse};
-    use mairie360_api_lib::{jwt_manager::generate_jwt, security::JwtMiddleware};
+    use mairie360_api_lib::{
+        jwt_manager::{generate_jwt, generate_jwt_with_claims, Claims},
+        security::{AuthMethod, AuthenticatedUser, JwtMiddleware, MaybeAuthenticated},
+    };
 
     // Route de test simple protégée par le middleware
     async fn index() -> HttpResponse {
@@ -104,6 +107,148 @@ mod auth_middleware {
         );
     }
 
+    #[tokio::test]
+    async fn test_middleware_exposes_token_claims() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+        let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+            .get()
+            .unwrap();
+
+        let claims = Claims::new(alice_id.to_string(), 0)
+            .with_session_id("session-1")
+            .with_roles(&["agent"])
+            .with_scopes(&["documents:read"])
+            .with_tenant("lyon")
+            .with_auth_method("password");
+        let token = generate_jwt_with_claims(claims).unwrap();
+
+        async fn whoami(user: AuthenticatedUser) -> HttpResponse {
+            HttpResponse::Ok().body(format!(
+                "{}:{}:{}:{}:{:?}",
+                user.id,
+                user.session_id.as_deref().unwrap_or_default(),
+                user.has_role("agent"),
+                user.has_scope("documents:read"),
+                user.auth_method
+            ))
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state.clone())
+                .wrap(JwtMiddleware)
+                .route("/protected", web::get().to(whoami)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/protected")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = test::read_body(resp).await;
+        assert_eq!(body, format!("{}:session-1:true:true:Password", alice_id));
+    }
+
+    #[tokio::test]
+    async fn test_optional_auth_path_attaches_user_when_token_is_valid() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(
+            AppState::new("".to_string(), url.to_string())
+                .await
+                .with_optional_auth_path("/feed"),
+        );
+        let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+            .get()
+            .unwrap();
+        let token = generate_jwt(&alice_id.to_string()).unwrap();
+
+        async fn feed(user: MaybeAuthenticated) -> HttpResponse {
+            match user.user() {
+                Some(user) => HttpResponse::Ok().body(format!("user {}", user.id)),
+                None => HttpResponse::Ok().body("anonymous"),
+            }
+        }
+
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state.clone())
+                .wrap(JwtMiddleware)
+                .route("/feed", web::get().to(feed))
+                .route("/protected", web::get().to(index)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/feed").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, "anonymous");
+
+        let req = test::TestRequest::get()
+            .uri("/feed")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(test::read_body(resp).await, format!("user {}", alice_id));
+
+        let req = test::TestRequest::get()
+            .uri("/feed")
+            .insert_header(("Authorization", "Bearer not-a-jwt"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::get().uri("/protected").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_non_numeric_subject_returns_401() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+        let token = generate_jwt("alice").unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state.clone())
+                .wrap(JwtMiddleware)
+                .route("/protected", web::get().to(index)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/protected")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_principal_from_claims() {
+        let claims = Claims::new("42".to_string(), 0)
+            .with_token_id("jti-1")
+            .with_roles(&["agent", "maire"]);
+        let user = AuthenticatedUser::from_claims(&claims).unwrap();
+        assert_eq!(user.id, 42);
+        assert_eq!(user.token_id.as_deref(), Some("jti-1"));
+        assert!(user.has_any_role(&["maire"]));
+        assert!(!user.has_role("admin"));
+        assert_eq!(user.auth_method, AuthMethod::Unspecified);
+
+        assert_eq!(
+            AuthenticatedUser::from_claims(&Claims::new("abc".to_string(), 0)),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_middleware_expired_token_returns_401() {
         let (_container, url) = get_shared_db().await;
@@ -185,6 +330,7 @@ mod access_middleware {
                         resource_name: "users", // Nom de la table/ressource
                         action: "read",
                         id_param_pattern: Some("user_id"),
+                        ..Default::default()
                     })
                     .wrap(from_fn(access_guard_middleware))
                     .route(web::get().to(fake_handler)),
@@ -198,9 +344,8 @@ mod access_middleware {
             .to_request();
 
         // On injecte l'utilisateur authentifié (simule le JwtMiddleware)
-        req.extensions_mut().insert(AuthenticatedUser {
-            id: alice_id as u64,
-        });
+        req.extensions_mut()
+            .insert(AuthenticatedUser::new(alice_id as u64));
 
         let resp = test::call_service(&app, req).await;
 
@@ -227,6 +372,7 @@ mod access_middleware {
                         resource_name: "users",
                         action: "write",
                         id_param_pattern: Some("user_id"),
+                        ..Default::default()
                     })
                     .wrap(from_fn(access_guard_middleware))
                     .route(web::get().to(fake_handler)),
@@ -239,9 +385,8 @@ mod access_middleware {
             .uri(&format!("/users/{}/secret", bob_id))
             .to_request();
 
-        req.extensions_mut().insert(AuthenticatedUser {
-            id: alice_id as u64,
-        });
+        req.extensions_mut()
+            .insert(AuthenticatedUser::new(alice_id as u64));
 
         let resp = test::try_call_service(&app, req).await;
 
@@ -268,6 +413,7 @@ mod access_middleware {
                         resource_name: "items",
                         action: "read",
                         id_param_pattern: Some("id"),
+                        ..Default::default()
                     })
                     .wrap(from_fn(access_guard_middleware))
                     .route(web::get().to(fake_handler)),
@@ -279,7 +425,7 @@ mod access_middleware {
         let req = test::TestRequest::get()
             .uri("/items/not-an-integer")
             .to_request();
-        req.extensions_mut().insert(AuthenticatedUser { id: 1 });
+        req.extensions_mut().insert(AuthenticatedUser::new(1));
 
         let resp = test::try_call_service(&app, req).await;
 
@@ -312,6 +458,7 @@ mod access_middleware {
                         resource_name: "users",
                         action: "read", // La fonction SQL cherchera 'read_all'
                         id_param_pattern: None,
+                        ..Default::default()
                     })
                     .wrap(from_fn(access_guard_middleware))
                     .route("/all-users", web::get().to(fake_handler)),
@@ -326,7 +473,7 @@ mod access_middleware {
         // On simule un utilisateur (ID 1)
         // Note: Pour que ce test réussisse, Alice doit avoir le droit 'read_all'
         // sur 'users' dans ta base de données de test.
-        req.extensions_mut().insert(AuthenticatedUser { id: 1 });
+        req.extensions_mut().insert(AuthenticatedUser::new(1));
 
         let resp = test::call_service(&app, req).await;
 
@@ -411,3 +558,513 @@ mod admin_path_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod require_access_extractor {
+    use super::*;
+    use actix_web::{http::StatusCode, test, web, App, HttpMessage, HttpResponse};
+    use mairie360_api_lib::access_requirement;
+    use mairie360_api_lib::security::{
+        AccessConfigError, AccessRequirements, AuthenticatedUser, RequireAccess,
+    };
+
+    access_requirement!(ReadUser, "users", "read", "user_id");
+    access_requirement!(WriteUser, "users", "write", "user_id");
+    access_requirement!(ReadGhost, "ghost_resource", "read");
+
+    async fn read_handler(access: RequireAccess<ReadUser>) -> HttpResponse {
+        HttpResponse::Ok().body(access.user().id.to_string())
+    }
+
+    async fn write_handler(_access: RequireAccess<WriteUser>) -> HttpResponse {
+        HttpResponse::Ok().body("Granted")
+    }
+
+    #[tokio::test]
+    async fn test_require_access_granted_owner() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+        let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+            .get()
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state.clone())
+                .route("/users/{user_id}", web::get().to(read_handler)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/users/{}", alice_id))
+            .to_request();
+        req.extensions_mut()
+            .insert(AuthenticatedUser::new(alice_id as u64));
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = test::read_body(resp).await;
+        assert_eq!(body, alice_id.to_string());
+    }
+
+    #[tokio::test]
+    async fn test_require_access_forbidden_for_other_user() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+        let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+            .get()
+            .unwrap();
+        let bob_id = *mairie360_api_lib::test_setup::queries_setup::BOB_ID
+            .get()
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state.clone())
+                .route("/users/{user_id}", web::post().to(write_handler)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/users/{}", alice_id))
+            .to_request();
+        req.extensions_mut()
+            .insert(AuthenticatedUser::new(bob_id as u64));
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_require_access_without_user_returns_401() {
+        let app =
+            test::init_service(App::new().route("/users/{user_id}", web::get().to(read_handler)))
+                .await;
+
+        let req = test::TestRequest::get().uri("/users/1").to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_requirements_validation_rejects_unknown_resource() {
+        let (_container, url) = get_shared_db().await;
+        let app_state = AppState::new("".to_string(), url.to_string()).await;
+
+        let result = AccessRequirements::new()
+            .with::<ReadUser>()
+            .with::<ReadGhost>()
+            .validate(&app_state)
+            .await;
+
+        assert_eq!(
+            result,
+            Err(AccessConfigError::UnknownResource(
+                "ghost_resource".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_declared_requirements_are_validated_without_listing() {
+        let (_container, url) = get_shared_db().await;
+        let app_state = AppState::new("".to_string(), url.to_string()).await;
+
+        // `ReadGhost` n'est pas listé : `access_requirement!` l'a recensé
+        let result = AccessRequirements::new().validate(&app_state).await;
+
+        assert_eq!(
+            result,
+            Err(AccessConfigError::UnknownResource(
+                "ghost_resource".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_id_param_missing_from_route_is_rejected() {
+        let app_state = web::Data::new(AppState::new("".to_string(), "".to_string()).await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .route("/users/{id}", web::get().to(read_handler)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/users/1").to_request();
+        req.extensions_mut().insert(AuthenticatedUser::new(1));
+
+        // Sans ce contrôle, `user_id` absent donnerait une vérification globale
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
+
+#[cfg(test)]
+mod access_instance_ids {
+    use super::*;
+    use actix_web::middleware::from_fn;
+    use actix_web::{http::StatusCode, test, web, App, HttpMessage, HttpResponse};
+    use mairie360_api_lib::database::instance_id::InstanceIdKind;
+    use mairie360_api_lib::security::{
+        access_guard_middleware, AccessCheckConfig, AuthenticatedUser, InstanceIdSource,
+    };
+
+    async fn echo_handler(body: web::Json<serde_json::Value>) -> HttpResponse {
+        HttpResponse::Ok().json(body.into_inner())
+    }
+
+    async fn fake_handler() -> HttpResponse {
+        HttpResponse::Ok().body("Granted")
+    }
+
+    fn ids() -> (i32, i32) {
+        let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+            .get()
+            .unwrap();
+        let bob_id = *mairie360_api_lib::test_setup::queries_setup::BOB_ID
+            .get()
+            .unwrap();
+        (alice_id, bob_id)
+    }
+
+    #[tokio::test]
+    async fn test_access_invalid_uuid_returns_400() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+
+        let app = test::init_service(
+            App::new().app_data(app_state.clone()).service(
+                web::resource("/documents/{document_id}")
+                    .app_data(
+                        AccessCheckConfig::new("document", "read")
+                            .with_id_param("document_id")
+                            .with_id_kind(InstanceIdKind::Uuid),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::get().to(fake_handler)),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/documents/not-a-uuid")
+            .to_request();
+        req.extensions_mut().insert(AuthenticatedUser::new(1));
+
+        let resp = test::try_call_service(&app, req).await;
+        match resp {
+            Ok(res) => assert_eq!(res.status(), StatusCode::BAD_REQUEST),
+            Err(err) => assert_eq!(
+                err.as_response_error().status_code(),
+                StatusCode::BAD_REQUEST
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_access_json_body_id_keeps_body_for_handler() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+        let (alice_id, _) = ids();
+
+        let app = test::init_service(
+            App::new().app_data(app_state.clone()).service(
+                web::resource("/users/data")
+                    .app_data(
+                        AccessCheckConfig::new("users", "read")
+                            .with_id_param("user_id")
+                            .with_id_source(InstanceIdSource::Json),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::post().to(echo_handler)),
+            ),
+        )
+        .await;
+
+        let payload = serde_json::json!({ "user_id": alice_id, "note": "hello" });
+        let req = test::TestRequest::post()
+            .uri("/users/data")
+            .set_json(&payload)
+            .to_request();
+        req.extensions_mut()
+            .insert(AuthenticatedUser::new(alice_id as u64));
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body, payload);
+    }
+
+    #[tokio::test]
+    async fn test_access_query_param_forbidden_for_other_user() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+        let (alice_id, bob_id) = ids();
+
+        let app = test::init_service(
+            App::new().app_data(app_state.clone()).service(
+                web::resource("/users")
+                    .app_data(
+                        AccessCheckConfig::new("users", "write")
+                            .with_id_param("user_id")
+                            .with_id_source(InstanceIdSource::Query),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::get().to(fake_handler)),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/users?user_id={}", alice_id))
+            .to_request();
+        req.extensions_mut()
+            .insert(AuthenticatedUser::new(bob_id as u64));
+
+        let resp = test::try_call_service(&app, req).await;
+        match resp {
+            Ok(res) => assert_eq!(res.status(), StatusCode::FORBIDDEN),
+            Err(err) => assert_eq!(err.as_response_error().status_code(), StatusCode::FORBIDDEN),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_access_parent_check_denies_nested_route() {
+        setup();
+        let (_container, url) = get_shared_db().await;
+        let app_state = web::Data::new(AppState::new("".to_string(), url.to_string()).await);
+        let (alice_id, bob_id) = ids();
+
+        // Bob peut lire son propre profil, mais pas écrire sur celui d'Alice (parent).
+        let app = test::init_service(
+            App::new().app_data(app_state.clone()).service(
+                web::resource("/users/{owner_id}/delegates/{user_id}")
+                    .app_data(
+                        AccessCheckConfig::new("users", "read")
+                            .with_id_param("user_id")
+                            .with_parent(
+                                AccessCheckConfig::new("users", "write").with_id_param("owner_id"),
+                            ),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::get().to(fake_handler)),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/users/{}/delegates/{}", alice_id, bob_id))
+            .to_request();
+        req.extensions_mut()
+            .insert(AuthenticatedUser::new(bob_id as u64));
+
+        let resp = test::try_call_service(&app, req).await;
+        match resp {
+            Ok(res) => assert_eq!(res.status(), StatusCode::FORBIDDEN),
+            Err(err) => assert_eq!(err.as_response_error().status_code(), StatusCode::FORBIDDEN),
+        }
+    }
+}
+
+#[cfg(test)]
+mod rebac_access_guard {
+    use actix_web::middleware::from_fn;
+    use actix_web::{http::StatusCode, test, web, App, HttpMessage, HttpResponse};
+    use mairie360_api_lib::abac::{PolicyEngine, PolicySet};
+    use mairie360_api_lib::pool::AppState;
+    use mairie360_api_lib::rebac::{
+        MemoryTupleStore, NamespaceConfig, RebacEngine, RebacSchema, RelationRewrite,
+    };
+    use mairie360_api_lib::security::{
+        access_guard_middleware, AccessCheckConfig, AuthenticatedUser, TrustedProxies,
+    };
+    use std::sync::Arc;
+
+    async fn fake_handler() -> HttpResponse {
+        HttpResponse::Ok().body("Granted")
+    }
+
+    // Aucune base n'est nécessaire : le moteur ReBAC remplace `check_access`.
+    async fn rebac_state() -> AppState {
+        let schema = RebacSchema::new()
+            .with_namespace(NamespaceConfig::new("group").with_relation("member"))
+            .with_namespace(
+                NamespaceConfig::new("document")
+                    .with_rewrite(
+                        "read",
+                        RelationRewrite::Union(vec![
+                            RelationRewrite::This,
+                            RelationRewrite::tuple_to_userset("parent", "member"),
+                        ]),
+                    )
+                    .with_relation("parent")
+                    .with_relation("tenant"),
+            );
+        let store = MemoryTupleStore::with_tuples(
+            [
+                "group:urbanisme#member@user:7",
+                "document:plan#parent@group:urbanisme",
+                "document:plan#tenant@tenant:lyon",
+            ]
+            .map(|t| t.parse().unwrap()),
+        );
+        AppState::new("".to_string(), "".to_string())
+            .await
+            .with_rebac_engine(RebacEngine::new(schema, Arc::new(store)))
+    }
+
+    async fn call_as(user_id: u64) -> StatusCode {
+        call_as_user(AuthenticatedUser::new(user_id)).await
+    }
+
+    async fn call_as_user(user: AuthenticatedUser) -> StatusCode {
+        let app_state = web::Data::new(rebac_state().await);
+        let app = test::init_service(
+            App::new().app_data(app_state).service(
+                web::resource("/documents/{slug}")
+                    .app_data(
+                        AccessCheckConfig::new("document", "read")
+                            .with_id_param("slug")
+                            .with_id_kind(
+                                mairie360_api_lib::database::instance_id::InstanceIdKind::Str,
+                            ),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::get().to(fake_handler)),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/documents/plan").to_request();
+        req.extensions_mut().insert(user);
+        match test::try_call_service(&app, req).await {
+            Ok(res) => res.status(),
+            Err(err) => err.as_response_error().status_code(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rebac_grants_group_member() {
+        assert_eq!(call_as(7).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_rebac_checks_object_tenant() {
+        let member_of = |tenant: &str| AuthenticatedUser {
+            tenant: Some(tenant.to_string()),
+            ..AuthenticatedUser::new(7)
+        };
+        assert_eq!(call_as_user(member_of("lyon")).await, StatusCode::OK);
+        assert_eq!(
+            call_as_user(member_of("paris")).await,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rebac_denies_non_member() {
+        assert_eq!(call_as(8).await, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_policy_denies_after_rebac_grant() {
+        let policies = PolicySet::from_toml(
+            r#"
+            [[policies]]
+            id = "suspended"
+            effect = "deny"
+            condition = { compare = { attr = "subject.id", op = "eq", value = 7 } }
+            "#,
+        )
+        .unwrap();
+        let app_state = web::Data::new(
+            rebac_state()
+                .await
+                .with_policy_engine(PolicyEngine::new(policies)),
+        );
+        let app = test::init_service(
+            App::new().app_data(app_state).service(
+                web::resource("/documents/{slug}")
+                    .app_data(
+                        AccessCheckConfig::new("document", "read")
+                            .with_id_param("slug")
+                            .with_id_kind(
+                                mairie360_api_lib::database::instance_id::InstanceIdKind::Str,
+                            ),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::get().to(fake_handler)),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/documents/plan").to_request();
+        req.extensions_mut().insert(AuthenticatedUser::new(7));
+        let status = match test::try_call_service(&app, req).await {
+            Ok(res) => res.status(),
+            Err(err) => err.as_response_error().status_code(),
+        };
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    async fn call_from_forwarded_ip(app_state: AppState) -> StatusCode {
+        let policies = PolicySet::from_toml(
+            r#"
+            [[policies]]
+            id = "blocked_ip"
+            effect = "deny"
+            condition = { compare = { attr = "context.ip", op = "eq", value = "203.0.113.9" } }
+            "#,
+        )
+        .unwrap();
+        let app_state = web::Data::new(app_state.with_policy_engine(PolicyEngine::new(policies)));
+        let app = test::init_service(
+            App::new().app_data(app_state).service(
+                web::resource("/documents/{slug}")
+                    .app_data(
+                        AccessCheckConfig::new("document", "read")
+                            .with_id_param("slug")
+                            .with_id_kind(
+                                mairie360_api_lib::database::instance_id::InstanceIdKind::Str,
+                            ),
+                    )
+                    .wrap(from_fn(access_guard_middleware))
+                    .route(web::get().to(fake_handler)),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/documents/plan")
+            .peer_addr("10.0.0.1:1234".parse().unwrap())
+            .insert_header(("X-Forwarded-For", "203.0.113.9"))
+            .to_request();
+        req.extensions_mut().insert(AuthenticatedUser::new(7));
+        match test::try_call_service(&app, req).await {
+            Ok(res) => res.status(),
+            Err(err) => err.as_response_error().status_code(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_policy_ip_ignores_forwarded_header_from_untrusted_peer() {
+        let status = call_from_forwarded_ip(rebac_state().await).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_policy_ip_uses_forwarded_header_from_trusted_proxy() {
+        let app_state = rebac_state()
+            .await
+            .with_trusted_proxies(TrustedProxies::new(["10.0.0.1".parse().unwrap()]));
+        let status = call_from_forwarded_ip(app_state).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+}
@@ -377,6 +377,50 @@ mod queries_tests {
 
             assert!(result == -1, "expected access denied, got {}", result);
         }
+
+        #[tokio::test]
+        #[serial]
+        async fn test_has_access_requires_instance_of_tenant() {
+            use mairie360_api_lib::database::queries::assign_instance_tenant_query;
+            use mairie360_api_lib::database::query_views::AssignInstanceTenantQueryView;
+            use mairie360_api_lib::tenant::install_tenant_columns;
+
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+            install_tenant_columns(&pool).await.unwrap();
+            let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+                .get()
+                .unwrap();
+            sqlx::query("UPDATE users SET tenant_id = 'lyon' WHERE id = $1")
+                .bind(alice_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let check = || {
+                HasAccessQueryView::new(alice_id as u64, "document", "read", 1)
+                    .with_tenant(Some("lyon"))
+            };
+            // Document d'aucune commune : refusé malgré le droit de Alice
+            assert_eq!(has_access_query(check(), pool.clone()).await.unwrap(), 0);
+
+            let view = AssignInstanceTenantQueryView::new("document", 1, "paris");
+            assign_instance_tenant_query(view, pool.clone())
+                .await
+                .unwrap();
+            assert_eq!(has_access_query(check(), pool.clone()).await.unwrap(), 0);
+
+            let view = AssignInstanceTenantQueryView::new("document", 1, "lyon");
+            assign_instance_tenant_query(view, pool.clone())
+                .await
+                .unwrap();
+            assert_eq!(has_access_query(check(), pool.clone()).await.unwrap(), 1);
+
+            sqlx::raw_sql("DELETE FROM tenant_resources; UPDATE users SET tenant_id = NULL;")
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
     }
 
     #[cfg(test)]
@@ -419,4 +463,374 @@ mod queries_tests {
             assert!(!result, "Expected non-admin to not be admin");
         }
     }
+
+    #[cfg(test)]
+    mod has_access_batch_tests {
+        use super::*;
+        use mairie360_api_lib::database::{
+            access_filter::AccessFilter, db_interface::DatabaseQueryView, instance_id::InstanceId,
+            queries::has_access_batch_query, query_views::HasAccessBatchQueryView,
+        };
+        use sqlx::{QueryBuilder, Row};
+
+        #[tokio::test]
+        #[serial]
+        async fn test_has_access_batch_keeps_allowed_ids_only() {
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+
+            // Alice est admin : elle lit le document 1, le document 999 999 n'existe pas (-1).
+            let view = HasAccessBatchQueryView::new(
+                *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+                    .get()
+                    .unwrap() as u64,
+                "document",
+                "read",
+                [999_999, 1],
+            );
+
+            let result = has_access_batch_query(view, pool).await.unwrap();
+
+            assert_eq!(result, vec![InstanceId::Int(1)]);
+        }
+
+        #[tokio::test]
+        #[serial]
+        async fn test_has_access_batch_empty_input() {
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+
+            let view =
+                HasAccessBatchQueryView::new(1, "document", "read", Vec::<InstanceId>::new());
+            let result = has_access_batch_query(view, pool).await.unwrap();
+
+            assert!(result.is_empty());
+        }
+
+        #[tokio::test]
+        #[serial]
+        async fn test_has_access_batch_rejects_mixed_id_kinds() {
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+
+            let ids = [InstanceId::Int(1), InstanceId::Str("plan".to_string())];
+            let view = HasAccessBatchQueryView::new(1, "document", "read", ids);
+            let result = has_access_batch_query(view, pool).await;
+
+            assert!(matches!(
+                result.unwrap_err(),
+                DatabaseError::Query(QueryError::InvalidId(_))
+            ));
+        }
+
+        #[test]
+        fn test_has_access_batch_binds_instance_ids_as_text() {
+            let view = HasAccessBatchQueryView::new(1, "document", "read", [1u64, u64::MAX]);
+            assert_eq!(view.get_sql_type(), Some("bigint"));
+            assert!(view.get_request().contains("unnest($4::text[])"));
+            assert!(view
+                .get_request()
+                .contains("check_access($1, $2, $3, ids.id::bigint)"));
+
+            let uuid = uuid::Uuid::nil();
+            let view = HasAccessBatchQueryView::new(1, "document", "read", [uuid]);
+            assert_eq!(view.get_sql_type(), Some("uuid"));
+
+            let view = HasAccessBatchQueryView::new(1, "document", "read", ["plan", "budget"]);
+            assert!(view.get_request().contains("ids.id::text"));
+        }
+
+        #[test]
+        fn test_access_filter_sql_fragment() {
+            let filter = AccessFilter::new(3, "document", "read");
+            assert_eq!(
+                filter.to_sql("d.id", 2).unwrap(),
+                "check_access($2, $3, $4, d.id) = 1"
+            );
+            assert!(filter.to_sql("id; DROP TABLE users", 1).is_err());
+        }
+
+        #[tokio::test]
+        #[serial]
+        async fn test_access_filter_in_list_query() {
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+
+            let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+                .get()
+                .unwrap() as u64;
+            let filter = AccessFilter::new(alice_id, "document", "read");
+
+            let mut builder = QueryBuilder::new("SELECT d.id FROM document d WHERE ");
+            filter.push_to(&mut builder, "d.id").unwrap();
+            let rows = builder.build().fetch_all(&pool).await.unwrap();
+
+            let ids: Vec<i32> = rows.iter().map(|row| row.get(0)).collect();
+            assert!(ids.contains(&1), "expected document 1 in {:?}", ids);
+        }
+        #[test]
+        fn test_access_filter_with_tenant_sql_fragment() {
+            let filter = AccessFilter::new(3, "document", "read").with_tenant(Some("lyon"));
+            assert_eq!(filter.get_tenant_id(), Some("lyon"));
+            let sql = filter.to_sql("d.id", 2).unwrap();
+            assert!(sql.starts_with("check_access($2, $3, $4, d.id) = 1 AND "));
+            assert!(sql.contains("FROM users WHERE id = $2 AND tenant_id = $5"));
+            assert!(sql
+                .contains("resource_name = $3 AND instance_id = (d.id)::text AND tenant_id = $5"));
+        }
+
+        #[tokio::test]
+        #[serial]
+        async fn test_access_filter_excludes_other_tenants() {
+            use mairie360_api_lib::database::queries::assign_instance_tenant_query;
+            use mairie360_api_lib::database::query_views::AssignInstanceTenantQueryView;
+            use mairie360_api_lib::tenant::install_tenant_columns;
+
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+            install_tenant_columns(&pool).await.unwrap();
+            let alice_id = *mairie360_api_lib::test_setup::queries_setup::ALICE_ID
+                .get()
+                .unwrap();
+            sqlx::query("UPDATE users SET tenant_id = 'lyon' WHERE id = $1")
+                .bind(alice_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+
+            let list = |tenant: &'static str| {
+                let pool = pool.clone();
+                async move {
+                    let filter = AccessFilter::new(alice_id as u64, "document", "read")
+                        .with_tenant(Some(tenant));
+                    let mut builder = QueryBuilder::new("SELECT d.id FROM document d WHERE ");
+                    filter.push_to(&mut builder, "d.id").unwrap();
+                    let rows = builder.build().fetch_all(&pool).await.unwrap();
+                    rows.iter().map(|row| row.get(0)).collect::<Vec<i32>>()
+                }
+            };
+
+            // Document 1 rattaché à une autre commune : exclu du listing
+            let view = AssignInstanceTenantQueryView::new("document", 1, "paris");
+            assign_instance_tenant_query(view, pool.clone())
+                .await
+                .unwrap();
+            assert!(!list("lyon").await.contains(&1));
+            // Alice n'est pas de la commune demandée : aucune ligne
+            assert!(list("paris").await.is_empty());
+
+            let view = AssignInstanceTenantQueryView::new("document", 1, "lyon");
+            assign_instance_tenant_query(view, pool.clone())
+                .await
+                .unwrap();
+            assert!(list("lyon").await.contains(&1));
+
+            sqlx::raw_sql("DELETE FROM tenant_resources; UPDATE users SET tenant_id = NULL;")
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[cfg(test)]
+    mod admin_queries_tests {
+        use super::*;
+        use mairie360_api_lib::database::{
+            queries::{
+                assign_role_query, grant_permission_query, list_instance_access_query,
+                list_user_permissions_query, remove_role_query, revoke_permission_query,
+            },
+            queries_result_views::EffectivePermission,
+            query_views::{
+                AssignRoleQueryView, GrantPermissionQueryView, ListInstanceAccessQueryView,
+                ListUserPermissionsQueryView, RemoveRoleQueryView, RevokePermissionQueryView,
+            },
+        };
+
+        #[tokio::test]
+        #[serial]
+        async fn test_grant_list_and_revoke_instance_permission() {
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+            let owner_id = *mairie360_api_lib::test_setup::queries_setup::GROUP_OWNER_ID
+                .get()
+                .unwrap();
+            let bob_id = *mairie360_api_lib::test_setup::queries_setup::BOB_ID
+                .get()
+                .unwrap() as u64;
+
+            sqlx::query("INSERT INTO public.resources (name) VALUES ('groups') ON CONFLICT (name) DO NOTHING")
+                .execute(&pool)
+                .await
+                .unwrap();
+            sqlx::query(
+                "INSERT INTO public.permissions (resource_id, action) \
+                VALUES ((SELECT id FROM public.resources WHERE name = 'groups'), 'read') \
+                ON CONFLICT DO NOTHING",
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO public.groups (id, owner_id, name) \
+                VALUES (60, $1, 'Delegated Group') \
+                ON CONFLICT (id) DO NOTHING",
+            )
+            .bind(owner_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            let grant = || GrantPermissionQueryView::new(bob_id, "groups", "read", Some(60.into()));
+            assert!(grant_permission_query(grant(), pool.clone()).await.unwrap());
+            assert!(!grant_permission_query(grant(), pool.clone()).await.unwrap());
+
+            let readers = list_instance_access_query(
+                ListInstanceAccessQueryView::new("groups", "read", 60),
+                pool.clone(),
+            )
+            .await
+            .unwrap();
+            assert!(readers.contains(&bob_id));
+
+            let permissions = list_user_permissions_query(
+                ListUserPermissionsQueryView::new(bob_id),
+                pool.clone(),
+            )
+            .await
+            .unwrap();
+            assert!(permissions.contains(&EffectivePermission {
+                resource_name: "groups".to_string(),
+                action: "read".to_string(),
+                instance_id: Some(60),
+            }));
+
+            let revoke =
+                || RevokePermissionQueryView::new(bob_id, "groups", "read", Some(60.into()));
+            assert!(revoke_permission_query(revoke(), pool.clone())
+                .await
+                .unwrap());
+            assert!(!revoke_permission_query(revoke(), pool.clone())
+                .await
+                .unwrap());
+
+            let readers = list_instance_access_query(
+                ListInstanceAccessQueryView::new("groups", "read", 60),
+                pool,
+            )
+            .await
+            .unwrap();
+            assert!(!readers.contains(&bob_id));
+        }
+
+        #[test]
+        fn test_grant_and_revoke_cast_instance_id_type() {
+            use mairie360_api_lib::database::db_interface::DatabaseQueryView;
+            use mairie360_api_lib::database::instance_id::InstanceId;
+
+            let uuid = InstanceId::Uuid(uuid::Uuid::nil());
+            let grant = GrantPermissionQueryView::new(1, "document", "read", Some(uuid.clone()));
+            assert_eq!(grant.get_instance_id(), Some(&uuid));
+            assert!(grant
+                .get_request()
+                .contains("perm.permission_id, $4::uuid FROM perm"));
+            assert!(grant
+                .get_request()
+                .contains("IS NOT DISTINCT FROM $4::uuid"));
+
+            let revoke = RevokePermissionQueryView::new(1, "document", "read", Some("plan".into()));
+            assert!(revoke
+                .get_request()
+                .contains("IS NOT DISTINCT FROM $4::text"));
+
+            let revoke = RevokePermissionQueryView::new(1, "document", "read", None);
+            assert!(revoke
+                .get_request()
+                .contains("IS NOT DISTINCT FROM $4::int"));
+        }
+
+        #[tokio::test]
+        #[serial]
+        async fn test_grant_unknown_permission() {
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+
+            let view = GrantPermissionQueryView::new(1, "groups", "teleport", None);
+            let result = grant_permission_query(view, pool).await;
+
+            assert_eq!(result, Err(DatabaseError::Query(QueryError::NoResults)));
+        }
+
+        #[tokio::test]
+        #[serial]
+        async fn test_assign_and_remove_role() {
+            let (_container, host) = get_shared_db().await;
+            let pool = get_pool(host.as_str().to_string()).await;
+            let bob_id = *mairie360_api_lib::test_setup::queries_setup::BOB_ID
+                .get()
+                .unwrap() as u64;
+
+            assert!(
+                assign_role_query(AssignRoleQueryView::new(bob_id, 1), pool.clone())
+                    .await
+                    .unwrap()
+            );
+            assert!(
+                !assign_role_query(AssignRoleQueryView::new(bob_id, 1), pool.clone())
+                    .await
+                    .unwrap()
+            );
+            assert!(
+                remove_role_query(RemoveRoleQueryView::new(bob_id, 1), pool.clone())
+                    .await
+                    .unwrap()
+            );
+            assert!(
+                !remove_role_query(RemoveRoleQueryView::new(bob_id, 1), pool)
+                    .await
+                    .unwrap()
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod instance_id_tests {
+        use mairie360_api_lib::database::instance_id::{InstanceId, InstanceIdKind};
+        use mairie360_api_lib::database::{
+            db_interface::DatabaseQueryView, query_views::HasAccessQueryView,
+        };
+
+        #[test]
+        fn test_instance_id_kind_parse() {
+            assert_eq!(InstanceIdKind::Int.parse("42"), Ok(InstanceId::Int(42)));
+            assert_eq!(
+                InstanceIdKind::BigInt.parse("9000000000"),
+                Ok(InstanceId::BigInt(9_000_000_000))
+            );
+            assert!(InstanceIdKind::Int.parse("9000000000").is_err());
+            assert!(InstanceIdKind::Uuid.parse("not-a-uuid").is_err());
+            assert_eq!(
+                InstanceIdKind::Str.parse("mairie-de-lyon"),
+                Ok(InstanceId::Str("mairie-de-lyon".to_string()))
+            );
+        }
+
+        #[test]
+        fn test_has_access_request_casts_instance_type() {
+            let view = HasAccessQueryView::new(1, "document", "read", 3);
+            assert_eq!(
+                view.get_request(),
+                "SELECT check_access($1, $2, $3, $4::int)"
+            );
+
+            let view = HasAccessQueryView::new(1, "document", "read", "slug");
+            assert_eq!(
+                view.get_request(),
+                "SELECT check_access($1, $2, $3, $4::text)"
+            );
+
+            let view = HasAccessQueryView::new_global(1, "document", "read");
+            assert_eq!(view.get_instance_id(), None);
+        }
+    }
 }
@@ -0,0 +1,229 @@
+use mairie360_api_lib::pool::AppState;
+use mairie360_api_lib::rate_limit::{MemoryRateLimiter, Quota, RateLimitKey, RateLimiter};
+use std::time::Duration;
+
+/**
+ * Limiteur en mémoire (GCRA) et sélection des quotas.
+ */
+#[cfg(test)]
+mod rate_limiter_tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_reject() {
+        let limiter = MemoryRateLimiter::new();
+        let quota = Quota::per_minute(3);
+
+        let remaining: Vec<u32> = (0..3)
+            .map(|_| limiter.check("ip:1.2.3.4", &quota))
+            .inspect(|decision| assert!(decision.is_allowed()))
+            .map(|decision| decision.remaining)
+            .collect();
+        assert_eq!(remaining, vec![2, 1, 0]);
+
+        let rejected = limiter.check("ip:1.2.3.4", &quota);
+        assert!(!rejected.is_allowed());
+        assert_eq!(rejected.remaining, 0);
+        let retry_after = rejected.retry_after.unwrap();
+        assert!(retry_after > Duration::from_secs(19) && retry_after <= Duration::from_secs(20));
+
+        // Les compteurs sont indépendants d'une clé à l'autre
+        assert!(limiter.check("ip:5.6.7.8", &quota).is_allowed());
+        assert_eq!(limiter.len(), 2);
+    }
+
+    #[test]
+    fn test_quota_is_refilled_over_time() {
+        let limiter = MemoryRateLimiter::new();
+        let quota = Quota::new(2, Duration::from_millis(100));
+
+        assert!(limiter.check("user:1", &quota).is_allowed());
+        assert!(limiter.check("user:1", &quota).is_allowed());
+        assert!(!limiter.check("user:1", &quota).is_allowed());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(limiter.check("user:1", &quota).is_allowed());
+    }
+
+    #[test]
+    fn test_route_quotas() {
+        let limiter = RateLimiter::new(Quota::per_minute(100))
+            .with_route_quota("/auth", Quota::per_minute(10))
+            .with_route_quota("/auth/login", Quota::per_minute(5))
+            .without_limit_on("/health");
+
+        assert_eq!(limiter.quota_for("/users").1, Some(&Quota::per_minute(100)));
+        assert_eq!(
+            limiter.quota_for("/auth/register").1,
+            Some(&Quota::per_minute(10))
+        );
+        assert_eq!(
+            limiter.quota_for("/auth/login"),
+            ("/auth/login", Some(&Quota::per_minute(5)))
+        );
+        assert_eq!(limiter.quota_for("/health/live").1, None);
+    }
+
+    #[test]
+    fn test_subjects() {
+        let ip = Some("10.0.0.1");
+        assert_eq!(RateLimitKey::Ip.subject(ip, Some(4), None), "ip:10.0.0.1");
+        assert_eq!(RateLimitKey::User.subject(ip, Some(4), None), "user:4");
+        assert_eq!(RateLimitKey::User.subject(ip, None, None), "ip:10.0.0.1");
+
+        let key = RateLimitKey::ApiKey("X-Api-Key".to_string());
+        let subject = key.subject(ip, None, Some("secret-key"));
+        assert!(subject.starts_with("key:"));
+        assert!(!subject.contains("secret-key"));
+        assert_eq!(key.subject(ip, None, None), "ip:10.0.0.1");
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_memory_without_redis() {
+        let state = AppState::new("".to_string(), "".to_string()).await;
+        let limiter = RateLimiter::new(Quota::per_minute(1));
+
+        assert!(limiter.check(&state, "ip:1", "/users").await.is_allowed());
+        assert!(!limiter.check(&state, "ip:1", "/users").await.is_allowed());
+    }
+}
+
+/**
+ * Middleware actix : en-têtes `RateLimit-*` et réponse 429.
+ */
+#[cfg(test)]
+mod rate_limit_middleware_tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test, web, App, HttpMessage, HttpResponse};
+    use mairie360_api_lib::rate_limit::RateLimitMiddleware;
+    use mairie360_api_lib::security::AuthenticatedUser;
+
+    #[tokio::test]
+    async fn test_headers_and_too_many_requests() {
+        let app_state = web::Data::new(AppState::new("".to_string(), "".to_string()).await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .wrap(RateLimitMiddleware::new(
+                    RateLimiter::new(Quota::per_minute(2)).with_key(RateLimitKey::User),
+                ))
+                .route("/ping", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let call = |user_id: u64| {
+            let req = test::TestRequest::get().uri("/ping").to_request();
+            req.extensions_mut().insert(AuthenticatedUser::new(user_id));
+            req
+        };
+
+        let resp = test::call_service(&app, call(1)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("ratelimit-limit").unwrap(), "2");
+        assert_eq!(resp.headers().get("ratelimit-remaining").unwrap(), "1");
+
+        test::call_service(&app, call(1)).await;
+        let resp = test::call_service(&app, call(1)).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get("ratelimit-remaining").unwrap(), "0");
+        assert_eq!(resp.headers().get("retry-after").unwrap(), "30");
+
+        // Un autre utilisateur a son propre quota
+        let resp = test::call_service(&app, call(2)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_for_is_ignored_from_untrusted_peer() {
+        let app_state = web::Data::new(AppState::new("".to_string(), "".to_string()).await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .wrap(RateLimitMiddleware::new(RateLimiter::new(
+                    Quota::per_minute(1),
+                )))
+                .route("/ping", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let call = |forwarded_for: &str| {
+            test::TestRequest::get()
+                .uri("/ping")
+                .peer_addr("10.0.0.1:1234".parse().unwrap())
+                .insert_header(("X-Forwarded-For", forwarded_for))
+                .to_request()
+        };
+
+        let resp = test::call_service(&app, call("203.0.113.1")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = test::call_service(&app, call("203.0.113.2")).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_for_is_used_behind_trusted_proxy() {
+        use mairie360_api_lib::security::TrustedProxies;
+
+        let app_state = web::Data::new(
+            AppState::new("".to_string(), "".to_string())
+                .await
+                .with_trusted_proxies(TrustedProxies::new(["10.0.0.1".parse().unwrap()])),
+        );
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .wrap(RateLimitMiddleware::new(RateLimiter::new(
+                    Quota::per_minute(1),
+                )))
+                .route("/ping", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let call = |forwarded_for: &str| {
+            test::TestRequest::get()
+                .uri("/ping")
+                .peer_addr("10.0.0.1:1234".parse().unwrap())
+                .insert_header(("X-Forwarded-For", forwarded_for))
+                .to_request()
+        };
+
+        let resp = test::call_service(&app, call("203.0.113.1")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = test::call_service(&app, call("203.0.113.2")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_unvalidated_api_keys_share_the_ip_quota() {
+        let app_state = web::Data::new(AppState::new("".to_string(), "".to_string()).await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .wrap(RateLimitMiddleware::new(
+                    RateLimiter::new(Quota::per_minute(1))
+                        .with_key(RateLimitKey::ApiKey("X-Api-Key".to_string()))
+                        .with_api_key_validator(|key| key.starts_with("valid-")),
+                ))
+                .route("/ping", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let call = |api_key: &str| {
+            test::TestRequest::get()
+                .uri("/ping")
+                .peer_addr("10.0.0.1:1234".parse().unwrap())
+                .insert_header(("X-Api-Key", api_key))
+                .to_request()
+        };
+
+        // Clés inventées : un seul quota, celui de l'IP
+        let resp = test::call_service(&app, call("forged-1")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = test::call_service(&app, call("forged-2")).await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Une clé reconnue a son propre quota
+        let resp = test::call_service(&app, call("valid-1")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
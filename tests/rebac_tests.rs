@@ -0,0 +1,348 @@
+use mairie360_api_lib::rebac::{
+    MemoryTupleStore, NamespaceConfig, ObjectRef, RebacEngine, RebacError, RebacSchema,
+    RelationRewrite, RelationTuple, SubjectRef,
+};
+use std::sync::Arc;
+
+/**
+ * Schéma municipal de référence : un agent appartient à un service, un service
+ * à un département ; les responsables d'un niveau gèrent les niveaux inférieurs.
+ * Les documents héritent des lecteurs de leur service.
+ */
+fn municipal_schema() -> RebacSchema {
+    RebacSchema::new()
+        .with_namespace(NamespaceConfig::new("group").with_relation("member"))
+        .with_namespace(NamespaceConfig::new("department").with_relation("manager"))
+        .with_namespace(
+            NamespaceConfig::new("service")
+                .with_relation("parent")
+                .with_rewrite(
+                    "manager",
+                    RelationRewrite::Union(vec![
+                        RelationRewrite::This,
+                        RelationRewrite::tuple_to_userset("parent", "manager"),
+                    ]),
+                )
+                .with_rewrite(
+                    "member",
+                    RelationRewrite::Union(vec![
+                        RelationRewrite::This,
+                        RelationRewrite::computed("manager"),
+                    ]),
+                ),
+        )
+        .with_namespace(
+            NamespaceConfig::new("document")
+                .with_relation("parent")
+                .with_relation("owner")
+                .with_rewrite(
+                    "read",
+                    RelationRewrite::Union(vec![
+                        RelationRewrite::This,
+                        RelationRewrite::computed("owner"),
+                        RelationRewrite::tuple_to_userset("parent", "member"),
+                    ]),
+                ),
+        )
+}
+
+fn tuple(s: &str) -> RelationTuple {
+    s.parse().unwrap()
+}
+
+fn municipal_engine() -> RebacEngine {
+    let store = MemoryTupleStore::with_tuples(
+        [
+            "department:urbanisme#manager@user:1",
+            "service:voirie#parent@department:urbanisme",
+            "service:voirie#member@user:2",
+            "service:etat-civil#member@group:stagiaires#member",
+            "group:stagiaires#member@user:3",
+            "document:plan-2026#parent@service:voirie",
+            "document:acte-12#parent@service:etat-civil",
+            "document:note#owner@user:4",
+        ]
+        .map(tuple),
+    );
+    RebacEngine::new(municipal_schema(), Arc::new(store))
+}
+
+#[cfg(test)]
+mod relation_tuple_tests {
+    use super::*;
+
+    #[test]
+    fn test_tuple_round_trip() {
+        for raw in [
+            "document:12#viewer@user:42",
+            "service:7#parent@department:2",
+            "document:12#viewer@group:5#member",
+        ] {
+            assert_eq!(tuple(raw).to_string(), raw);
+        }
+    }
+
+    #[test]
+    fn test_tuple_subjects() {
+        assert_eq!(
+            tuple("document:12#viewer@user:42").get_subject(),
+            &SubjectRef::User(42)
+        );
+        assert_eq!(
+            tuple("document:12#viewer@group:5#member").get_subject(),
+            &SubjectRef::userset(ObjectRef::new("group", 5), "member")
+        );
+    }
+
+    #[test]
+    fn test_invalid_tuples_are_rejected() {
+        for raw in [
+            "document:12#viewer",
+            "document#viewer@user:1",
+            "document:12@user:1",
+        ] {
+            assert!(matches!(
+                raw.parse::<RelationTuple>(),
+                Err(RebacError::InvalidTuple(_))
+            ));
+        }
+        assert!("document:1#read@user:abc".parse::<RelationTuple>().is_err());
+    }
+}
+
+#[cfg(test)]
+mod engine_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_check_direct_and_computed() {
+        let engine = municipal_engine();
+        let note = ObjectRef::new("document", "note");
+        assert!(engine.check(&note, "read", 4).await.unwrap());
+        assert!(!engine.check(&note, "read", 2).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_check_follows_delegation_chain() {
+        let engine = municipal_engine();
+        let plan = ObjectRef::new("document", "plan-2026");
+        // Agent du service
+        assert!(engine.check(&plan, "read", 2).await.unwrap());
+        // Responsable du département parent du service
+        assert!(engine.check(&plan, "read", 1).await.unwrap());
+        // Stagiaire d'un autre service
+        assert!(!engine.check(&plan, "read", 3).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_check_follows_group_membership() {
+        let engine = municipal_engine();
+        let acte = ObjectRef::new("document", "acte-12");
+        assert!(engine.check(&acte, "read", 3).await.unwrap());
+        assert!(!engine.check(&acte, "read", 1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_expand_lists_every_reader() {
+        let engine = municipal_engine();
+        let tree = engine
+            .expand(&ObjectRef::new("document", "plan-2026"), "read")
+            .await
+            .unwrap();
+        assert_eq!(tree.users().into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_list_objects() {
+        let engine = municipal_engine();
+        assert_eq!(
+            engine.list_objects("document", "read", 1).await.unwrap(),
+            vec!["plan-2026".to_string()]
+        );
+        assert_eq!(
+            engine.list_objects("document", "read", 3).await.unwrap(),
+            vec!["acte-12".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_relation_is_an_error() {
+        let engine = municipal_engine();
+        let result = engine
+            .check(&ObjectRef::new("document", "note"), "delete", 4)
+            .await;
+        assert_eq!(
+            result,
+            Err(RebacError::UnknownRelation {
+                namespace: "document".to_string(),
+                relation: "delete".to_string()
+            })
+        );
+        assert!(engine
+            .write(&tuple("document:note#delete@user:4"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cycles_stop_at_max_depth() {
+        let schema = RebacSchema::new()
+            .with_namespace(NamespaceConfig::new("group").with_relation("member"));
+        let store = MemoryTupleStore::with_tuples([
+            tuple("group:a#member@group:b#member"),
+            tuple("group:b#member@group:a#member"),
+            tuple("group:b#member@user:2"),
+        ]);
+        let engine = RebacEngine::new(schema, Arc::new(store)).with_max_depth(4);
+        let group_a = ObjectRef::new("group", "a");
+
+        // Un cycle refuse l'accès au lieu de produire une erreur (500)
+        assert_eq!(engine.check(&group_a, "member", 1).await, Ok(false));
+        assert_eq!(engine.check(&group_a, "member", 2).await, Ok(true));
+
+        let tree = engine.expand(&group_a, "member").await.unwrap();
+        assert_eq!(tree.users().into_iter().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_deep_chain_is_denied() {
+        let schema = RebacSchema::new()
+            .with_namespace(NamespaceConfig::new("group").with_relation("member"));
+        let store = MemoryTupleStore::with_tuples([
+            tuple("group:a#member@group:b#member"),
+            tuple("group:b#member@group:c#member"),
+            tuple("group:c#member@group:d#member"),
+            tuple("group:d#member@user:1"),
+        ]);
+        let engine = RebacEngine::new(schema, Arc::new(store));
+        let group_a = ObjectRef::new("group", "a");
+        assert_eq!(engine.check(&group_a, "member", 1).await, Ok(true));
+
+        let engine = engine.with_max_depth(2);
+        assert_eq!(engine.check(&group_a, "member", 1).await, Ok(false));
+        assert_eq!(
+            engine.expand(&group_a, "member").await,
+            Err(RebacError::MaxDepthExceeded(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_then_delete() {
+        let engine = municipal_engine();
+        let note = ObjectRef::new("document", "note");
+        let grant = tuple("document:note#read@user:9");
+
+        assert!(engine.write(&grant).await.unwrap());
+        assert!(!engine.write(&grant).await.unwrap());
+        assert!(engine.check(&note, "read", 9).await.unwrap());
+
+        assert!(engine.delete(&grant).await.unwrap());
+        assert!(!engine.check(&note, "read", 9).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_belongs_to_tenant() {
+        let store = MemoryTupleStore::with_tuples([tuple("document:plan-2026#tenant@tenant:lyon")]);
+        let engine = RebacEngine::new(municipal_schema(), Arc::new(store));
+        let plan = ObjectRef::new("document", "plan-2026");
+
+        assert!(engine.belongs_to_tenant(&plan, "lyon").await.unwrap());
+        assert!(!engine.belongs_to_tenant(&plan, "paris").await.unwrap());
+        assert!(!engine
+            .belongs_to_tenant(&ObjectRef::new("document", "note"), "lyon")
+            .await
+            .unwrap());
+    }
+
+    #[test]
+    fn test_schema_from_json() {
+        let schema = RebacSchema::from_json(
+            r#"{"group": {"name": "group", "relations": {"member": "this"}},
+                "document": {"name": "document", "relations": {
+                    "read": {"union": ["this", {"tuple_to_userset": {"tupleset": "parent", "computed_relation": "member"}}]}
+                }}}"#,
+        )
+        .unwrap();
+        assert!(schema.has_relation("document", "read"));
+        assert!(!schema.has_relation("document", "write"));
+        assert!(RebacSchema::from_json("{").is_err());
+    }
+}
+
+#[cfg(test)]
+mod pg_tuple_store_tests {
+    use super::*;
+    use mairie360_api_lib::pool::AppState;
+    use mairie360_api_lib::rebac::{install_relation_tuples_table, PgTupleStore, TupleStore};
+    use mairie360_api_lib::test_setup::queries_setup::get_shared_db;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_pg_store_matches_memory_store() {
+        let (_container, url) = get_shared_db().await;
+        let state = AppState::new("".to_string(), url.to_string()).await;
+        let pool = state.db_pool().cloned().unwrap();
+        install_relation_tuples_table(&pool).await.unwrap();
+
+        let store = PgTupleStore::new(pool);
+        let tuples = [
+            tuple("service:pg-voirie#member@user:2"),
+            tuple("document:pg-plan#parent@service:pg-voirie"),
+            tuple("service:pg-voirie#member@group:pg-stagiaires#member"),
+        ];
+        for t in &tuples {
+            store.write(t).await.unwrap();
+        }
+        assert!(!store.write(&tuples[0]).await.unwrap());
+
+        let mut subjects = store
+            .read_subjects(&ObjectRef::new("service", "pg-voirie"), "member")
+            .await
+            .unwrap();
+        subjects.sort();
+        assert_eq!(
+            subjects,
+            vec![
+                SubjectRef::User(2),
+                SubjectRef::userset(ObjectRef::new("group", "pg-stagiaires"), "member"),
+            ]
+        );
+
+        let engine = RebacEngine::new(municipal_schema(), Arc::new(store.clone()));
+        assert!(engine
+            .check(&ObjectRef::new("document", "pg-plan"), "read", 2)
+            .await
+            .unwrap());
+
+        for t in &tuples {
+            assert!(store.delete(t).await.unwrap());
+        }
+        assert!(store.list_object_ids("document").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_tuple_changes_notify_cache_invalidation() {
+        use mairie360_api_lib::cache::{InvalidationEvent, INVALIDATION_CHANNEL};
+        use sqlx::postgres::PgListener;
+
+        let (_container, url) = get_shared_db().await;
+        let state = AppState::new("".to_string(), url.to_string()).await;
+        let pool = state.db_pool().cloned().unwrap();
+        install_relation_tuples_table(&pool).await.unwrap();
+        let mut listener = PgListener::connect_with(&pool).await.unwrap();
+        listener.listen(INVALIDATION_CHANNEL).await.unwrap();
+
+        let engine = RebacEngine::new(municipal_schema(), Arc::new(PgTupleStore::new(pool)));
+        let grant = tuple("document:pg-notify#owner@user:5");
+        for changed in [engine.write(&grant).await, engine.delete(&grant).await] {
+            assert!(changed.unwrap());
+            let notification = listener.recv().await.unwrap();
+            assert_eq!(
+                InvalidationEvent::from_payload(notification.payload()),
+                InvalidationEvent::All
+            );
+        }
+    }
+}
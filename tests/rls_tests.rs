@@ -0,0 +1,152 @@
+use mairie360_api_lib::database::rls_transaction::RlsTransaction;
+use mairie360_api_lib::pool::AppState;
+use mairie360_api_lib::security::AuthenticatedUser;
+use mairie360_api_lib::tenant::{TenantConfig, TenantSource};
+
+/**
+ * Variables de session positionnées par `RlsTransaction`.
+ */
+#[cfg(test)]
+mod rls_transaction_tests {
+    use super::*;
+    use mairie360_api_lib::test_setup::queries_setup::get_shared_db;
+    use serial_test::serial;
+
+    async fn setting(conn: &mut sqlx::PgConnection, name: &str) -> Option<String> {
+        sqlx::query_scalar::<_, Option<String>>("SELECT current_setting($1, true)")
+            .bind(name)
+            .fetch_one(conn)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_settings_are_local_to_the_transaction() {
+        let (_container, url) = get_shared_db().await;
+        let state = AppState::new("".to_string(), url.to_string()).await;
+        let pool = state.db_pool().cloned().unwrap();
+
+        let mut tx = RlsTransaction::begin(&pool, 5, Some("lyon")).await.unwrap();
+        assert_eq!(setting(&mut tx, "app.user_id").await.as_deref(), Some("5"));
+        assert_eq!(
+            setting(&mut tx, "app.tenant_id").await.as_deref(),
+            Some("lyon")
+        );
+        tx.commit().await.unwrap();
+
+        let mut tx = RlsTransaction::begin(&pool, 6, None).await.unwrap();
+        assert_eq!(setting(&mut tx, "app.user_id").await.as_deref(), Some("6"));
+        assert_eq!(setting(&mut tx, "app.tenant_id").await.as_deref(), Some(""));
+        tx.rollback().await.unwrap();
+
+        // Hors transaction, la connexion rendue au pool ne garde aucune valeur
+        let mut conn = pool.acquire().await.unwrap();
+        let user_id = setting(&mut conn, "app.user_id").await;
+        assert!(user_id.is_none() || user_id.as_deref() == Some(""));
+    }
+}
+
+/**
+ * Extracteurs : refus avant toute connexion à la base.
+ */
+#[cfg(test)]
+mod rls_extractor_tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test, web, App, HttpMessage, HttpResponse};
+    use std::sync::Arc;
+
+    async fn handler(tx: RlsTransaction) -> HttpResponse {
+        HttpResponse::Ok().body(tx.get_user_id().to_string())
+    }
+
+    #[tokio::test]
+    async fn test_actix_rejections() {
+        let app_state = web::Data::new(AppState::new("".to_string(), "".to_string()).await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .route("/rls", web::get().to(handler)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/rls").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::get().uri("/rls").to_request();
+        req.extensions_mut().insert(AuthenticatedUser::new(3));
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_axum_rejections() {
+        use axum::extract::FromRequestParts;
+        use axum::http::{Request, StatusCode};
+
+        let state = Arc::new(AppState::new("".to_string(), "".to_string()).await);
+
+        let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+        let rejection = RlsTransaction::from_request_parts(&mut parts, &state)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(rejection.0, StatusCode::UNAUTHORIZED);
+
+        parts.extensions.insert(AuthenticatedUser::new(3));
+        let rejection = RlsTransaction::from_request_parts(&mut parts, &state)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(rejection.0, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    async fn state_with_tenant_header() -> AppState {
+        AppState::new("".to_string(), "".to_string())
+            .await
+            .with_tenant_config(TenantConfig::new(vec![
+                TenantSource::Header("X-Tenant-Id".to_string()),
+                TenantSource::Claim,
+            ]))
+    }
+
+    #[tokio::test]
+    async fn test_actix_tenant_header_without_claim_is_rejected() {
+        let app_state = web::Data::new(state_with_tenant_header().await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .route("/rls", web::get().to(handler)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/rls")
+            .insert_header(("X-Tenant-Id", "lyon"))
+            .to_request();
+        req.extensions_mut().insert(AuthenticatedUser::new(3));
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_axum_tenant_header_without_claim_is_rejected() {
+        use axum::extract::FromRequestParts;
+        use axum::http::{Request, StatusCode};
+
+        let state = Arc::new(state_with_tenant_header().await);
+
+        let (mut parts, _) = Request::builder()
+            .header("X-Tenant-Id", "lyon")
+            .body(())
+            .unwrap()
+            .into_parts();
+        parts.extensions.insert(AuthenticatedUser::new(3));
+        let rejection = RlsTransaction::from_request_parts(&mut parts, &state)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(rejection.0, StatusCode::BAD_REQUEST);
+    }
+}
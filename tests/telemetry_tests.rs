@@ -0,0 +1,170 @@
+/**
+ * Export OTLP/HTTP vers un collecteur local factice : les requêtes protobuf reçues
+ * sont conservées et l'on y cherche les noms de spans, d'attributs et de métriques.
+ */
+#[cfg(all(test, feature = "telemetry"))]
+mod telemetry_tests {
+    use actix_web::{test, web, App, HttpResponse};
+    use mairie360_api_lib::correlation::{outgoing_headers, RequestIdMiddleware, TraceParent};
+    use mairie360_api_lib::pool::AppState;
+    use mairie360_api_lib::rate_limit::{Quota, RateLimitMiddleware, RateLimiter};
+    use mairie360_api_lib::telemetry::{init_telemetry, TelemetryConfig};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    type Received = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+    /// Collecteur OTLP/HTTP minimal : répond 200 à chaque POST et garde (chemin, corps).
+    async fn start_collector() -> (String, Received) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let received = Received::default();
+        let store = received.clone();
+        tokio::spawn(async move {
+            loop {
+                let (socket, _) = listener.accept().await.unwrap();
+                let store = store.clone();
+                tokio::spawn(async move {
+                    let mut reader = BufReader::new(socket);
+                    loop {
+                        let mut request_line = String::new();
+                        if reader.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let path = request_line
+                            .split_whitespace()
+                            .nth(1)
+                            .unwrap_or_default()
+                            .to_string();
+                        let mut content_length = 0;
+                        loop {
+                            let mut line = String::new();
+                            reader.read_line(&mut line).await.unwrap();
+                            let line = line.trim_end();
+                            if line.is_empty() {
+                                break;
+                            }
+                            if let Some((name, value)) = line.split_once(':') {
+                                if name.eq_ignore_ascii_case("content-length") {
+                                    content_length = value.trim().parse().unwrap();
+                                }
+                            }
+                        }
+                        let mut body = vec![0; content_length];
+                        reader.read_exact(&mut body).await.unwrap();
+                        store.lock().unwrap().push((path, body));
+                        reader
+                            .get_mut()
+                            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                            .await
+                            .unwrap();
+                    }
+                });
+            }
+        });
+        (endpoint, received)
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    fn received_on(received: &Received, path: &str) -> Vec<u8> {
+        received
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(p, _)| p == path)
+            .flat_map(|(_, body)| body.clone())
+            .collect()
+    }
+
+    async fn handler() -> HttpResponse {
+        let headers = outgoing_headers();
+        let trace_parent = TraceParent::parse(&headers[1].1).unwrap();
+        // L'appel sortant continue la trace de l'appelant depuis le span exporté
+        assert_eq!(trace_parent.get_trace_id(), TRACE_ID);
+        assert_ne!(trace_parent.get_parent_id(), "00f067aa0ba902b7");
+        HttpResponse::Ok().finish()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_exports_spans_and_metrics_to_collector() {
+        let (endpoint, received) = start_collector().await;
+        let config = TelemetryConfig::new("telemetry-test")
+            .with_endpoint(endpoint)
+            .with_export_timeout(Duration::from_secs(5));
+        let guard = init_telemetry(&config).unwrap();
+        let subscriber = tracing_subscriber::registry().with(guard.tracing_layer());
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let app_state = web::Data::new(AppState::new("".to_string(), "".to_string()).await);
+        let app = test::init_service(
+            App::new()
+                .app_data(app_state)
+                .wrap(RateLimitMiddleware::new(RateLimiter::new(
+                    Quota::per_minute(10),
+                )))
+                .wrap(RequestIdMiddleware::new())
+                .route("/ping", web::get().to(handler)),
+        )
+        .await;
+        let req = test::TestRequest::get()
+            .uri("/ping")
+            .insert_header(("traceparent", TRACEPARENT))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        guard.force_flush().unwrap();
+
+        let traces = received_on(&received, "/v1/traces");
+        assert!(contains(&traces, b"telemetry-test"));
+        assert!(contains(&traces, b"http.request"));
+        assert!(contains(&traces, b"http.security"));
+        assert!(contains(&traces, b"rate_limit"));
+        assert!(contains(&traces, &hex::decode(TRACE_ID).unwrap()));
+
+        let metrics = received_on(&received, "/v1/metrics");
+        assert!(contains(&metrics, b"security.decisions"));
+        assert!(contains(&metrics, b"security.check.duration"));
+
+        guard.shutdown().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_config_from_env() {
+        temp_env::with_vars(
+            [
+                ("OTEL_SERVICE_NAME", Some("from-env")),
+                (
+                    "OTEL_EXPORTER_OTLP_ENDPOINT",
+                    Some("http://collector:4318/"),
+                ),
+                ("OTEL_EXPORTER_OTLP_TIMEOUT", Some("2500")),
+                ("OTEL_METRIC_EXPORT_INTERVAL", None),
+            ],
+            || {
+                let config = TelemetryConfig::from_env("default-name");
+                assert_eq!(config.service_name, "from-env");
+                assert_eq!(config.endpoint, "http://collector:4318/");
+                assert_eq!(config.export_timeout, Duration::from_millis(2500));
+                assert_eq!(config.metrics_interval, Duration::from_secs(60));
+            },
+        );
+        temp_env::with_var_unset("OTEL_SERVICE_NAME", || {
+            assert_eq!(
+                TelemetryConfig::from_env("default-name").service_name,
+                "default-name"
+            );
+        });
+    }
+}
@@ -0,0 +1,210 @@
+use mairie360_api_lib::security::AuthenticatedUser;
+use mairie360_api_lib::tenant::{
+    resolve_tenant, TenantConfig, TenantError, TenantScope, TenantSource,
+};
+
+fn user_of(tenant: &str) -> AuthenticatedUser {
+    AuthenticatedUser {
+        tenant: Some(tenant.to_string()),
+        ..AuthenticatedUser::new(1)
+    }
+}
+
+fn super_admin() -> AuthenticatedUser {
+    AuthenticatedUser {
+        roles: vec!["super_admin".to_string()],
+        ..AuthenticatedUser::new(2)
+    }
+}
+
+fn no_header(_: &str) -> Option<String> {
+    None
+}
+
+/**
+ * Résolution de la commune à partir du claim, du sous-domaine ou d'un en-tête.
+ */
+#[cfg(test)]
+mod resolve_tenant_tests {
+    use super::*;
+
+    fn all_sources() -> TenantConfig {
+        TenantConfig::new(vec![
+            TenantSource::Header("X-Tenant-Id".to_string()),
+            TenantSource::Subdomain {
+                base_domain: "mairie360.fr".to_string(),
+            },
+            TenantSource::Claim,
+        ])
+    }
+
+    #[test]
+    fn test_claim_is_the_default_source() {
+        let scope = resolve_tenant(
+            &TenantConfig::default(),
+            Some(&user_of("lyon")),
+            Some("paris.mairie360.fr"),
+            no_header,
+        );
+        assert_eq!(scope, Ok(TenantScope::Tenant("lyon".to_string())));
+    }
+
+    #[test]
+    fn test_subdomain_and_header_sources() {
+        let config = all_sources();
+
+        let scope = resolve_tenant(
+            &config,
+            Some(&user_of("lyon")),
+            Some("lyon.mairie360.fr:8080"),
+            no_header,
+        );
+        assert_eq!(scope, Ok(TenantScope::Tenant("lyon".to_string())));
+
+        let scope = resolve_tenant(
+            &config,
+            Some(&super_admin()),
+            Some("lyon.mairie360.fr"),
+            |name| (name == "X-Tenant-Id").then(|| "paris".to_string()),
+        );
+        assert_eq!(scope, Ok(TenantScope::Tenant("paris".to_string())));
+
+        // Hôte hors du domaine de base : la source suivante (claim) répond
+        let scope = resolve_tenant(
+            &config,
+            Some(&user_of("nice")),
+            Some("example.org"),
+            no_header,
+        );
+        assert_eq!(scope, Ok(TenantScope::Tenant("nice".to_string())));
+
+        let scope = resolve_tenant(&config, None, Some("mairie360.fr"), no_header);
+        assert_eq!(scope, Ok(TenantScope::All));
+    }
+
+    #[test]
+    fn test_requested_tenant_without_claim_is_rejected() {
+        let config = all_sources();
+
+        let scope = resolve_tenant(
+            &config,
+            Some(&AuthenticatedUser::new(1)),
+            Some("lyon.mairie360.fr"),
+            no_header,
+        );
+        assert_eq!(scope, Err(TenantError::MissingTenant));
+
+        let scope = resolve_tenant(&config, None, None, |name| {
+            (name == "X-Tenant-Id").then(|| "paris".to_string())
+        });
+        assert_eq!(scope, Err(TenantError::MissingTenant));
+    }
+
+    #[test]
+    fn test_other_tenant_is_rejected() {
+        let scope = resolve_tenant(
+            &all_sources(),
+            Some(&user_of("lyon")),
+            Some("paris.mairie360.fr"),
+            no_header,
+        );
+        assert_eq!(
+            scope,
+            Err(TenantError::TenantMismatch {
+                requested: "paris".to_string(),
+                user_tenant: "lyon".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_super_admin_crosses_tenants() {
+        let config = all_sources().with_required(true);
+
+        let scope = resolve_tenant(&config, Some(&super_admin()), None, no_header);
+        assert_eq!(scope, Ok(TenantScope::All));
+
+        let admin_of_lyon = AuthenticatedUser {
+            roles: vec!["super_admin".to_string()],
+            ..user_of("lyon")
+        };
+        let scope = resolve_tenant(
+            &config,
+            Some(&admin_of_lyon),
+            Some("paris.mairie360.fr"),
+            no_header,
+        );
+        assert_eq!(scope, Ok(TenantScope::Tenant("paris".to_string())));
+    }
+
+    #[test]
+    fn test_required_tenant() {
+        let config = TenantConfig::default().with_required(true);
+        assert_eq!(
+            resolve_tenant(&config, Some(&AuthenticatedUser::new(1)), None, no_header),
+            Err(TenantError::MissingTenant)
+        );
+        assert_eq!(
+            resolve_tenant(&TenantConfig::default(), None, None, no_header),
+            Ok(TenantScope::All)
+        );
+    }
+}
+
+/**
+ * Requêtes filtrées par commune : le SQL ne change pas sans commune.
+ */
+#[cfg(test)]
+mod tenant_queries_tests {
+    use mairie360_api_lib::database::db_interface::DatabaseQueryView;
+    use mairie360_api_lib::database::query_views::{
+        DoesUserExistByIdQueryView, HasAccessBatchQueryView, HasAccessQueryView,
+        ListInstanceAccessQueryView,
+    };
+
+    #[test]
+    fn test_does_user_exist_by_id_filters_on_tenant() {
+        let view = DoesUserExistByIdQueryView::new(1);
+        assert!(!view.get_request().contains("tenant_id"));
+
+        let view = DoesUserExistByIdQueryView::new(1).with_tenant(Some("lyon"));
+        assert_eq!(view.get_tenant_id(), Some("lyon"));
+        assert!(view.get_request().contains("tenant_id = $2"));
+    }
+
+    #[test]
+    fn test_access_queries_filter_on_tenant() {
+        let view = HasAccessQueryView::new(1, "document", "read", 3).with_tenant(None);
+        assert_eq!(
+            view.get_request(),
+            "SELECT check_access($1, $2, $3, $4::int)"
+        );
+
+        let view = HasAccessQueryView::new(1, "document", "read", 3).with_tenant(Some("lyon"));
+        let request = view.get_request();
+        assert!(request.contains("tenant_id = $5"));
+        assert!(request.contains("instance_id = ($4::int)::text"));
+        assert!(request.contains("check_access($1, $2, $3, $4::int)"));
+
+        // Vérification globale : seule la commune de l'utilisateur est contrôlée
+        let view = HasAccessQueryView::new_global(1, "document", "read").with_tenant(Some("lyon"));
+        assert!(!view.get_request().contains("tenant_resources"));
+
+        let view =
+            HasAccessBatchQueryView::new(1, "document", "read", [1, 2]).with_tenant(Some("lyon"));
+        let request = view.get_request();
+        assert!(request.contains("tenant_id = $5"));
+        assert!(request.contains("tr.instance_id = (ids.id::int)::text"));
+
+        let view =
+            ListInstanceAccessQueryView::new_global("document", "read").with_tenant(Some("lyon"));
+        assert!(view.get_request().contains("u.tenant_id = $4"));
+        assert!(!view.get_request().contains("tenant_resources"));
+
+        let view =
+            ListInstanceAccessQueryView::new("document", "read", 3).with_tenant(Some("lyon"));
+        assert!(view
+            .get_request()
+            .contains("tr.instance_id = ($3::int)::text AND tr.tenant_id = $4"));
+    }
+}